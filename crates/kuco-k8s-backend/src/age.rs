@@ -0,0 +1,34 @@
+use k8s_openapi::chrono::{DateTime, Utc};
+
+/// Format the time elapsed since `timestamp` the way kubectl does ("45s", "12m", "3h", "9d").
+pub fn format_age(timestamp: DateTime<Utc>) -> String {
+    format_duration_secs((Utc::now() - timestamp).num_seconds())
+}
+
+/// Format a duration in seconds using its single largest unit.
+pub fn format_duration_secs(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        s if s < 120 => format!("{}s", s),
+        s if s < 3600 * 2 => format!("{}m", s / 60),
+        s if s < 86400 * 2 => format!("{}h", s / 3600),
+        s if s < 86400 * 365 => format!("{}d", s / 86400),
+        s => format!("{}y", s / (86400 * 365)),
+    }
+}
+
+/// Parse a duration formatted by [`format_duration_secs`] back into (approximate) seconds.
+pub fn parse_duration_secs(duration: &str) -> Option<i64> {
+    let duration = duration.trim();
+    let unit = duration.chars().last()?;
+    let value: i64 = duration[..duration.len() - unit.len_utf8()].parse().ok()?;
+
+    match unit {
+        's' => Some(value),
+        'm' => Some(value * 60),
+        'h' => Some(value * 3600),
+        'd' => Some(value * 86400),
+        'y' => Some(value * 86400 * 365),
+        _ => None,
+    }
+}
@@ -0,0 +1,275 @@
+use k8s_openapi::api::autoscaling::v2::{
+    CrossVersionObjectReference, HorizontalPodAutoscaler, MetricSpec, MetricStatus, MetricTarget,
+    MetricValueStatus,
+};
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, ListParams, Patch, PatchParams},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::KucoBackendError;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct HpaMetric {
+    /// e.g. "cpu", "container/app memory", "pods requests_per_second".
+    pub name: String,
+    pub current: String,
+    pub target: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct HpaCondition {
+    pub type_: String,
+    pub status: String,
+    pub reason: String,
+    pub message: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct HpaInfo {
+    pub name: String,
+    pub target_kind: String,
+    pub target_name: String,
+    pub min_replicas: i32,
+    pub max_replicas: i32,
+    pub current_replicas: i32,
+    pub desired_replicas: i32,
+    pub metrics: Vec<HpaMetric>,
+    pub conditions: Vec<HpaCondition>,
+}
+
+impl HpaInfo {
+    /// Whether the condition `type_` (AbleToScale, ScalingActive, ScalingLimited) is "True".
+    pub fn condition_is(&self, type_: &str) -> bool {
+        self.conditions
+            .iter()
+            .any(|c| c.type_ == type_ && c.status == "True")
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct HpaData {
+    pub list: Vec<HpaInfo>,
+}
+
+impl HpaData {
+    pub async fn update(
+        &mut self,
+        client: Client,
+        namespace: &str,
+    ) -> Result<(), KucoBackendError> {
+        let hpas: Api<HorizontalPodAutoscaler> = Api::namespaced(client, namespace);
+        let hpa_list = hpas.list(&ListParams::default()).await?;
+
+        self.list = hpa_list.items.into_iter().map(hpa_info).collect();
+
+        Ok(())
+    }
+}
+
+fn hpa_info(hpa: HorizontalPodAutoscaler) -> HpaInfo {
+    let name = hpa.name_any();
+    let spec = hpa.spec.unwrap_or_default();
+    let status = hpa.status.unwrap_or_default();
+
+    // Pair every metric in the spec with its reported status, if the controller has one yet.
+    let current_metrics = status.current_metrics.unwrap_or_default();
+    let metrics = spec
+        .metrics
+        .unwrap_or_default()
+        .iter()
+        .map(|metric| {
+            let name = metric_spec_name(metric);
+            let current = current_metrics
+                .iter()
+                .find(|m| metric_status_name(m) == name)
+                .and_then(metric_status_value)
+                .map(|value| format_metric_value(&value))
+                .unwrap_or_else(|| "<unknown>".to_owned());
+
+            HpaMetric {
+                target: metric_spec_target(metric)
+                    .map(format_metric_target)
+                    .unwrap_or_default(),
+                current,
+                name,
+            }
+        })
+        .collect();
+
+    let conditions = status
+        .conditions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| HpaCondition {
+            type_: c.type_,
+            status: c.status,
+            reason: c.reason.unwrap_or_default(),
+            message: c.message.unwrap_or_default(),
+        })
+        .collect();
+
+    HpaInfo {
+        name,
+        target_kind: spec.scale_target_ref.kind,
+        target_name: spec.scale_target_ref.name,
+        min_replicas: spec.min_replicas.unwrap_or(1),
+        max_replicas: spec.max_replicas,
+        current_replicas: status.current_replicas.unwrap_or_default(),
+        desired_replicas: status.desired_replicas,
+        metrics,
+        conditions,
+    }
+}
+
+fn metric_spec_name(metric: &MetricSpec) -> String {
+    metric_name(
+        &metric.type_,
+        metric.resource.as_ref().map(|m| m.name.as_str()),
+        metric
+            .container_resource
+            .as_ref()
+            .map(|m| (m.container.as_str(), m.name.as_str())),
+        metric.pods.as_ref().map(|m| m.metric.name.as_str()),
+        metric
+            .object
+            .as_ref()
+            .map(|m| (&m.described_object, m.metric.name.as_str())),
+        metric.external.as_ref().map(|m| m.metric.name.as_str()),
+    )
+}
+
+fn metric_status_name(metric: &MetricStatus) -> String {
+    metric_name(
+        &metric.type_,
+        metric.resource.as_ref().map(|m| m.name.as_str()),
+        metric
+            .container_resource
+            .as_ref()
+            .map(|m| (m.container.as_str(), m.name.as_str())),
+        metric.pods.as_ref().map(|m| m.metric.name.as_str()),
+        metric
+            .object
+            .as_ref()
+            .map(|m| (&m.described_object, m.metric.name.as_str())),
+        metric.external.as_ref().map(|m| m.metric.name.as_str()),
+    )
+}
+
+// Specs and statuses describe a metric with the same sources, so both name it the same way and
+// can be paired by name.
+fn metric_name(
+    type_: &str,
+    resource: Option<&str>,
+    container_resource: Option<(&str, &str)>,
+    pods: Option<&str>,
+    object: Option<(&CrossVersionObjectReference, &str)>,
+    external: Option<&str>,
+) -> String {
+    if let Some(name) = resource {
+        name.to_owned()
+    } else if let Some((container, name)) = container_resource {
+        format!("{}/{}", container, name)
+    } else if let Some(name) = pods {
+        format!("pods {}", name)
+    } else if let Some((described, name)) = object {
+        format!(
+            "{}/{} {}",
+            described.kind.to_lowercase(),
+            described.name,
+            name
+        )
+    } else if let Some(name) = external {
+        format!("external {}", name)
+    } else {
+        type_.to_owned()
+    }
+}
+
+fn metric_spec_target(metric: &MetricSpec) -> Option<&MetricTarget> {
+    metric
+        .resource
+        .as_ref()
+        .map(|m| &m.target)
+        .or(metric.container_resource.as_ref().map(|m| &m.target))
+        .or(metric.pods.as_ref().map(|m| &m.target))
+        .or(metric.object.as_ref().map(|m| &m.target))
+        .or(metric.external.as_ref().map(|m| &m.target))
+}
+
+fn metric_status_value(metric: &MetricStatus) -> Option<MetricValueStatus> {
+    metric
+        .resource
+        .as_ref()
+        .map(|m| m.current.clone())
+        .or(metric
+            .container_resource
+            .as_ref()
+            .map(|m| m.current.clone()))
+        .or(metric.pods.as_ref().map(|m| m.current.clone()))
+        .or(metric.object.as_ref().map(|m| m.current.clone()))
+        .or(metric.external.as_ref().map(|m| m.current.clone()))
+}
+
+fn format_metric_target(target: &MetricTarget) -> String {
+    if let Some(utilization) = target.average_utilization {
+        format!("{}%", utilization)
+    } else if let Some(value) = &target.average_value {
+        format!("{} (avg)", value.0)
+    } else if let Some(value) = &target.value {
+        value.0.clone()
+    } else {
+        String::new()
+    }
+}
+
+fn format_metric_value(value: &MetricValueStatus) -> String {
+    if let Some(utilization) = value.average_utilization {
+        format!("{}%", utilization)
+    } else if let Some(value) = &value.average_value {
+        value.0.clone()
+    } else if let Some(value) = &value.value {
+        value.0.clone()
+    } else {
+        "<unknown>".to_owned()
+    }
+}
+
+/// Update an HPA's replica bounds.
+pub async fn set_hpa_replicas(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    min_replicas: i32,
+    max_replicas: i32,
+) -> Result<(), KucoBackendError> {
+    let hpas: Api<HorizontalPodAutoscaler> = Api::namespaced(client, namespace);
+    let patch = serde_json::json!({
+        "spec": { "minReplicas": min_replicas, "maxReplicas": max_replicas }
+    });
+
+    hpas.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+
+    Ok(())
+}
+
+/// Find the HPA whose scale target is `kind`/`name`, if any.
+pub async fn find_hpa_for(
+    client: Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<Option<HpaInfo>, KucoBackendError> {
+    let mut hpas = HpaData::default();
+    hpas.update(client, namespace).await?;
+
+    Ok(hpas
+        .list
+        .into_iter()
+        .find(|hpa| hpa.target_kind == kind && hpa.target_name == name))
+}
@@ -1,4 +1,9 @@
-use kube::Client;
+use std::path::{Path, PathBuf};
+
+use kube::{
+    Client, Config,
+    config::{KubeConfigOptions, Kubeconfig},
+};
 
 use crate::error::KucoBackendError;
 use crate::get_client;
@@ -6,12 +11,75 @@ use crate::get_client;
 #[derive(Default, Clone)]
 pub struct KubeContext {
     pub client: Option<Client>,
+    /// The kubeconfig context the client was built from, `None` for the default one.
+    pub name: Option<String>,
+    /// The kubeconfig file contexts are read from, `None` for the default lookup.
+    pub kubeconfig: Option<PathBuf>,
 }
 
 impl KubeContext {
     pub async fn init_context(&mut self) -> Result<(), KucoBackendError> {
         self.client = Some(get_client().await?);
+        self.name = Kubeconfig::read().ok().and_then(|k| k.current_context);
+
+        Ok(())
+    }
+
+    /// Build a client from the kubeconfig at `kubeconfig` (or the default one), for `context` or
+    /// the file's current context.
+    pub async fn init_context_from(
+        &mut self,
+        kubeconfig: Option<PathBuf>,
+        context: Option<String>,
+    ) -> Result<(), KucoBackendError> {
+        let config_file = read_kubeconfig(kubeconfig.as_deref())?;
+        let name = context.or_else(|| config_file.current_context.clone());
+        if let Some(name) = &name {
+            if !config_file.contexts.iter().any(|c| &c.name == name) {
+                return Err(KucoBackendError::ContextNotFound(name.clone()));
+            }
+        }
+
+        let options = KubeConfigOptions {
+            context: name.clone(),
+            ..Default::default()
+        };
+        let config = Config::from_custom_kubeconfig(config_file, &options).await?;
+        self.client = Some(Client::try_from(config)?);
+        self.name = name;
+        self.kubeconfig = kubeconfig;
 
         Ok(())
     }
+
+    /// Build a client for the kubeconfig context `name`, keeping the current one on failure.
+    pub async fn init_named_context(&mut self, name: &str) -> Result<(), KucoBackendError> {
+        let options = KubeConfigOptions {
+            context: Some(name.to_owned()),
+            ..Default::default()
+        };
+        let config_file = read_kubeconfig(self.kubeconfig.as_deref())?;
+        let config = Config::from_custom_kubeconfig(config_file, &options).await?;
+        self.client = Some(Client::try_from(config)?);
+        self.name = Some(name.to_owned());
+
+        Ok(())
+    }
+}
+
+fn read_kubeconfig(path: Option<&Path>) -> Result<Kubeconfig, KucoBackendError> {
+    Ok(match path {
+        Some(path) => Kubeconfig::read_from(path)?,
+        None => Kubeconfig::read()?,
+    })
+}
+
+/// Names of every context in the kubeconfig, and the one currently selected there.
+pub fn list_contexts(
+    kubeconfig: Option<&Path>,
+) -> Result<(Vec<String>, Option<String>), KucoBackendError> {
+    let kubeconfig = read_kubeconfig(kubeconfig)?;
+    let names = kubeconfig.contexts.into_iter().map(|c| c.name).collect();
+
+    Ok((names, kubeconfig.current_context))
 }
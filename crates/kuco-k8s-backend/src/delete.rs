@@ -0,0 +1,287 @@
+use std::time::Duration;
+
+use k8s_openapi::{
+    api::{apps::v1::ReplicaSet, core::v1::Pod},
+    apimachinery::pkg::apis::meta::v1::OwnerReference,
+};
+use kube::{
+    Client, ResourceExt,
+    api::{Api, DeleteParams, ListParams, PropagationPolicy},
+};
+use tokio::{sync::mpsc::UnboundedSender, time::Instant};
+
+use crate::error::KucoBackendError;
+use crate::restart::RESTARTABLE_KINDS;
+
+const DELETION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait for the pod to go, on top of its grace period.
+const TERMINATION_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long to wait for the owner to create a replacement once the pod is gone.
+const REPLACEMENT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Grace period assumed when the pod does not set one, as the API server does.
+const DEFAULT_GRACE_PERIOD_SECS: i64 = 30;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Propagation {
+    #[default]
+    Background,
+    Foreground,
+    Orphan,
+}
+
+impl Propagation {
+    pub fn next(&self) -> Self {
+        match self {
+            Propagation::Background => Propagation::Foreground,
+            Propagation::Foreground => Propagation::Orphan,
+            Propagation::Orphan => Propagation::Background,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Propagation::Background => "Background",
+            Propagation::Foreground => "Foreground",
+            Propagation::Orphan => "Orphan",
+        }
+    }
+
+    fn policy(&self) -> PropagationPolicy {
+        match self {
+            Propagation::Background => PropagationPolicy::Background,
+            Propagation::Foreground => PropagationPolicy::Foreground,
+            Propagation::Orphan => PropagationPolicy::Orphan,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DeleteOptions {
+    /// Seconds the pod gets to shut down, instead of its own terminationGracePeriodSeconds.
+    pub grace_period: Option<u32>,
+    /// Delete with gracePeriodSeconds=0, killing the containers without a graceful shutdown.
+    pub force: bool,
+    pub propagation: Propagation,
+}
+
+impl DeleteOptions {
+    pub fn grace_period_seconds(&self) -> Option<u32> {
+        if self.force {
+            Some(0)
+        } else {
+            self.grace_period
+        }
+    }
+}
+
+/// The controller owning a pod, which recreates it once deleted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodOwner {
+    pub kind: String,
+    pub name: String,
+    pub uid: String,
+    /// The Deployment owning the ReplicaSet, when the owner is one.
+    pub deployment: Option<String>,
+}
+
+impl PodOwner {
+    /// Kind and name of the workload a rollout restart would replace the pod through.
+    pub fn workload(&self) -> Option<(&str, &str)> {
+        match &self.deployment {
+            Some(deployment) => Some(("Deployment", deployment.as_str())),
+            None if RESTARTABLE_KINDS.contains(&self.kind.as_str()) => {
+                Some((self.kind.as_str(), self.name.as_str()))
+            }
+            None => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeleteStatus {
+    Deleting,
+    Terminating,
+    Gone,
+    /// The owner created this pod in place of the deleted one.
+    Replaced(String),
+    /// The owner did not create a replacement within the timeout.
+    NotReplaced,
+    /// The owner's pods could not be listed before the delete, so a replacement could not be
+    /// told apart from the pods that were already there.
+    ReplacementUnknown,
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct DeleteProgress {
+    pub namespace: String,
+    pub name: String,
+    pub status: DeleteStatus,
+}
+
+/// The controller of a pod, if it has one. For ReplicaSets the Deployment above is looked up
+/// as well, since that is the name people know.
+pub async fn get_pod_owner(
+    client: Client,
+    namespace: &str,
+    name: &str,
+) -> Result<Option<PodOwner>, KucoBackendError> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod = pods.get(name).await?;
+    let Some(owner) = controller_of(&pod) else {
+        return Ok(None);
+    };
+
+    let mut deployment = None;
+    if owner.kind == "ReplicaSet" {
+        let replica_sets: Api<ReplicaSet> = Api::namespaced(client, namespace);
+        if let Some(replica_set) = replica_sets.get_opt(&owner.name).await? {
+            deployment = replica_set
+                .owner_references()
+                .iter()
+                .find(|o| o.controller.unwrap_or(false) && o.kind == "Deployment")
+                .map(|o| o.name.clone());
+        }
+    }
+
+    Ok(Some(PodOwner {
+        kind: owner.kind.clone(),
+        name: owner.name.clone(),
+        uid: owner.uid.clone(),
+        deployment,
+    }))
+}
+
+fn controller_of(pod: &Pod) -> Option<&OwnerReference> {
+    pod.owner_references()
+        .iter()
+        .find(|owner| owner.controller.unwrap_or(false))
+}
+
+/// Delete a pod and follow it until it is gone, and then until its controller has created a
+/// replacement, reporting each step on `progress`.
+///
+/// Only a refused delete is returned as an error; once the API server accepted it, problems
+/// while waiting are reported as [`DeleteStatus::Failed`].
+pub async fn delete_pod(
+    client: Client,
+    namespace: &str,
+    name: &str,
+    options: DeleteOptions,
+    progress: &UnboundedSender<DeleteProgress>,
+) -> Result<(), KucoBackendError> {
+    let report = |status: DeleteStatus| {
+        let _ = progress.send(DeleteProgress {
+            namespace: namespace.to_owned(),
+            name: name.to_owned(),
+            status,
+        });
+    };
+
+    let api: Api<Pod> = Api::namespaced(client, namespace);
+    report(DeleteStatus::Deleting);
+    let pod = match api.get(name).await {
+        Ok(pod) => pod,
+        Err(e) => {
+            report(DeleteStatus::Failed(e.to_string()));
+            return Err(e.into());
+        }
+    };
+    let uid = pod.uid();
+    let owner_uid = controller_of(&pod).map(|owner| owner.uid.clone());
+    // Pods of the same owner that exist already cannot be the replacement. Without them, any
+    // of those pods would pass for one.
+    let siblings: Option<Vec<String>> = match &owner_uid {
+        Some(owner_uid) => owned_pods(&api, owner_uid)
+            .await
+            .ok()
+            .map(|pods| pods.into_iter().map(|(_, uid)| uid).collect()),
+        None => Some(Vec::new()),
+    };
+
+    let params = DeleteParams {
+        grace_period_seconds: options.grace_period_seconds(),
+        propagation_policy: Some(options.propagation.policy()),
+        ..Default::default()
+    };
+    if let Err(e) = api.delete(name, &params).await {
+        report(DeleteStatus::Failed(e.to_string()));
+        return Err(e.into());
+    }
+
+    report(DeleteStatus::Terminating);
+    let grace_period = options
+        .grace_period_seconds()
+        .map(i64::from)
+        .or_else(|| pod.spec.as_ref()?.termination_grace_period_seconds)
+        .unwrap_or(DEFAULT_GRACE_PERIOD_SECS);
+    let deadline =
+        Instant::now() + Duration::from_secs(grace_period.max(0) as u64) + TERMINATION_TIMEOUT;
+    loop {
+        match api.get_opt(name).await {
+            Ok(None) => break,
+            // A pod with the same name but a new UID is a replacement (e.g. StatefulSet).
+            Ok(Some(current)) if current.uid() != uid => {
+                report(DeleteStatus::Gone);
+                report(DeleteStatus::Replaced(name.to_owned()));
+                return Ok(());
+            }
+            Ok(Some(_)) => {}
+            Err(e) => {
+                report(DeleteStatus::Failed(e.to_string()));
+                return Ok(());
+            }
+        }
+
+        if Instant::now() >= deadline {
+            report(DeleteStatus::Failed(
+                "timed out waiting for pod to terminate".into(),
+            ));
+            return Ok(());
+        }
+        tokio::time::sleep(DELETION_POLL_INTERVAL).await;
+    }
+    report(DeleteStatus::Gone);
+
+    let Some(owner_uid) = owner_uid else {
+        return Ok(());
+    };
+    let Some(siblings) = siblings else {
+        report(DeleteStatus::ReplacementUnknown);
+        return Ok(());
+    };
+    let deadline = Instant::now() + REPLACEMENT_TIMEOUT;
+    loop {
+        match owned_pods(&api, &owner_uid).await {
+            Ok(pods) => {
+                let replacement = pods.into_iter().find(|(_, uid)| !siblings.contains(uid));
+                if let Some((replacement, _)) = replacement {
+                    report(DeleteStatus::Replaced(replacement));
+                    return Ok(());
+                }
+            }
+            Err(e) => {
+                report(DeleteStatus::Failed(e.to_string()));
+                return Ok(());
+            }
+        }
+
+        if Instant::now() >= deadline {
+            report(DeleteStatus::NotReplaced);
+            return Ok(());
+        }
+        tokio::time::sleep(DELETION_POLL_INTERVAL).await;
+    }
+}
+
+// Names and UIDs of the pods controlled by the owner with `owner_uid`.
+async fn owned_pods(api: &Api<Pod>, owner_uid: &str) -> Result<Vec<(String, String)>, kube::Error> {
+    let pods = api.list(&ListParams::default()).await?;
+
+    Ok(pods
+        .items
+        .iter()
+        .filter(|pod| controller_of(pod).is_some_and(|owner| owner.uid == owner_uid))
+        .map(|pod| (pod.name_any(), pod.uid().unwrap_or_default()))
+        .collect())
+}
@@ -0,0 +1,110 @@
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+
+use kube::{
+    Client,
+    api::{Api, ApiResource},
+    discovery::{Discovery, Scope, verbs},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::KucoBackendError;
+
+/// A listable resource kind discovered from the API server, built-in or custom.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ApiKind {
+    pub group: String,
+    pub version: String,
+    pub api_version: String,
+    pub kind: String,
+    pub plural: String,
+    pub namespaced: bool,
+}
+
+impl ApiKind {
+    /// The `plural.group` name used by `kubectl api-resources` (just `plural` for core kinds).
+    pub fn key(&self) -> String {
+        if self.group.is_empty() {
+            self.plural.clone()
+        } else {
+            format!("{}.{}", self.plural, self.group)
+        }
+    }
+
+    pub fn api_resource(&self) -> ApiResource {
+        ApiResource {
+            group: self.group.clone(),
+            version: self.version.clone(),
+            api_version: self.api_version.clone(),
+            kind: self.kind.clone(),
+            plural: self.plural.clone(),
+        }
+    }
+}
+
+/// One of a CRD's `additionalPrinterColumns`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct PrinterColumn {
+    pub name: String,
+    pub json_path: String,
+    pub type_: String,
+}
+
+/// Discover every resource kind the server can list, at each group's preferred version.
+pub async fn discover_kinds(client: Client) -> Result<Vec<ApiKind>, KucoBackendError> {
+    let discovery = Discovery::new(client).run().await?;
+
+    let mut kinds: Vec<ApiKind> = Vec::new();
+    for group in discovery.groups() {
+        for (ar, caps) in group.recommended_resources() {
+            if !caps.supports_operation(verbs::LIST) {
+                continue;
+            }
+
+            kinds.push(ApiKind {
+                group: ar.group,
+                version: ar.version,
+                api_version: ar.api_version,
+                kind: ar.kind,
+                plural: ar.plural,
+                namespaced: caps.scope == Scope::Namespaced,
+            });
+        }
+    }
+    kinds.sort_by_key(|k| k.key());
+
+    Ok(kinds)
+}
+
+/// Fetch the default-priority printer columns a CRD declares for the discovered version.
+///
+/// Built-in kinds have no CRD, so they (and CRDs without printer columns) yield an empty list.
+pub async fn get_printer_columns(
+    client: Client,
+    kind: &ApiKind,
+) -> Result<Vec<PrinterColumn>, KucoBackendError> {
+    if kind.group.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let crds: Api<CustomResourceDefinition> = Api::all(client);
+    let Some(crd) = crds.get_opt(&kind.key()).await? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(crd
+        .spec
+        .versions
+        .into_iter()
+        .find(|v| v.name == kind.version)
+        .and_then(|v| v.additional_printer_columns)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.priority.unwrap_or(0) == 0)
+        .map(|c| PrinterColumn {
+            name: c.name,
+            json_path: c.json_path,
+            type_: c.type_,
+        })
+        .collect())
+}
@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use k8s_openapi::api::core::v1::Pod;
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, EvictParams, ListParams},
+};
+use tokio::{sync::mpsc::UnboundedSender, task::JoinSet, time::Instant};
+
+use crate::error::KucoBackendError;
+use crate::nodes::set_unschedulable;
+
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+const EVICTION_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const DELETION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug)]
+pub struct DrainOptions {
+    /// How long to keep retrying evictions and waiting for pods to terminate.
+    pub timeout: Duration,
+    /// Also evict pods that are not managed by a controller, which will not be recreated.
+    pub force: bool,
+}
+
+impl Default for DrainOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(120),
+            force: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrainStatus {
+    Skipped(String),
+    Evicting,
+    /// A PodDisruptionBudget refused the eviction; it will be retried until the timeout.
+    Blocked(String),
+    Terminating,
+    Evicted,
+    Failed(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum DrainProgress {
+    Pod {
+        namespace: String,
+        name: String,
+        status: DrainStatus,
+    },
+    /// The drain has finished; carries the error if it could not run to completion.
+    Done(Option<String>),
+}
+
+/// Cordon a node and evict every pod on it through the Eviction subresource.
+///
+/// DaemonSet and mirror pods are skipped, evictions refused by a PodDisruptionBudget are
+/// retried until `options.timeout`, and per-pod progress is reported on `progress`. Without
+/// `options.force`, pods not managed by a controller refuse the drain before the node is
+/// cordoned. A [`DrainProgress::Done`] is always sent last, even when the drain fails early.
+pub async fn drain_node(
+    client: Client,
+    node_name: &str,
+    options: DrainOptions,
+    progress: UnboundedSender<DrainProgress>,
+) -> Result<(), KucoBackendError> {
+    let result = run_drain(client, node_name, options, &progress).await;
+    let _ = progress.send(DrainProgress::Done(
+        result.as_ref().err().map(ToString::to_string),
+    ));
+
+    result
+}
+
+async fn run_drain(
+    client: Client,
+    node_name: &str,
+    options: DrainOptions,
+    progress: &UnboundedSender<DrainProgress>,
+) -> Result<(), KucoBackendError> {
+    let pods: Api<Pod> = Api::all(client.clone());
+    let lp = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+    let pod_list = pods.list(&lp).await?;
+
+    // Like `kubectl drain`, refuse the whole drain before touching the node when any pod would
+    // block it, rather than cordoning and evicting everything around it.
+    let blocking: Vec<String> = pod_list
+        .items
+        .iter()
+        .filter(|pod| {
+            matches!(
+                skip_reason(pod, options.force),
+                Some(DrainStatus::Failed(_))
+            )
+        })
+        .map(|pod| format!("{}/{}", pod.namespace().unwrap_or_default(), pod.name_any()))
+        .collect();
+    if !blocking.is_empty() {
+        return Err(KucoBackendError::DrainBlocked(blocking.join(", ")));
+    }
+
+    set_unschedulable(client.clone(), node_name, true).await?;
+
+    let deadline = Instant::now() + options.timeout;
+    let mut evictions = JoinSet::new();
+
+    for pod in pod_list.items {
+        let namespace = pod.namespace().unwrap_or_default();
+        let name = pod.name_any();
+
+        if let Some(reason) = skip_reason(&pod, options.force) {
+            let _ = progress.send(DrainProgress::Pod {
+                namespace,
+                name,
+                status: reason,
+            });
+            continue;
+        }
+
+        let api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+        let uid = pod.uid();
+        let progress = progress.clone();
+        evictions.spawn(async move {
+            let status = evict_pod(&api, &namespace, &name, uid, deadline, &progress).await;
+            let _ = progress.send(DrainProgress::Pod {
+                namespace,
+                name,
+                status,
+            });
+        });
+    }
+
+    while evictions.join_next().await.is_some() {}
+
+    Ok(())
+}
+
+// Mirrors `kubectl drain` filtering: DaemonSet and mirror pods are never evicted, and pods
+// without a controller are only evicted with force since nothing will recreate them.
+fn skip_reason(pod: &Pod, force: bool) -> Option<DrainStatus> {
+    if pod.annotations().contains_key(MIRROR_POD_ANNOTATION) {
+        return Some(DrainStatus::Skipped("mirror pod".into()));
+    }
+
+    let controller = pod
+        .owner_references()
+        .iter()
+        .find(|owner| owner.controller.unwrap_or(false));
+
+    match controller {
+        Some(owner) if owner.kind == "DaemonSet" => {
+            Some(DrainStatus::Skipped("DaemonSet-managed".into()))
+        }
+        None if !force => Some(DrainStatus::Failed(
+            "not managed by a controller (use force)".into(),
+        )),
+        _ => None,
+    }
+}
+
+async fn evict_pod(
+    api: &Api<Pod>,
+    namespace: &str,
+    name: &str,
+    uid: Option<String>,
+    deadline: Instant,
+    progress: &UnboundedSender<DrainProgress>,
+) -> DrainStatus {
+    let report = |status: DrainStatus| {
+        let _ = progress.send(DrainProgress::Pod {
+            namespace: namespace.to_owned(),
+            name: name.to_owned(),
+            status,
+        });
+    };
+
+    report(DrainStatus::Evicting);
+    loop {
+        match api.evict(name, &EvictParams::default()).await {
+            Ok(_) => break,
+            Err(kube::Error::Api(e)) if e.code == 404 => return DrainStatus::Evicted,
+            Err(kube::Error::Api(e)) if e.code == 429 => {
+                if Instant::now() + EVICTION_RETRY_INTERVAL > deadline {
+                    return DrainStatus::Failed(format!(
+                        "timed out waiting on disruption budget: {}",
+                        e.message
+                    ));
+                }
+                report(DrainStatus::Blocked(e.message));
+                tokio::time::sleep(EVICTION_RETRY_INTERVAL).await;
+            }
+            Err(e) => return DrainStatus::Failed(e.to_string()),
+        }
+    }
+
+    report(DrainStatus::Terminating);
+    loop {
+        match api.get_opt(name).await {
+            Ok(None) => return DrainStatus::Evicted,
+            // A pod with the same name but a new UID is a replacement (e.g. StatefulSet).
+            Ok(Some(pod)) if pod.uid() != uid => return DrainStatus::Evicted,
+            Ok(Some(_)) => {}
+            Err(e) => return DrainStatus::Failed(e.to_string()),
+        }
+
+        if Instant::now() >= deadline {
+            return DrainStatus::Failed("timed out waiting for pod to terminate".into());
+        }
+        tokio::time::sleep(DELETION_POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,319 @@
+use k8s_openapi::chrono::{DateTime, Utc};
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, DynamicObject, ListParams},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::age::format_age;
+use crate::discovery::{ApiKind, PrinterColumn, get_printer_columns};
+use crate::error::KucoBackendError;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct DynamicRow {
+    pub name: String,
+    pub namespace: Option<String>,
+    /// One cell per entry in [`DynamicData::columns`], after the name.
+    pub cells: Vec<String>,
+}
+
+/// Objects of any discovered kind, with columns derived from the kind's printer columns.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct DynamicData {
+    pub columns: Vec<String>,
+    pub rows: Vec<DynamicRow>,
+    pub names: Vec<String>,
+}
+
+impl DynamicData {
+    pub async fn update(
+        &mut self,
+        client: Client,
+        kind: &ApiKind,
+        namespace: &str,
+    ) -> Result<(), KucoBackendError> {
+        // Printer columns are a nicety; lacking RBAC on CRDs should not block the listing.
+        let printer_columns = get_printer_columns(client.clone(), kind)
+            .await
+            .unwrap_or_default();
+
+        let ar = kind.api_resource();
+        let api: Api<DynamicObject> = if kind.namespaced {
+            Api::namespaced_with(client, namespace, &ar)
+        } else {
+            Api::all_with(client, &ar)
+        };
+        let object_list = api.list(&ListParams::default()).await?;
+
+        let has_age = printer_columns
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case("age"));
+
+        let mut columns: Vec<String> = printer_columns
+            .iter()
+            .map(|c| c.name.to_uppercase())
+            .collect();
+        if !has_age {
+            columns.push("AGE".to_owned());
+        }
+
+        let mut rows: Vec<DynamicRow> = Vec::new();
+        for object in object_list.items {
+            let creation = object.creation_timestamp();
+            let value = serde_json::to_value(&object).unwrap_or(Value::Null);
+
+            let mut cells: Vec<String> = printer_columns
+                .iter()
+                .map(|c| format_column(&value, c))
+                .collect();
+            if !has_age {
+                cells.push(creation.map(|t| format_age(t.0)).unwrap_or_default());
+            }
+
+            rows.push(DynamicRow {
+                name: object.name_any(),
+                namespace: object.namespace(),
+                cells,
+            });
+        }
+
+        self.names = rows.iter().map(|r| r.name.clone()).collect();
+        self.columns = columns;
+        self.rows = rows;
+
+        Ok(())
+    }
+}
+
+/// Fetch one object of any kind as the API server returns it. `namespace` is ignored for
+/// cluster-scoped kinds.
+pub async fn get_object(
+    client: Client,
+    kind: &ApiKind,
+    namespace: &str,
+    name: &str,
+) -> Result<DynamicObject, KucoBackendError> {
+    let ar = kind.api_resource();
+    let api: Api<DynamicObject> = if kind.namespaced {
+        Api::namespaced_with(client, namespace, &ar)
+    } else {
+        Api::all_with(client, &ar)
+    };
+
+    Ok(api.get(name).await?)
+}
+
+fn format_column(object: &Value, column: &PrinterColumn) -> String {
+    let values = eval_json_path(object, &column.json_path);
+
+    values
+        .iter()
+        .map(|v| match (column.type_.as_str(), v) {
+            ("date", Value::String(s)) => s
+                .parse::<DateTime<Utc>>()
+                .map(format_age)
+                .unwrap_or_else(|_| s.clone()),
+            (_, Value::String(s)) => s.clone(),
+            (_, other) => other.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Evaluate the subset of JSONPath used by `additionalPrinterColumns`: `.field` access,
+/// `[n]` indices, `[*]` wildcards and `[?(@.field=="value")]` filters.
+pub fn eval_json_path<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+    let path = path.trim().trim_start_matches('{').trim_end_matches('}');
+    let chars: Vec<char> = path.chars().collect();
+
+    let mut current: Vec<&Value> = vec![value];
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                let field: String = chars[start..i].iter().collect();
+                if field.is_empty() {
+                    continue;
+                }
+                current = current.into_iter().filter_map(|v| v.get(&field)).collect();
+            }
+            '[' => {
+                let start = i + 1;
+                let mut in_quotes = false;
+                i = start;
+                while i < chars.len() && (in_quotes || chars[i] != ']') {
+                    if chars[i] == '"' || chars[i] == '\'' {
+                        in_quotes = !in_quotes;
+                    }
+                    i += 1;
+                }
+                let selector: String = chars[start..i.min(chars.len())].iter().collect();
+                i += 1;
+                current = apply_selector(current, selector.trim());
+            }
+            _ => {
+                // Paths are allowed to omit the leading dot.
+                let start = i;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                let field: String = chars[start..i].iter().collect();
+                current = current.into_iter().filter_map(|v| v.get(&field)).collect();
+            }
+        }
+    }
+
+    current
+}
+
+fn apply_selector<'a>(current: Vec<&'a Value>, selector: &str) -> Vec<&'a Value> {
+    if selector == "*" {
+        return current
+            .into_iter()
+            .flat_map(|v| match v {
+                Value::Array(items) => items.iter().collect(),
+                Value::Object(map) => map.values().collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+    }
+
+    if let Ok(index) = selector.parse::<usize>() {
+        return current.into_iter().filter_map(|v| v.get(index)).collect();
+    }
+
+    if let Some(filter) = selector
+        .strip_prefix("?(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let (lhs, expected, negate) = match filter.split_once("!=") {
+            Some((lhs, rhs)) => (lhs, rhs, true),
+            None => match filter.split_once("==") {
+                Some((lhs, rhs)) => (lhs, rhs, false),
+                None => return Vec::new(),
+            },
+        };
+        let lhs = lhs.trim().trim_start_matches('@');
+        let expected = expected.trim().trim_matches(|c| c == '"' || c == '\'');
+
+        return current
+            .into_iter()
+            .filter_map(|v| v.as_array())
+            .flatten()
+            .filter(|item| {
+                let found = eval_json_path(item, lhs).into_iter().any(|v| match v {
+                    Value::String(s) => s == expected,
+                    other => expected.parse::<Value>().is_ok_and(|e| &e == other),
+                });
+                found != negate
+            })
+            .collect();
+    }
+
+    let field = selector.trim_matches(|c| c == '"' || c == '\'');
+    current.into_iter().filter_map(|v| v.get(field)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn pod() -> Value {
+        json!({
+            "metadata": { "name": "web-0", "labels": { "app.kubernetes.io/name": "web" } },
+            "spec": {
+                "containers": [
+                    { "name": "app", "image": "nginx:1.27" },
+                    { "name": "sidecar", "image": "envoy:1.30" }
+                ]
+            },
+            "status": {
+                "phase": "Running",
+                "conditions": [
+                    { "type": "Initialized", "status": "True" },
+                    { "type": "Ready", "status": "False" }
+                ],
+                "containerStatuses": [{ "restartCount": 3 }]
+            }
+        })
+    }
+
+    #[test]
+    fn evaluates_field_access() {
+        let pod = pod();
+        assert_eq!(eval_json_path(&pod, ".status.phase"), vec!["Running"]);
+        assert_eq!(eval_json_path(&pod, "{.status.phase}"), vec!["Running"]);
+        assert_eq!(eval_json_path(&pod, "status.phase"), vec!["Running"]);
+        assert!(eval_json_path(&pod, ".status.missing").is_empty());
+    }
+
+    #[test]
+    fn evaluates_array_indexes_and_wildcards() {
+        let pod = pod();
+        assert_eq!(
+            eval_json_path(&pod, ".spec.containers[1].name"),
+            vec!["sidecar"]
+        );
+        assert_eq!(
+            eval_json_path(&pod, ".spec.containers[*].image"),
+            vec!["nginx:1.27", "envoy:1.30"]
+        );
+        assert_eq!(
+            eval_json_path(&pod, ".status.containerStatuses[0].restartCount"),
+            vec![3]
+        );
+        assert!(eval_json_path(&pod, ".spec.containers[5].name").is_empty());
+    }
+
+    #[test]
+    fn evaluates_filters() {
+        let pod = pod();
+        assert_eq!(
+            eval_json_path(&pod, r#".status.conditions[?(@.type=="Ready")].status"#),
+            vec!["False"]
+        );
+        assert_eq!(
+            eval_json_path(&pod, ".status.conditions[?(@.type == 'Ready')].status"),
+            vec!["False"]
+        );
+        assert_eq!(
+            eval_json_path(&pod, r#".status.conditions[?(@.type!="Ready")].type"#),
+            vec!["Initialized"]
+        );
+        assert_eq!(
+            eval_json_path(
+                &pod,
+                ".status.containerStatuses[?(@.restartCount==3)].restartCount"
+            ),
+            vec![3]
+        );
+        assert!(eval_json_path(&pod, r#".status.conditions[?(@.type=="Nope")]"#).is_empty());
+    }
+
+    #[test]
+    fn evaluates_quoted_keys() {
+        let pod = pod();
+        assert_eq!(
+            eval_json_path(&pod, ".metadata.labels['app.kubernetes.io/name']"),
+            vec!["web"]
+        );
+        assert_eq!(
+            apply_selector(
+                vec![&pod["metadata"]["labels"]],
+                r#""app.kubernetes.io/name""#
+            ),
+            vec!["web"]
+        );
+    }
+}
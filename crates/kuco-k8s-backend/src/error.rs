@@ -4,6 +4,14 @@ use thiserror::Error;
 pub enum KucoBackendError {
     #[error("unable to initialize kubernetes client - please verify you can access the cluster")]
     KubeConnectionError(#[from] kube::Error),
+    #[error("unable to load kubeconfig: {0}")]
+    KubeconfigError(#[from] kube::config::KubeconfigError),
+    #[error("no context '{0}' in kubeconfig")]
+    ContextNotFound(String),
+    #[error("{0} cannot be restarted")]
+    NotRestartable(String),
+    #[error("cannot drain, not managed by a controller (use force): {0}")]
+    DrainBlocked(String),
     #[error("unknown data store error")]
     Unknown,
 }
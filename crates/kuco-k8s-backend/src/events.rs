@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::Event;
+
+use kube::ResourceExt;
+use kube::runtime::{WatchStreamExt, watcher};
+use kube::{
+    Client,
+    api::{Api, ListParams},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::error::KucoBackendError;
+
+/// Kinds whose Events are recorded outside of any single namespace.
+const CLUSTER_SCOPED_KINDS: [&str; 3] = ["Node", "PersistentVolume", "Namespace"];
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct EventInfo {
+    pub uid: String,
+    pub type_: String,
+    pub reason: String,
+    pub message: String,
+    pub count: i32,
+    /// Unix timestamp, in seconds, of the most recent occurrence.
+    pub last_seen: i64,
+    pub object_kind: String,
+    pub object_name: String,
+    pub object_namespace: String,
+}
+
+/// A change to an Event seen while watching.
+#[derive(Clone, Debug)]
+pub enum EventChange {
+    /// Added or modified.
+    Applied(EventInfo),
+    /// Deleted, usually on expiring after the API server's event TTL.
+    Deleted(EventInfo),
+    /// Listed afresh after the watch (re)started, replacing every Event seen before.
+    Relisted(Vec<EventInfo>),
+}
+
+impl EventInfo {
+    /// Events sharing this key are repeats of each other and get aggregated into one row.
+    pub fn key(&self) -> String {
+        format!(
+            "{} {}/{}: {}",
+            self.reason, self.object_kind, self.object_name, self.message
+        )
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.type_ == "Warning"
+    }
+}
+
+impl From<Event> for EventInfo {
+    fn from(event: Event) -> Self {
+        // Newer reporters only fill in `event_time`/`series`, older ones only `last_timestamp`.
+        let last_seen = event
+            .series
+            .as_ref()
+            .and_then(|s| s.last_observed_time.as_ref())
+            .map(|t| t.0)
+            .or(event.last_timestamp.as_ref().map(|t| t.0))
+            .or(event.event_time.as_ref().map(|t| t.0))
+            .or(event.metadata.creation_timestamp.as_ref().map(|t| t.0))
+            .map(|t| t.timestamp())
+            .unwrap_or_default();
+
+        let count = event
+            .series
+            .as_ref()
+            .and_then(|s| s.count)
+            .or(event.count)
+            .unwrap_or(1);
+
+        EventInfo {
+            uid: event.uid().unwrap_or_default(),
+            type_: event.type_.unwrap_or_default(),
+            reason: event.reason.unwrap_or_default(),
+            message: event.message.unwrap_or_default().trim().to_owned(),
+            count,
+            last_seen,
+            object_kind: event.involved_object.kind.unwrap_or_default(),
+            object_name: event.involved_object.name.unwrap_or_default(),
+            object_namespace: event.involved_object.namespace.unwrap_or_default(),
+        }
+    }
+}
+
+/// What to fetch Events for: a namespace, optionally narrowed to a single involved object.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventScope {
+    pub namespace: String,
+    /// `(kind, name)` of the involved object.
+    pub object: Option<(String, String)>,
+}
+
+impl EventScope {
+    fn api(&self, client: Client) -> Api<Event> {
+        match &self.object {
+            Some((kind, _)) if CLUSTER_SCOPED_KINDS.contains(&kind.as_str()) => Api::all(client),
+            _ => Api::namespaced(client, &self.namespace),
+        }
+    }
+
+    fn field_selector(&self) -> Option<String> {
+        self.object.as_ref().map(|(kind, name)| {
+            format!("involvedObject.kind={},involvedObject.name={}", kind, name)
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct EventData {
+    /// Every Event object seen, one per uid.
+    pub raw: Vec<EventInfo>,
+    /// Repeats aggregated by [`EventInfo::key`], most recent first.
+    pub list: Vec<EventInfo>,
+}
+
+impl EventData {
+    pub async fn update(
+        &mut self,
+        client: Client,
+        scope: &EventScope,
+    ) -> Result<(), KucoBackendError> {
+        let mut lp = ListParams::default();
+        if let Some(fields) = scope.field_selector() {
+            lp = lp.fields(&fields);
+        }
+
+        let event_list = scope.api(client).list(&lp).await?;
+        self.raw = event_list.items.into_iter().map(EventInfo::from).collect();
+        self.aggregate();
+
+        Ok(())
+    }
+
+    /// Merge a change received from a watch, replacing any earlier version of an Event with the
+    /// same uid.
+    pub fn merge(&mut self, change: EventChange) {
+        match change {
+            EventChange::Applied(event) => match self.raw.iter_mut().find(|e| e.uid == event.uid) {
+                Some(existing) => *existing = event,
+                None => self.raw.push(event),
+            },
+            EventChange::Deleted(event) => self.raw.retain(|e| e.uid != event.uid),
+            EventChange::Relisted(events) => self.raw = events,
+        }
+        self.aggregate();
+    }
+
+    fn aggregate(&mut self) {
+        let mut aggregated: Vec<EventInfo> = Vec::new();
+        let mut index_by_key: HashMap<String, usize> = HashMap::new();
+        for event in &self.raw {
+            match index_by_key.entry(event.key()) {
+                Entry::Occupied(entry) => {
+                    let existing = &mut aggregated[*entry.get()];
+                    existing.count += event.count;
+                    if event.last_seen > existing.last_seen {
+                        existing.last_seen = event.last_seen;
+                        existing.type_ = event.type_.clone();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(aggregated.len());
+                    aggregated.push(event.clone());
+                }
+            }
+        }
+        aggregated.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+
+        self.list = aggregated;
+    }
+}
+
+/// Fetch the Events recorded for one object, most recent first.
+pub async fn get_object_events(
+    client: Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<Vec<EventInfo>, KucoBackendError> {
+    let mut events = EventData::default();
+    let scope = EventScope {
+        namespace: namespace.to_owned(),
+        object: Some((kind.to_owned(), name.to_owned())),
+    };
+    events.update(client, &scope).await?;
+
+    Ok(events.list)
+}
+
+/// Watch Events in `scope` and forward every added, modified or deleted one on `sender`.
+///
+/// Each (re)list is collected and forwarded whole once it completes, so Events deleted while the
+/// watch was disconnected do not linger. Runs until the receiving side is dropped. Watch errors
+/// are retried with backoff.
+pub async fn watch_events(client: Client, scope: EventScope, sender: UnboundedSender<EventChange>) {
+    let mut config = watcher::Config::default();
+    if let Some(fields) = scope.field_selector() {
+        config = config.fields(&fields);
+    }
+
+    let mut stream = watcher(scope.api(client), config).default_backoff().boxed();
+
+    let mut relisted: Vec<EventInfo> = Vec::new();
+    while let Some(result) = stream.next().await {
+        let change = match result {
+            Ok(watcher::Event::Init) => {
+                relisted.clear();
+                continue;
+            }
+            Ok(watcher::Event::InitApply(event)) => {
+                relisted.push(EventInfo::from(event));
+                continue;
+            }
+            Ok(watcher::Event::InitDone) => EventChange::Relisted(std::mem::take(&mut relisted)),
+            Ok(watcher::Event::Apply(event)) => EventChange::Applied(EventInfo::from(event)),
+            Ok(watcher::Event::Delete(event)) => EventChange::Deleted(EventInfo::from(event)),
+            Err(_) => continue,
+        };
+        if sender.send(change).is_err() {
+            break;
+        }
+    }
+}
@@ -1,9 +1,22 @@
+pub mod age;
+pub mod autoscaling;
 pub mod containers;
 pub mod context;
+pub mod delete;
+pub mod discovery;
+pub mod drain;
+pub mod dynamic;
 pub mod error;
+pub mod events;
 pub mod logs;
 pub mod namespaces;
+pub mod nodes;
 pub mod pods;
+pub mod policy;
+pub mod quantity;
+pub mod restart;
+pub mod scale;
+pub mod storage;
 
 use kube::Client;
 
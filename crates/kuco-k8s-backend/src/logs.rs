@@ -1,13 +1,22 @@
-use k8s_openapi::api::core::v1::Pod;
+use futures::future::join_all;
+use k8s_openapi::{
+    api::core::v1::Pod,
+    chrono::{DateTime, FixedOffset},
+};
 
 use kube::api::LogParams;
-use kube::{Client, api::Api};
+use kube::{Client, ResourceExt, api::Api};
 
 use crate::error::KucoBackendError;
 
+/// Names the container `kubectl logs` picks when none is given.
+const DEFAULT_CONTAINER_ANNOTATION: &str = "kubectl.kubernetes.io/default-container";
+
 #[derive(Clone, Debug)]
 pub struct LogData {
     pub lines: Vec<String>,
+    /// How many lines to fetch from the end of the log.
+    pub tail_lines: i64,
 }
 
 impl Default for LogData {
@@ -18,7 +27,10 @@ impl Default for LogData {
 
 impl LogData {
     pub fn new() -> Self {
-        LogData { lines: Vec::new() }
+        LogData {
+            lines: Vec::new(),
+            tail_lines: 200,
+        }
     }
 
     pub async fn update(
@@ -33,7 +45,7 @@ impl LogData {
         let log_params = LogParams {
             container: Some(container_name.to_string()),
             timestamps: true,
-            tail_lines: Some(200),
+            tail_lines: Some(self.tail_lines),
             ..Default::default()
         };
 
@@ -50,4 +62,70 @@ impl LogData {
 
         Ok(())
     }
+
+    /// Interleave the logs of several pods by timestamp, each line tagged with its pod. Every
+    /// pod's default container is followed, as with `kubectl logs <pod>`.
+    pub async fn update_merged(
+        &mut self,
+        client: Client,
+        sources: &[(String, String)],
+    ) -> Result<(), KucoBackendError> {
+        let tail_lines = self.tail_lines;
+        let fetches = sources.iter().map(|(namespace, pod_name)| {
+            let client = client.clone();
+            async move {
+                let log = default_container_logs(client, namespace, pod_name, tail_lines).await;
+                (pod_name, log)
+            }
+        });
+
+        let mut lines: Vec<(Option<DateTime<FixedOffset>>, String)> = Vec::new();
+        for (pod_name, log) in join_all(fetches).await {
+            let log = match log {
+                Ok(log) => log,
+                Err(e) => {
+                    lines.push((None, format!("[{}] failed to fetch logs: {}", pod_name, e)));
+                    continue;
+                }
+            };
+            for line in log.lines() {
+                let (timestamp, message) = line.split_once(' ').unwrap_or((line, ""));
+                lines.push((
+                    DateTime::parse_from_rfc3339(timestamp).ok(),
+                    format!("{} [{}] {}", timestamp, pod_name, message),
+                ));
+            }
+        }
+        // Timestamps drop trailing zeros from their fraction, so they do not sort as text. The
+        // sort is stable, keeping each pod's lines in order where timestamps tie.
+        lines.sort_by_key(|(timestamp, _)| *timestamp);
+
+        self.lines = lines.into_iter().map(|(_, line)| line).collect();
+
+        Ok(())
+    }
+}
+
+async fn default_container_logs(
+    client: Client,
+    namespace: &str,
+    pod_name: &str,
+    tail_lines: i64,
+) -> Result<String, kube::Error> {
+    let pods_api: Api<Pod> = Api::namespaced(client, namespace);
+    let pod = pods_api.get(pod_name).await?;
+
+    let container = pod
+        .annotations()
+        .get(DEFAULT_CONTAINER_ANNOTATION)
+        .cloned()
+        .or_else(|| Some(pod.spec.as_ref()?.containers.first()?.name.clone()));
+    let log_params = LogParams {
+        container,
+        timestamps: true,
+        tail_lines: Some(tail_lines),
+        ..Default::default()
+    };
+
+    pods_api.logs(pod_name, &log_params).await
 }
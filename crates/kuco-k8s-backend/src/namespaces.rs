@@ -22,12 +22,12 @@ impl NamespaceData {
         NamespaceData { names: Vec::new() }
     }
 
-    pub async fn update(&mut self, client: Client) {
+    pub async fn update(&mut self, client: Client) -> Result<(), kube::Error> {
         let ns_api_data: Api<Namespace> = Api::all(client);
 
         // List all pods in the namespace.
         let lp = ListParams::default();
-        let ns_list = ns_api_data.list(&lp).await.unwrap().items;
+        let ns_list = ns_api_data.list(&lp).await?.items;
 
         // If a namespace was deleted, remove it as well.
         if ns_list.len() < self.names.len() {
@@ -49,5 +49,7 @@ impl NamespaceData {
                 self.names.push(ns_name);
             }
         }
+
+        Ok(())
     }
 }
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use k8s_openapi::api::core::v1::{Node, Pod};
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, ListParams, Patch, PatchParams},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::KucoBackendError;
+use crate::pods::PodInfo;
+use crate::quantity::{parse_bytes, parse_cpu_millis};
+
+const ROLE_LABEL_PREFIX: &str = "node-role.kubernetes.io/";
+const LEGACY_ROLE_LABEL: &str = "kubernetes.io/role";
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct NodeInfo {
+    pub name: String,
+    pub ready: String,
+    pub memory_pressure: String,
+    pub disk_pressure: String,
+    pub unschedulable: bool,
+    pub roles: Vec<String>,
+    pub kubelet_version: String,
+    pub allocatable_cpu_millis: i64,
+    pub allocatable_memory_bytes: i64,
+    pub requested_cpu_millis: i64,
+    pub requested_memory_bytes: i64,
+    pub taints: Vec<String>,
+    pub pod_count: usize,
+}
+
+/// Sum of the requests of all non-terminated pods scheduled on a single node.
+#[derive(Clone, Debug, Default)]
+struct NodeUsage {
+    cpu_millis: i64,
+    memory_bytes: i64,
+    pod_count: usize,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct NodeData {
+    pub list: Vec<NodeInfo>,
+    pub names: Vec<String>,
+}
+
+impl NodeData {
+    /// List the nodes, with requests summed over `pods`, which should be every pod in the
+    /// cluster, as listed by [`crate::pods::PodData::update_list_all`].
+    pub async fn update(
+        &mut self,
+        client: Client,
+        pods: &[PodInfo],
+    ) -> Result<(), KucoBackendError> {
+        let nodes: Api<Node> = Api::all(client);
+        let node_list = nodes.list(&ListParams::default()).await?;
+
+        let usage = usage_by_node(pods);
+
+        let mut node_info_list: Vec<NodeInfo> = Vec::new();
+        for node in node_list.items {
+            let name = node.name_any();
+            let node_usage = usage.get(&name).cloned().unwrap_or_default();
+
+            let roles: Vec<String> = node
+                .labels()
+                .iter()
+                .filter_map(|(key, value)| {
+                    if let Some(role) = key.strip_prefix(ROLE_LABEL_PREFIX) {
+                        Some(role.to_owned())
+                    } else if key == LEGACY_ROLE_LABEL {
+                        Some(value.to_owned())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            let spec = node.spec.unwrap_or_default();
+            let taints: Vec<String> = spec
+                .taints
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| match t.value {
+                    Some(value) => format!("{}={}:{}", t.key, value, t.effect),
+                    None => format!("{}:{}", t.key, t.effect),
+                })
+                .collect();
+
+            let status = node.status.unwrap_or_default();
+            let condition = |kind: &str| {
+                status
+                    .conditions
+                    .as_ref()
+                    .and_then(|c| c.iter().find(|c| c.type_ == kind))
+                    .map(|c| c.status.clone())
+                    .unwrap_or_else(|| "Unknown".into())
+            };
+            let allocatable = |resource: &str| {
+                status
+                    .allocatable
+                    .as_ref()
+                    .and_then(|a| a.get(resource))
+                    .map(|q| q.0.clone())
+            };
+
+            node_info_list.push(NodeInfo {
+                ready: condition("Ready"),
+                memory_pressure: condition("MemoryPressure"),
+                disk_pressure: condition("DiskPressure"),
+                unschedulable: spec.unschedulable.unwrap_or(false),
+                roles,
+                kubelet_version: status
+                    .node_info
+                    .as_ref()
+                    .map(|i| i.kubelet_version.clone())
+                    .unwrap_or_default(),
+                allocatable_cpu_millis: allocatable("cpu")
+                    .and_then(|q| parse_cpu_millis(&q))
+                    .unwrap_or_default(),
+                allocatable_memory_bytes: allocatable("memory")
+                    .and_then(|q| parse_bytes(&q))
+                    .unwrap_or_default(),
+                requested_cpu_millis: node_usage.cpu_millis,
+                requested_memory_bytes: node_usage.memory_bytes,
+                taints,
+                pod_count: node_usage.pod_count,
+                name,
+            });
+        }
+
+        self.names = node_info_list.iter().map(|n| n.name.clone()).collect();
+        self.list = node_info_list;
+
+        Ok(())
+    }
+}
+
+/// Cordon (`true`) or uncordon (`false`) a node by patching `spec.unschedulable`.
+pub async fn set_unschedulable(
+    client: Client,
+    node_name: &str,
+    unschedulable: bool,
+) -> Result<(), KucoBackendError> {
+    let nodes: Api<Node> = Api::all(client);
+    let patch = serde_json::json!({ "spec": { "unschedulable": unschedulable } });
+
+    nodes
+        .patch(node_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+
+    Ok(())
+}
+
+/// List every pod scheduled on a node, across all namespaces, as `(namespace, pod)` pairs.
+pub async fn get_pods_on_node(
+    client: Client,
+    node_name: &str,
+) -> Result<Vec<(String, String)>, KucoBackendError> {
+    let pods: Api<Pod> = Api::all(client);
+    let lp = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+
+    let pod_list = pods.list(&lp).await?;
+
+    Ok(pod_list
+        .items
+        .iter()
+        .map(|pod| (pod.namespace().unwrap_or_default(), pod.name_any()))
+        .collect())
+}
+
+// Sum the resource requests of every running pod, grouped by the node it is scheduled on.
+fn usage_by_node(pods: &[PodInfo]) -> HashMap<String, NodeUsage> {
+    let mut usage: HashMap<String, NodeUsage> = HashMap::new();
+    for pod in pods {
+        if pod.node.is_empty() || matches!(pod.phase.as_str(), "Succeeded" | "Failed") {
+            continue;
+        }
+
+        let entry = usage.entry(pod.node.clone()).or_default();
+        entry.cpu_millis += pod.requested_cpu_millis;
+        entry.memory_bytes += pod.requested_memory_bytes;
+        entry.pod_count += 1;
+    }
+
+    usage
+}
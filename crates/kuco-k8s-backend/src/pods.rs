@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
 use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet, StatefulSet};
-use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::core::v1::{Container, Pod, PodSpec};
 
 use kube::ResourceExt;
 use kube::{
@@ -9,12 +12,290 @@ use kube::{
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::KucoBackendError;
+use crate::namespaces::NamespaceData;
+use crate::quantity::{parse_bytes, parse_cpu_millis};
+
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct PodInfo {
     pub name: String,
+    #[serde(default)]
+    pub namespace: String,
     pub status: String,
     pub replicas: Option<i32>,
     pub desired_replicas: Option<i32>,
+    pub ready_containers: i32,
+    pub total_containers: i32,
+    pub restarts: i32,
+    /// Unix timestamp, in seconds, of when the pod was created.
+    pub created_at: Option<i64>,
+    pub node: String,
+    pub ip: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Raw `status.phase`, unlike `status` which is the kubectl-style display status.
+    #[serde(default)]
+    pub phase: String,
+    #[serde(default)]
+    pub requested_cpu_millis: i64,
+    #[serde(default)]
+    pub requested_memory_bytes: i64,
+}
+
+impl PodInfo {
+    /// Whether the pod is running with every container ready, or has completed successfully.
+    pub fn is_healthy(&self) -> bool {
+        (self.status == "Running" && self.ready_containers == self.total_containers)
+            || self.status == "Completed"
+    }
+
+    /// Whether the pod is on its way to healthy, rather than stuck or failed.
+    pub fn is_progressing(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "Running" | "Pending" | "ContainerCreating" | "PodInitializing" | "Terminating"
+        ) || (self.status.starts_with("Init:")
+            && !self.status.contains("Err")
+            && !self.status.contains("BackOff"))
+    }
+}
+
+/// Build the listing row for a pod, without the replica counts of its owner (which need
+/// extra lookups).
+fn pod_info(pod: &Pod) -> PodInfo {
+    let status = pod.status.as_ref();
+    let container_statuses = status
+        .and_then(|s| s.container_statuses.clone())
+        .unwrap_or_default();
+    let requests = pod.spec.as_ref().map(pod_requests).unwrap_or_default();
+
+    PodInfo {
+        name: pod.name_any(),
+        namespace: pod.namespace().unwrap_or_default(),
+        status: pod_status(pod),
+        replicas: None,
+        desired_replicas: None,
+        ready_containers: container_statuses.iter().filter(|c| c.ready).count() as i32,
+        total_containers: pod
+            .spec
+            .as_ref()
+            .map(|s| s.containers.len() as i32)
+            .unwrap_or_default(),
+        restarts: container_statuses.iter().map(|c| c.restart_count).sum(),
+        created_at: pod.creation_timestamp().map(|t| t.0.timestamp()),
+        node: pod
+            .spec
+            .as_ref()
+            .and_then(|s| s.node_name.clone())
+            .unwrap_or_default(),
+        ip: status.and_then(|s| s.pod_ip.clone()).unwrap_or_default(),
+        labels: pod.labels().clone(),
+        phase: status.and_then(|s| s.phase.clone()).unwrap_or_default(),
+        requested_cpu_millis: requests.0,
+        requested_memory_bytes: requests.1,
+    }
+}
+
+/// Effective `(cpu millis, memory bytes)` requests of a pod: the max of the summed app
+/// containers and any single init container, since init containers run one at a time before
+/// the app starts.
+fn pod_requests(spec: &PodSpec) -> (i64, i64) {
+    let requests = |containers: &[Container]| {
+        containers.iter().fold((0_i64, 0_i64), |(cpu, mem), c| {
+            let reqs = c.resources.as_ref().and_then(|r| r.requests.as_ref());
+            let get = |resource: &str| reqs.and_then(|r| r.get(resource)).map(|q| q.0.as_str());
+            (
+                cpu + get("cpu").and_then(parse_cpu_millis).unwrap_or_default(),
+                mem + get("memory").and_then(parse_bytes).unwrap_or_default(),
+            )
+        })
+    };
+
+    let (mut cpu, mut mem) = requests(&spec.containers);
+    for init in spec.init_containers.iter().flatten() {
+        let (init_cpu, init_mem) = requests(std::slice::from_ref(init));
+        cpu = cpu.max(init_cpu);
+        mem = mem.max(init_mem);
+    }
+    (cpu, mem)
+}
+
+/// Field paths the API server accepts in a pod field selector.
+pub const POD_FIELD_PATHS: [&str; 10] = [
+    "metadata.name",
+    "metadata.namespace",
+    "spec.nodeName",
+    "spec.restartPolicy",
+    "spec.schedulerName",
+    "spec.serviceAccountName",
+    "spec.hostNetwork",
+    "status.phase",
+    "status.podIP",
+    "status.nominatedNodeName",
+];
+
+/// Label and field selectors narrowing a pod list.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct PodSelector {
+    pub labels: Option<String>,
+    pub fields: Option<String>,
+}
+
+impl PodSelector {
+    /// Split a combined selector such as `app=api,tier!=cache,status.phase=Running` into its
+    /// label and field parts. Terms on `metadata.`, `spec.` or `status.` paths are fields.
+    pub fn parse(input: &str) -> Self {
+        let mut labels: Vec<String> = Vec::new();
+        let mut fields: Vec<String> = Vec::new();
+
+        for term in split_terms(input) {
+            let key = term.trim_start_matches('!');
+            if ["metadata.", "spec.", "status."]
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+            {
+                fields.push(term);
+            } else {
+                labels.push(term);
+            }
+        }
+
+        let join = |terms: Vec<String>| (!terms.is_empty()).then(|| terms.join(","));
+        PodSelector {
+            labels: join(labels),
+            fields: join(fields),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_none() && self.fields.is_none()
+    }
+
+    pub fn list_params(&self) -> ListParams {
+        let mut lp = ListParams::default();
+        if let Some(labels) = &self.labels {
+            lp = lp.labels(labels);
+        }
+        if let Some(fields) = &self.fields {
+            lp = lp.fields(fields);
+        }
+        lp
+    }
+}
+
+impl fmt::Display for PodSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<&str> = [&self.labels, &self.fields]
+            .into_iter()
+            .flatten()
+            .map(|s| s.as_str())
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+/// Split a selector on commas, except those inside set-based values like `env in (a,b)`. A stray
+/// `)` does not stop later commas from splitting.
+pub fn split_terms(input: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut depth: usize = 0;
+
+    for c in input.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                terms.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    terms.push(current);
+
+    terms
+        .into_iter()
+        .map(|t| t.trim().to_owned())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// The STATUS column as kubectl computes it: the most telling container waiting/terminated
+/// reason (e.g. CrashLoopBackOff, OOMKilled), init container progress, or else the phase.
+pub fn pod_status(pod: &Pod) -> String {
+    if pod.metadata.deletion_timestamp.is_some() {
+        return "Terminating".to_owned();
+    }
+
+    let Some(status) = &pod.status else {
+        return "Unknown".to_owned();
+    };
+    let mut reason = status
+        .reason
+        .clone()
+        .or(status.phase.clone())
+        .unwrap_or_else(|| "Unknown".into());
+
+    let init_statuses = status.init_container_statuses.clone().unwrap_or_default();
+    for (i, container) in init_statuses.iter().enumerate() {
+        let state = container.state.clone().unwrap_or_default();
+        if let Some(terminated) = state.terminated {
+            if terminated.exit_code == 0 {
+                continue;
+            }
+            return format!(
+                "Init:{}",
+                terminated
+                    .reason
+                    .unwrap_or_else(|| format!("ExitCode:{}", terminated.exit_code))
+            );
+        }
+        if let Some(waiting) = state.waiting {
+            if let Some(waiting_reason) = waiting.reason.filter(|r| r != "PodInitializing") {
+                return format!("Init:{}", waiting_reason);
+            }
+        }
+        return format!("Init:{}/{}", i, init_statuses.len());
+    }
+
+    // Later containers are checked first so that the first container's reason wins.
+    for container in status
+        .container_statuses
+        .clone()
+        .unwrap_or_default()
+        .iter()
+        .rev()
+    {
+        let state = container.state.clone().unwrap_or_default();
+        if let Some(waiting_reason) = state.waiting.and_then(|w| w.reason) {
+            reason = waiting_reason;
+        } else if let Some(terminated) = state.terminated {
+            reason = terminated
+                .reason
+                .unwrap_or_else(|| format!("ExitCode:{}", terminated.exit_code));
+        }
+    }
+
+    if reason == "Succeeded" {
+        reason = "Completed".to_owned();
+    }
+    reason
+}
+
+/// The live pod for exporting, without `metadata.managedFields`, which only matters to the API
+/// server.
+pub async fn get_pod_manifest(
+    client: Client,
+    namespace: &str,
+    name: &str,
+) -> Result<Pod, KucoBackendError> {
+    let pods: Api<Pod> = Api::namespaced(client, namespace);
+    let mut pod = pods.get(name).await?;
+    pod.metadata.managed_fields = None;
+
+    Ok(pod)
 }
 
 impl PodInfo {
@@ -27,14 +308,8 @@ impl PodInfo {
         let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
         let pod = pods.get(pod_name).await?;
 
-        // Update name
-        self.name = pod.name_any();
-
-        // Update status
-        self.status = pod
-            .status
-            .and_then(|s| s.phase)
-            .unwrap_or_else(|| "Unknown".into());
+        // Update name, status & listing columns
+        *self = pod_info(&pod);
 
         if let Some(owners) = &pod.metadata.owner_references {
             for owner in owners {
@@ -100,6 +375,71 @@ impl PodData {
         Ok(())
     }
 
+    /// List the pods in a namespace with everything needed for the pod table, in one call.
+    pub async fn update_list(
+        &mut self,
+        client: Client,
+        namespace: &str,
+        selector: &PodSelector,
+    ) -> Result<(), kube::Error> {
+        let pods: Api<Pod> = Api::namespaced(client, namespace);
+        let pod_list = pods.list(&selector.list_params()).await?;
+
+        self.list = pod_list.items.iter().map(pod_info).collect();
+        self.names = self.list.iter().map(|p| p.name.clone()).collect();
+
+        Ok(())
+    }
+
+    /// Like [`PodData::update_list`], but for every namespace at once. Names are
+    /// "namespace/pod" keys, since pod names are only unique within a namespace.
+    ///
+    /// When RBAC forbids listing pods cluster-wide, the namespaces are listed one by one instead,
+    /// leaving out those that are forbidden as well.
+    pub async fn update_list_all(
+        &mut self,
+        client: Client,
+        selector: &PodSelector,
+    ) -> Result<(), kube::Error> {
+        let pods: Api<Pod> = Api::all(client.clone());
+        self.list = match pods.list(&selector.list_params()).await {
+            Ok(pod_list) => pod_list.items.iter().map(pod_info).collect(),
+            Err(kube::Error::Api(e)) if e.code == 403 => {
+                let mut namespaces = NamespaceData::new();
+                namespaces.update(client.clone()).await?;
+
+                let mut list = Vec::new();
+                let mut listed_any = false;
+                for namespace in &namespaces.names {
+                    let mut namespace_pods = PodData::default();
+                    match namespace_pods
+                        .update_list(client.clone(), namespace, selector)
+                        .await
+                    {
+                        Ok(()) => {
+                            list.append(&mut namespace_pods.list);
+                            listed_any = true;
+                        }
+                        Err(kube::Error::Api(e)) if e.code == 403 => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+                if !listed_any {
+                    return Err(kube::Error::Api(e));
+                }
+                list
+            }
+            Err(e) => return Err(e),
+        };
+        self.names = self
+            .list
+            .iter()
+            .map(|p| format!("{}/{}", p.namespace, p.name))
+            .collect();
+
+        Ok(())
+    }
+
     pub async fn update_all(&mut self, client: Client, namespace: &str) -> Result<(), kube::Error> {
         // Get a reference to the Pod API within the specified namespace.
         let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
@@ -111,12 +451,6 @@ impl PodData {
         let mut pod_info_list: Vec<PodInfo> = Vec::new();
 
         for pod in pod_list.items {
-            let pod_name = pod.name_any();
-            let pod_status = pod
-                .status
-                .and_then(|s| s.phase)
-                .unwrap_or_else(|| "Unknown".into());
-
             let mut replicas: Option<i32> = None;
             let mut desired_replicas: Option<i32> = None;
 
@@ -158,10 +492,9 @@ impl PodData {
             }
 
             pod_info_list.push(PodInfo {
-                name: pod_name,
-                status: pod_status,
                 replicas,
                 desired_replicas,
+                ..pod_info(&pod)
             });
         }
 
@@ -170,3 +503,77 @@ impl PodData {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(labels: Option<&str>, fields: Option<&str>) -> PodSelector {
+        PodSelector {
+            labels: labels.map(String::from),
+            fields: fields.map(String::from),
+        }
+    }
+
+    #[test]
+    fn splits_terms_outside_of_sets() {
+        assert_eq!(
+            split_terms("app=api, env in (prod,staging),tier notin (cache, db) ,!canary"),
+            vec![
+                "app=api",
+                "env in (prod,staging)",
+                "tier notin (cache, db)",
+                "!canary"
+            ]
+        );
+        assert_eq!(split_terms(" , app=api,,"), vec!["app=api"]);
+        assert!(split_terms("").is_empty());
+    }
+
+    #[test]
+    fn splits_malformed_sets() {
+        // An unclosed set runs to the end rather than splitting inside it.
+        assert_eq!(
+            split_terms("app=api,env in (prod,staging"),
+            vec!["app=api", "env in (prod,staging"]
+        );
+        assert_eq!(
+            split_terms("env in prod),app=api,tier=db"),
+            vec!["env in prod)", "app=api", "tier=db"]
+        );
+    }
+
+    #[test]
+    fn parses_labels_and_fields() {
+        assert_eq!(
+            PodSelector::parse("app=api,tier!=cache,status.phase=Running"),
+            selector(Some("app=api,tier!=cache"), Some("status.phase=Running"))
+        );
+        assert_eq!(
+            PodSelector::parse("spec.nodeName!=node-1,metadata.name==web-0"),
+            selector(None, Some("spec.nodeName!=node-1,metadata.name==web-0"))
+        );
+        assert_eq!(
+            PodSelector::parse("env in (prod,staging),!canary,app"),
+            selector(Some("env in (prod,staging),!canary,app"), None)
+        );
+        assert_eq!(PodSelector::parse(" , "), PodSelector::default());
+        assert!(PodSelector::parse("").is_empty());
+    }
+
+    #[test]
+    fn parses_quoted_values_as_typed() {
+        // Quotes are not selector syntax, so they are left for the API server to reject.
+        assert_eq!(
+            PodSelector::parse(r#"app="api",status.phase='Running'"#),
+            selector(Some(r#"app="api""#), Some("status.phase='Running'"))
+        );
+    }
+
+    #[test]
+    fn displays_labels_before_fields() {
+        let parsed = PodSelector::parse("status.phase=Running,app=api");
+        assert_eq!(parsed.to_string(), "app=api,status.phase=Running");
+        assert_eq!(PodSelector::parse(&parsed.to_string()), parsed);
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{LimitRange, ResourceQuota};
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, ListParams},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::KucoBackendError;
+use crate::quantity::quantity_ratio;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct QuotaUsage {
+    pub quota: String,
+    pub resource: String,
+    pub used: String,
+    pub hard: String,
+    /// `used / hard`, if both quantities could be parsed.
+    pub ratio: Option<f64>,
+}
+
+impl QuotaUsage {
+    pub fn key(&self) -> String {
+        format!("quota {} {}", self.quota, self.resource)
+    }
+}
+
+/// One resource of one LimitRange item, e.g. the Container limits on `memory`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct LimitInfo {
+    pub limit_range: String,
+    pub type_: String,
+    pub resource: String,
+    pub default: Option<String>,
+    pub default_request: Option<String>,
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+impl LimitInfo {
+    pub fn key(&self) -> String {
+        format!(
+            "limits {} {} {}",
+            self.limit_range, self.type_, self.resource
+        )
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PdbInfo {
+    pub name: String,
+    pub min_available: Option<String>,
+    pub max_unavailable: Option<String>,
+    pub disruptions_allowed: i32,
+    pub current_healthy: i32,
+    pub desired_healthy: i32,
+    pub expected_pods: i32,
+}
+
+impl PdbInfo {
+    pub fn key(&self) -> String {
+        format!("pdb {}", self.name)
+    }
+}
+
+/// The policies constraining what can be scheduled in, or evicted from, a namespace.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PolicyData {
+    pub quotas: Vec<QuotaUsage>,
+    pub limits: Vec<LimitInfo>,
+    pub pdbs: Vec<PdbInfo>,
+}
+
+impl PolicyData {
+    pub async fn update(
+        &mut self,
+        client: Client,
+        namespace: &str,
+    ) -> Result<(), KucoBackendError> {
+        let quotas: Api<ResourceQuota> = Api::namespaced(client.clone(), namespace);
+        let limit_ranges: Api<LimitRange> = Api::namespaced(client.clone(), namespace);
+        let pdbs: Api<PodDisruptionBudget> = Api::namespaced(client, namespace);
+
+        let mut quota_usage: Vec<QuotaUsage> = Vec::new();
+        for quota in quotas.list(&ListParams::default()).await?.items {
+            let name = quota.name_any();
+            let status = quota.status.unwrap_or_default();
+            let used = status.used.unwrap_or_default();
+
+            for (resource, hard) in status.hard.unwrap_or_default() {
+                let used = used
+                    .get(&resource)
+                    .map(|q| q.0.clone())
+                    .unwrap_or("0".into());
+                quota_usage.push(QuotaUsage {
+                    quota: name.clone(),
+                    ratio: quantity_ratio(&used, &hard.0),
+                    hard: hard.0,
+                    used,
+                    resource,
+                });
+            }
+        }
+
+        let mut limits: Vec<LimitInfo> = Vec::new();
+        for limit_range in limit_ranges.list(&ListParams::default()).await?.items {
+            let name = limit_range.name_any();
+            for item in limit_range.spec.map(|s| s.limits).unwrap_or_default() {
+                let lookup = |map: &Option<BTreeMap<String, Quantity>>, resource: &str| {
+                    map.as_ref()
+                        .and_then(|m| m.get(resource))
+                        .map(|q| q.0.clone())
+                };
+
+                let mut resources: Vec<&String> =
+                    [&item.default, &item.default_request, &item.min, &item.max]
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|m| m.keys())
+                        .collect();
+                resources.sort();
+                resources.dedup();
+
+                for resource in resources {
+                    limits.push(LimitInfo {
+                        limit_range: name.clone(),
+                        type_: item.type_.clone(),
+                        resource: resource.clone(),
+                        default: lookup(&item.default, resource),
+                        default_request: lookup(&item.default_request, resource),
+                        min: lookup(&item.min, resource),
+                        max: lookup(&item.max, resource),
+                    });
+                }
+            }
+        }
+
+        self.pdbs = pdbs
+            .list(&ListParams::default())
+            .await?
+            .items
+            .into_iter()
+            .map(|pdb| {
+                let name = pdb.name_any();
+                let spec = pdb.spec.unwrap_or_default();
+                let status = pdb.status.unwrap_or_default();
+
+                PdbInfo {
+                    name,
+                    min_available: spec.min_available.map(format_int_or_string),
+                    max_unavailable: spec.max_unavailable.map(format_int_or_string),
+                    disruptions_allowed: status.disruptions_allowed,
+                    current_healthy: status.current_healthy,
+                    desired_healthy: status.desired_healthy,
+                    expected_pods: status.expected_pods,
+                }
+            })
+            .collect();
+        self.quotas = quota_usage;
+        self.limits = limits;
+
+        Ok(())
+    }
+
+    /// Keys of every quota resource, limit and PDB, in display order.
+    pub fn keys(&self) -> Vec<String> {
+        self.quotas
+            .iter()
+            .map(|q| q.key())
+            .chain(self.limits.iter().map(|l| l.key()))
+            .chain(self.pdbs.iter().map(|p| p.key()))
+            .collect()
+    }
+}
+
+fn format_int_or_string(value: IntOrString) -> String {
+    match value {
+        IntOrString::Int(i) => i.to_string(),
+        IntOrString::String(s) => s,
+    }
+}
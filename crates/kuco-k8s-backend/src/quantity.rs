@@ -0,0 +1,83 @@
+// Helpers for turning Kubernetes resource quantities ("250m", "1.5", "512Mi") into plain
+// integers and back, so that requests and capacities can be summed and displayed.
+
+/// Parse a CPU quantity into millicores.
+pub fn parse_cpu_millis(quantity: &str) -> Option<i64> {
+    let quantity = quantity.trim();
+    parse_quantity(quantity).map(|cores| (cores * 1000.0).ceil() as i64)
+}
+
+/// Parse a memory (or any decimal/binary SI) quantity into bytes.
+pub fn parse_bytes(quantity: &str) -> Option<i64> {
+    parse_quantity(quantity).map(|bytes| bytes.ceil() as i64)
+}
+
+/// Parse any quantity into its plain numeric value ("250m" is 0.25, "1Ki" is 1024).
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+    let split_at = quantity
+        .find(|c: char| c.is_ascii_alphabetic() && c != 'e')
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(split_at);
+
+    let multiplier: f64 = match suffix {
+        "" => 1.0,
+        "m" => 0.001,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024.0,
+        "Mi" => 1024.0_f64.powi(2),
+        "Gi" => 1024.0_f64.powi(3),
+        "Ti" => 1024.0_f64.powi(4),
+        "Pi" => 1024.0_f64.powi(5),
+        "Ei" => 1024.0_f64.powi(6),
+        _ => return None,
+    };
+
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// How much of `hard` is taken up by `used`, for two quantities of the same resource.
+pub fn quantity_ratio(used: &str, hard: &str) -> Option<f64> {
+    let used = parse_quantity(used)?;
+    let hard = parse_quantity(hard)?;
+
+    if hard == 0.0 {
+        return Some(if used > 0.0 { f64::INFINITY } else { 0.0 });
+    }
+    Some(used / hard)
+}
+
+/// Format millicores the way kubectl does ("250m", "2").
+pub fn format_cpu_millis(millis: i64) -> String {
+    if millis % 1000 == 0 {
+        format!("{}", millis / 1000)
+    } else {
+        format!("{}m", millis)
+    }
+}
+
+/// Format bytes using the largest binary suffix that keeps the number readable.
+pub fn format_bytes(bytes: i64) -> String {
+    const SUFFIXES: [&str; 5] = ["Ki", "Mi", "Gi", "Ti", "Pi"];
+
+    let mut value = bytes as f64;
+    let mut suffix = "";
+    for s in SUFFIXES {
+        if value.abs() < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        suffix = s;
+    }
+
+    if suffix.is_empty() || value.fract() == 0.0 {
+        format!("{}{}", value as i64, suffix)
+    } else {
+        format!("{:.1}{}", value, suffix)
+    }
+}
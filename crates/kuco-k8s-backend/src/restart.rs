@@ -0,0 +1,51 @@
+use k8s_openapi::{
+    api::apps::v1::{DaemonSet, Deployment, StatefulSet},
+    chrono::Utc,
+};
+use kube::{
+    Client,
+    api::{Api, Patch, PatchParams},
+};
+
+use crate::error::KucoBackendError;
+
+/// Set on the pod template by `kubectl rollout restart`, so changing it rolls every pod.
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// Kinds that can be rolled out again.
+pub const RESTARTABLE_KINDS: [&str; 3] = ["Deployment", "StatefulSet", "DaemonSet"];
+
+/// Replace the pods of a workload the way `kubectl rollout restart` does, honouring its
+/// update strategy.
+pub async fn restart_workload(
+    client: Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<(), KucoBackendError> {
+    let patch = serde_json::json!({
+        "spec": { "template": { "metadata": { "annotations": {
+            RESTARTED_AT_ANNOTATION: Utc::now().to_rfc3339()
+        } } } }
+    });
+    let params = PatchParams::default();
+    let patch = Patch::Merge(&patch);
+
+    match kind {
+        "Deployment" => {
+            let api: Api<Deployment> = Api::namespaced(client, namespace);
+            api.patch(name, &params, &patch).await?;
+        }
+        "StatefulSet" => {
+            let api: Api<StatefulSet> = Api::namespaced(client, namespace);
+            api.patch(name, &params, &patch).await?;
+        }
+        "DaemonSet" => {
+            let api: Api<DaemonSet> = Api::namespaced(client, namespace);
+            api.patch(name, &params, &patch).await?;
+        }
+        other => return Err(KucoBackendError::NotRestartable(other.to_owned())),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use kube::{
+    Client,
+    api::{Api, DynamicObject, Patch, PatchParams},
+};
+
+use crate::discovery::ApiKind;
+use crate::error::KucoBackendError;
+
+/// Kinds served with a `scale` subresource that KuCo offers to scale.
+pub const SCALABLE_KINDS: [&str; 3] = ["Deployment", "StatefulSet", "ReplicaSet"];
+
+fn scale_api(client: Client, kind: &ApiKind, namespace: &str) -> Api<DynamicObject> {
+    Api::namespaced_with(client, namespace, &kind.api_resource())
+}
+
+/// Read the desired replica count from the object's scale subresource.
+pub async fn get_replicas(
+    client: Client,
+    kind: &ApiKind,
+    namespace: &str,
+    name: &str,
+) -> Result<i32, KucoBackendError> {
+    let scale = scale_api(client, kind, namespace).get_scale(name).await?;
+
+    Ok(scale.spec.and_then(|s| s.replicas).unwrap_or_default())
+}
+
+pub async fn set_replicas(
+    client: Client,
+    kind: &ApiKind,
+    namespace: &str,
+    name: &str,
+    replicas: i32,
+) -> Result<(), KucoBackendError> {
+    let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+
+    scale_api(client, kind, namespace)
+        .patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+
+    Ok(())
+}
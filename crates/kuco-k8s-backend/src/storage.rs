@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use k8s_openapi::api::core::v1::{PersistentVolume, PersistentVolumeClaim, Pod};
+
+use kube::ResourceExt;
+use kube::{
+    Client,
+    api::{Api, ListParams},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::KucoBackendError;
+use crate::events::get_object_events;
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PvcInfo {
+    pub name: String,
+    pub phase: String,
+    pub capacity: String,
+    pub access_modes: Vec<String>,
+    pub storage_class: String,
+    pub volume: String,
+    /// Pods in the namespace that mount this claim, leaving out completed ones.
+    pub mounted_by: Vec<String>,
+    /// Recent Events for the claim, only collected while it is stuck in Pending.
+    pub events: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PvInfo {
+    pub name: String,
+    pub phase: String,
+    pub capacity: String,
+    pub access_modes: Vec<String>,
+    pub reclaim_policy: String,
+    pub storage_class: String,
+    /// The bound claim as "namespace/name", if any.
+    pub claim: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct StorageData {
+    pub claims: Vec<PvcInfo>,
+    pub volumes: Vec<PvInfo>,
+}
+
+impl StorageData {
+    pub async fn update_claims(
+        &mut self,
+        client: Client,
+        namespace: &str,
+    ) -> Result<(), KucoBackendError> {
+        let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), namespace);
+        let pvc_list = pvcs.list(&ListParams::default()).await?;
+
+        // Index which pods mount each claim so the lookup is a single pod list per refresh. Like
+        // `kubectl describe`, pods that have completed no longer count as using the claim.
+        let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        let lp = ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed");
+        let mut mounts: HashMap<String, Vec<String>> = HashMap::new();
+        for pod in pods.list(&lp).await?.items {
+            let pod_name = pod.name_any();
+            let volumes = pod.spec.and_then(|s| s.volumes).unwrap_or_default();
+            for claim in volumes
+                .into_iter()
+                .filter_map(|v| v.persistent_volume_claim)
+            {
+                mounts
+                    .entry(claim.claim_name)
+                    .or_default()
+                    .push(pod_name.clone());
+            }
+        }
+
+        let mut claims: Vec<PvcInfo> = Vec::new();
+        for pvc in pvc_list.items {
+            let name = pvc.name_any();
+            let spec = pvc.spec.unwrap_or_default();
+            let status = pvc.status.unwrap_or_default();
+            let phase = status.phase.unwrap_or_else(|| "Unknown".into());
+
+            // The events only explain a pending claim, so failing to get them should not cost
+            // the whole list.
+            let events = if phase == "Pending" {
+                match get_object_events(client.clone(), namespace, "PersistentVolumeClaim", &name)
+                    .await
+                {
+                    Ok(events) => events
+                        .into_iter()
+                        .map(|e| format!("{} {}: {}", e.type_, e.reason, e.message))
+                        .collect(),
+                    Err(e) => {
+                        tracing::error!("Failed to get events of claim {}: {}", name, e);
+                        Vec::new()
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            claims.push(PvcInfo {
+                phase,
+                capacity: status
+                    .capacity
+                    .and_then(|c| c.get("storage").map(|q| q.0.clone()))
+                    .unwrap_or_default(),
+                access_modes: short_access_modes(status.access_modes.unwrap_or_default()),
+                storage_class: spec.storage_class_name.unwrap_or_default(),
+                volume: spec.volume_name.unwrap_or_default(),
+                mounted_by: mounts.remove(&name).unwrap_or_default(),
+                events,
+                name,
+            });
+        }
+
+        self.claims = claims;
+
+        Ok(())
+    }
+
+    pub async fn update_volumes(&mut self, client: Client) -> Result<(), KucoBackendError> {
+        let pvs: Api<PersistentVolume> = Api::all(client);
+        let pv_list = pvs.list(&ListParams::default()).await?;
+
+        self.volumes = pv_list
+            .items
+            .into_iter()
+            .map(|pv| {
+                let name = pv.name_any();
+                let spec = pv.spec.unwrap_or_default();
+                let status = pv.status.unwrap_or_default();
+
+                PvInfo {
+                    name,
+                    phase: status.phase.unwrap_or_else(|| "Unknown".into()),
+                    capacity: spec
+                        .capacity
+                        .and_then(|c| c.get("storage").map(|q| q.0.clone()))
+                        .unwrap_or_default(),
+                    access_modes: short_access_modes(spec.access_modes.unwrap_or_default()),
+                    reclaim_policy: spec.persistent_volume_reclaim_policy.unwrap_or_default(),
+                    storage_class: spec.storage_class_name.unwrap_or_default(),
+                    claim: spec.claim_ref.map(|c| {
+                        format!(
+                            "{}/{}",
+                            c.namespace.unwrap_or_default(),
+                            c.name.unwrap_or_default()
+                        )
+                    }),
+                }
+            })
+            .collect();
+
+        Ok(())
+    }
+}
+
+fn short_access_modes(modes: Vec<String>) -> Vec<String> {
+    modes
+        .into_iter()
+        .map(|mode| match mode.as_str() {
+            "ReadWriteOnce" => "RWO".to_owned(),
+            "ReadOnlyMany" => "ROX".to_owned(),
+            "ReadWriteMany" => "RWX".to_owned(),
+            "ReadWriteOncePod" => "RWOP".to_owned(),
+            _ => mode,
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+/*
+ * The audit log: one row per change made to a cluster from KuCo, whether it succeeded or not.
+ * Rows are only ever appended.
+ */
+
+use color_eyre::eyre::{Result, WrapErr};
+
+#[cfg(feature = "serde_support")]
+use serde::Serialize;
+
+use crate::persistence::SqliteDb;
+
+/// Outcome recorded for changes that went through.
+pub const AUDIT_OK: &str = "ok";
+
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+#[cfg_attr(feature = "serde_support", derive(Serialize))]
+pub struct AuditEntry {
+    /// Assigned when the entry is recorded.
+    pub id: i64,
+    /// Seconds since the epoch.
+    pub at: i64,
+    pub context: String,
+    /// Empty for cluster-scoped objects.
+    pub namespace: String,
+    pub kind: String,
+    pub name: String,
+    pub action: String,
+    /// JSON object with what the action was given, e.g. `{"replicas":3}`.
+    pub parameters: String,
+    /// [`AUDIT_OK`], or what went wrong.
+    pub outcome: String,
+}
+
+impl AuditEntry {
+    pub fn succeeded(&self) -> bool {
+        self.outcome == AUDIT_OK
+    }
+
+    /// "namespace/name", or the name alone for cluster-scoped objects.
+    pub fn object(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}/{}", self.namespace, self.name)
+        }
+    }
+
+    /// Unique per entry, and made of what one would search the log for.
+    pub fn key(&self) -> String {
+        format!(
+            "#{} {} {} {} {}",
+            self.id,
+            self.action,
+            self.kind.to_lowercase(),
+            self.object(),
+            self.context
+        )
+    }
+}
+
+/// Narrows the entries returned by [`SqliteDb::get_audit`]; fields left at `None` match all.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditFilter {
+    pub context: Option<String>,
+    pub namespace: Option<String>,
+    /// Compared case-insensitively.
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub action: Option<String>,
+    /// `Some(false)` for failed changes only, `Some(true)` for successful ones.
+    pub succeeded: Option<bool>,
+    /// Only entries recorded at or after this many seconds since the epoch.
+    pub since: Option<i64>,
+}
+
+impl SqliteDb {
+    /// Append `entry`, ignoring its `id`. Returns the id it was recorded under.
+    pub async fn record_audit(&self, entry: &AuditEntry) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO audit_log (at, context, namespace, kind, name, action, parameters, outcome)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(entry.at)
+        .bind(&entry.context)
+        .bind(&entry.namespace)
+        .bind(&entry.kind)
+        .bind(&entry.name)
+        .bind(&entry.action)
+        .bind(&entry.parameters)
+        .bind(&entry.outcome)
+        .execute(self.pool())
+        .await
+        .wrap_err_with(|| {
+            format!(
+                "Failed to record {} of {} '{}' in the audit log",
+                entry.action,
+                entry.kind,
+                entry.object()
+            )
+        })?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// The entries matching `filter`, oldest first.
+    pub async fn get_audit(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>> {
+        sqlx::query_as(
+            "SELECT id, at, context, namespace, kind, name, action, parameters, outcome
+             FROM audit_log
+             WHERE (?1 IS NULL OR context = ?1)
+               AND (?2 IS NULL OR namespace = ?2)
+               AND (?3 IS NULL OR lower(kind) = lower(?3))
+               AND (?4 IS NULL OR name = ?4)
+               AND (?5 IS NULL OR action = ?5)
+               AND (?6 IS NULL OR (outcome = ?7) = ?6)
+               AND (?8 IS NULL OR at >= ?8)
+             ORDER BY id",
+        )
+        .bind(&filter.context)
+        .bind(&filter.namespace)
+        .bind(&filter.kind)
+        .bind(&filter.name)
+        .bind(&filter.action)
+        .bind(filter.succeeded)
+        .bind(AUDIT_OK)
+        .bind(filter.since)
+        .fetch_all(self.pool())
+        .await
+        .wrap_err("Failed to read the audit log")
+    }
+}
@@ -1,7 +1,9 @@
+pub mod audit;
 pub mod cache;
 pub mod persistence;
 pub mod traits;
 
+pub use audit::{AuditEntry, AuditFilter};
 pub use cache::SqliteCache;
 pub use persistence::SqliteDb;
 pub use traits::KucoSqliteStore;
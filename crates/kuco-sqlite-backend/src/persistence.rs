@@ -53,9 +53,96 @@ impl SqliteDb {
         .await
         .wrap_err("Failed to create kv_cache table")?;
 
+        // UI preferences that should survive restarts (table sort order, visible columns ...).
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS view_prefs (
+                key TEXT PRIMARY KEY NOT NULL,
+                value BLOB NOT NULL,
+                updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            )",
+        )
+        .execute(pool)
+        .await
+        .wrap_err("Failed to create view_prefs table")?;
+
+        // Recently used prompt entries (pod selectors ...), grouped by `kind`.
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS history (
+                kind TEXT NOT NULL,
+                entry TEXT NOT NULL,
+                used_at INTEGER NOT NULL,
+                PRIMARY KEY (kind, entry)
+            )",
+        )
+        .execute(pool)
+        .await
+        .wrap_err("Failed to create history table")?;
+
+        // Changes made to clusters from KuCo, see [`crate::audit`].
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                at INTEGER NOT NULL,
+                context TEXT NOT NULL,
+                namespace TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                action TEXT NOT NULL,
+                parameters TEXT NOT NULL,
+                outcome TEXT NOT NULL
+            )",
+        )
+        .execute(pool)
+        .await
+        .wrap_err("Failed to create audit_log table")?;
+
         Ok(())
     }
 
+    pub(crate) fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Record `entry` as the most recently used of `kind`, keeping only the latest `limit`.
+    pub async fn push_history(&self, kind: &str, entry: &str, limit: i64) -> Result<()> {
+        // `used_at` is in milliseconds, so entries pushed within the same second keep their order.
+        sqlx::query(
+            "REPLACE INTO history (kind, entry, used_at)
+             VALUES (?, ?, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER))",
+        )
+        .bind(kind)
+        .bind(entry)
+        .execute(&self.pool)
+        .await
+        .wrap_err_with(|| format!("Failed to record {} history", kind))?;
+
+        sqlx::query(
+            "DELETE FROM history WHERE kind = ? AND entry NOT IN (
+                SELECT entry FROM history WHERE kind = ? ORDER BY used_at DESC LIMIT ?
+            )",
+        )
+        .bind(kind)
+        .bind(kind)
+        .bind(limit)
+        .execute(&self.pool)
+        .await
+        .wrap_err_with(|| format!("Failed to trim {} history", kind))?;
+
+        Ok(())
+    }
+
+    /// The entries of `kind`, most recently used first.
+    pub async fn get_history(&self, kind: &str) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT entry FROM history WHERE kind = ? ORDER BY used_at DESC")
+                .bind(kind)
+                .fetch_all(&self.pool)
+                .await
+                .wrap_err_with(|| format!("Failed to get {} history", kind))?;
+
+        Ok(rows.into_iter().map(|(entry,)| entry).collect())
+    }
+
     async fn erase_all_persistent_kv(&self, table: String) -> Result<()> {
         let query_string = format!("DELETE FROM {}", table.as_str());
 
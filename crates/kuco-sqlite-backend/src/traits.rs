@@ -64,6 +64,40 @@ pub trait KucoSqliteStore: Send + Sync + 'static {
         Ok(row_option.map(|(value,)| value))
     }
 
+    /// When `key` was last written, in seconds since the epoch.
+    async fn get_updated_at(&self, table: String, key: String) -> Result<Option<i64>> {
+        let pool = self.get_pool()?;
+
+        let query_string = format!("SELECT updated_at FROM {} WHERE key = ?", table.as_str());
+
+        let row_option: Option<(i64,)> = sqlx::query_as(&query_string)
+            .bind(key.as_str())
+            .fetch_optional(*pool.as_ref())
+            .await
+            .wrap_err_with(|| format!("SqliteCache: Failed to get age of key '{}'", key))?;
+
+        Ok(row_option.map(|(updated_at,)| updated_at))
+    }
+
+    /// Every key in `table` starting with `prefix`.
+    async fn get_keys(&self, table: String, prefix: String) -> Result<Vec<String>> {
+        let pool = self.get_pool()?;
+
+        // Compared with substr rather than LIKE, since '_' in the prefixes is a LIKE wildcard.
+        let query_string = format!(
+            "SELECT key FROM {} WHERE substr(key, 1, length(?1)) = ?1",
+            table.as_str()
+        );
+
+        let rows: Vec<(String,)> = sqlx::query_as(&query_string)
+            .bind(prefix.as_str())
+            .fetch_all(*pool.as_ref())
+            .await
+            .wrap_err_with(|| format!("SqliteCache: Failed to get keys '{}*'", prefix))?;
+
+        Ok(rows.into_iter().map(|(key,)| key).collect())
+    }
+
     #[cfg(feature = "serde_support")]
     async fn set_json<S: Serialize + Send + Sync + 'static>(
         &self,
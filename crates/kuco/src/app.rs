@@ -1,18 +1,72 @@
-use std::sync::Arc;
+use std::{fmt::Display, path::Path, sync::Arc, time::SystemTime};
 
-use kuco_sqlite_backend::{SqliteCache, SqliteDb};
-use nucleo_matcher::{
-    Config, Matcher,
-    pattern::{CaseMatching, Normalization, Pattern},
+use chrono::Local;
+use kube::Client;
+use kuco_k8s_backend::{
+    autoscaling::{find_hpa_for, set_hpa_replicas},
+    context::KubeContext,
+    delete::{DeleteOptions, DeleteProgress, PodOwner, delete_pod, get_pod_owner},
+    drain::{DrainProgress, drain_node},
+    dynamic::get_object,
+    events::{EventChange, EventScope, watch_events},
+    nodes::set_unschedulable,
+    pods::{PodSelector, get_pod_manifest},
+    restart::restart_workload,
+    scale::{SCALABLE_KINDS, get_replicas, set_replicas},
 };
+use kuco_sqlite_backend::{AuditFilter, KucoSqliteStore, SqliteCache, SqliteDb};
 use ratatui::{
     DefaultTerminal,
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
 };
+use serde_json::{Value, json};
+use tokio::task::JoinHandle;
 
-use crate::data::{KubeComponentState, KubeWidgetState};
+use crate::audit::{self, audit_entry, parse_filter};
+use crate::cli::LaunchTarget;
+use crate::command::{Command, CommandError, command_names, parse};
+use crate::config::{KucoConfig, Protection};
+use crate::constants::{
+    ALL_NAMESPACES, COMMAND_HISTORY, FINDER_USAGE_KEY, HISTORY_LIMIT, SELECTOR_HISTORY,
+    TABLE_PREFS_KEY, VIEW_PREFS_TABLE,
+};
+use crate::data::{EventTypeFilter, KubeComponentState, KubeWidgetState, PodRef};
 use crate::event::{AppEvent, Event, EventHandler};
+use crate::finder::{Finder, FinderEntry};
+use crate::keymap::{Action, KeyResolution, Keymap};
+use crate::line_editor::EditOutcome;
+use crate::popup::{
+    BulkAction, BulkMenu, ColumnChooser, DRAIN_TIMEOUT_STEP, GRACE_PERIOD_STEP, HpaEdit,
+    OperationProgress, PodDelete, Popup, ProgressItem, ScaleEdit, TypedConfirm,
+};
+use crate::prompt::{CommandPrompt, SelectorPrompt};
+use crate::search::match_items;
+use crate::tables::{prefs_key, table_for_view};
 use crate::view::KubeWidget;
+use crate::yaml::{PAGE_LINES, YamlViewer, copy_to_clipboard};
+
+/// Ticks between checks of the config file for changes, about once a second.
+const CONFIG_CHECK_TICKS: u64 = 30;
+
+// Confirming a popup in a protected context first asks for the name to be typed out.
+fn confirm_popup(events: &mut EventHandler, confirm: &mut Option<TypedConfirm>, event: AppEvent) {
+    match confirm {
+        Some(confirm) if !confirm.is_confirmed() => confirm.typing = true,
+        _ => events.send(event),
+    }
+}
+
+fn delete_parameters(options: &DeleteOptions) -> Value {
+    json!({
+        "grace_period_seconds": options.grace_period_seconds(),
+        "force": options.force,
+        "propagation_policy": options.propagation.label(),
+    })
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
 
 #[derive(Debug)]
 pub struct SqlitePoolCtx {
@@ -36,6 +90,19 @@ pub struct Kuco {
     pub events: EventHandler,
     pub view: KubeWidget,
     pub cache: Option<Vec<String>>,
+    pub popup: Option<Popup>,
+    pub status_message: Option<String>,
+    pub event_watch: Option<JoinHandle<()>>,
+    pub selector_prompt: SelectorPrompt,
+    pub command_prompt: CommandPrompt,
+    pub config: KucoConfig,
+    pub keymap: Keymap,
+    /// Modification time of the config file when it was last read, to notice edits.
+    pub config_modified: Option<SystemTime>,
+    pub ticks: u64,
+    /// Set by `--readonly`: every action that changes the cluster is refused, whatever the
+    /// protection rules say.
+    pub readonly: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -44,28 +111,69 @@ pub struct Cache {
 }
 
 // TODO: Find a better place for this.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ViewMode {
     NS,
     PODS,
     CONT,
     LOGS,
+    NODES,
+    NODEPODS,
+    ALLPODS,
+    KINDS,
+    RESOURCES,
+    PVCS,
+    PVS,
+    EVENTS,
+    HPAS,
+    POLICY,
+    AUDIT,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InteractionMode {
     NORMAL,
     SEARCH,
+    SELECTOR,
+    COMMAND,
 }
 
 impl Kuco {
-    pub async fn new(sqlite_cache: Arc<SqliteCache>, sqlite_db: Arc<SqliteDb>) -> Self {
+    pub async fn new(
+        sqlite_cache: Arc<SqliteCache>,
+        sqlite_db: Arc<SqliteDb>,
+        config: KucoConfig,
+        keymap: Keymap,
+        kube_context: KubeContext,
+    ) -> Self {
+        let mut view = KubeWidget::new(sqlite_cache.clone()).await;
+        view.data.set_context(kube_context);
+        view.table_prefs = sqlite_db
+            .get_json(VIEW_PREFS_TABLE.to_owned(), TABLE_PREFS_KEY.to_owned())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load table preferences: {}", e);
+                None
+            })
+            .unwrap_or_default();
+        view.data.logs.tail_lines = config.logs.tail_lines;
+
         Self {
-            arc_ctx: SqlitePoolCtx::new(sqlite_cache.clone(), sqlite_db.clone()).into(),
+            arc_ctx: SqlitePoolCtx::new(sqlite_cache.clone(), sqlite_db.clone()),
             running: true,
             events: EventHandler::new(),
-            view: KubeWidget::new(sqlite_cache.clone()).await,
+            view,
             cache: None,
+            popup: None,
+            status_message: None,
+            event_watch: None,
+            selector_prompt: SelectorPrompt::default(),
+            command_prompt: CommandPrompt::default(),
+            config_modified: config.source.as_deref().and_then(modified_at),
+            config,
+            keymap,
+            ticks: 0,
+            readonly: false,
         }
     }
 
@@ -73,7 +181,6 @@ impl Kuco {
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
         let mut kube_state = KubeWidgetState::new();
 
-
         while self.running {
             // Set Mode-Specific Data
             // Using a reference here so that I don't need to copy state over and over ...
@@ -81,9 +188,9 @@ impl Kuco {
             match self.view.view_mode {
                 ViewMode::NS => {
                     if kube_state.namespace_state.list_state.selected().is_none() {
-                        // TODO: Figure out a better place for this. This is here currently 
+                        // TODO: Figure out a better place for this. This is here currently
                         //       because this should only trigger on startup, where the list
-                        //       selection is not yet set. This function needs to run after 
+                        //       selection is not yet set. This function needs to run after
                         //       self.running is true, but only once ...
                         self.view.update_widget_kube_data().await;
 
@@ -98,6 +205,7 @@ impl Kuco {
                     }
                     mode_state = &mut kube_state.pods_state;
                     self.refresh_pods_selection(mode_state);
+                    self.prune_marks(mode_state);
                 }
                 ViewMode::CONT => {
                     if kube_state.containers_state.list_state.selected().is_none() {
@@ -112,14 +220,104 @@ impl Kuco {
                     }
                     mode_state = &mut kube_state.logs_state;
                 }
+                ViewMode::NODES => {
+                    if kube_state.nodes_state.list_state.selected().is_none() {
+                        kube_state.nodes_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.nodes_state;
+                    self.refresh_nodes_selection(mode_state);
+                }
+                ViewMode::NODEPODS => {
+                    if kube_state.node_pods_state.list_state.selected().is_none() {
+                        kube_state.node_pods_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.node_pods_state;
+                    self.refresh_node_pods_selection(mode_state);
+                    self.prune_marks(mode_state);
+                }
+                ViewMode::ALLPODS => {
+                    if kube_state.all_pods_state.list_state.selected().is_none() {
+                        kube_state.all_pods_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.all_pods_state;
+                    self.refresh_all_pods_selection(mode_state);
+                    self.prune_marks(mode_state);
+                }
+                ViewMode::KINDS => {
+                    if kube_state.kinds_state.list_state.selected().is_none() {
+                        kube_state.kinds_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.kinds_state;
+                    self.refresh_kinds_selection(mode_state);
+                }
+                ViewMode::RESOURCES => {
+                    if kube_state.resources_state.list_state.selected().is_none() {
+                        kube_state.resources_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.resources_state;
+                    self.refresh_resources_selection(mode_state);
+                }
+                ViewMode::PVCS => {
+                    if kube_state.pvcs_state.list_state.selected().is_none() {
+                        kube_state.pvcs_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.pvcs_state;
+                    self.refresh_claims_selection(mode_state);
+                }
+                ViewMode::PVS => {
+                    if kube_state.pvs_state.list_state.selected().is_none() {
+                        kube_state.pvs_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.pvs_state;
+                    self.refresh_volumes_selection(mode_state);
+                }
+                ViewMode::EVENTS => {
+                    if kube_state.events_state.list_state.selected().is_none() {
+                        kube_state.events_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.events_state;
+                    self.refresh_events_selection(mode_state);
+                }
+                ViewMode::HPAS => {
+                    if kube_state.hpas_state.list_state.selected().is_none() {
+                        kube_state.hpas_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.hpas_state;
+                    self.refresh_hpas_selection(mode_state);
+                }
+                ViewMode::POLICY => {
+                    if kube_state.policy_state.list_state.selected().is_none() {
+                        kube_state.policy_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.policy_state;
+                }
+                ViewMode::AUDIT => {
+                    if kube_state.audit_state.list_state.selected().is_none() {
+                        kube_state.audit_state.list_state.select_first();
+                    }
+                    mode_state = &mut kube_state.audit_state;
+                }
+            }
+
+            // Land on the entry a jump was aimed at, rather than the top of the list.
+            if let Some(target) = self.view.pending_selection.take() {
+                if let Some(index) = self
+                    .view
+                    .display
+                    .as_ref()
+                    .and_then(|display| display.iter().position(|entry| entry == &target))
+                {
+                    mode_state.list_state.select(Some(index));
+                }
             }
 
             // Reset search buffer
             match self.view.interact_mode {
                 InteractionMode::NORMAL => {
-                    mode_state.search.input = "".to_owned();
+                    mode_state.search.editor.clear();
+                    self.view.search_matches.clear();
                 }
-                InteractionMode::SEARCH => {}
+                InteractionMode::SEARCH | InteractionMode::SELECTOR | InteractionMode::COMMAND => {}
             }
 
             terminal.draw(|frame| {
@@ -128,17 +326,84 @@ impl Kuco {
 
             match self.events.next().await? {
                 Event::Tick => self.tick(),
-                Event::Crossterm(event) => {
-                    if let crossterm::event::Event::Key(key_event) = event {
+                Event::Crossterm(event) => match event {
+                    crossterm::event::Event::Key(key_event) => {
                         self.handle_key_events(key_event, mode_state)?
                     }
-                }
+                    crossterm::event::Event::Paste(text) => self.handle_paste(&text, mode_state),
+                    _ => {}
+                },
                 Event::App(app_event) => match app_event {
                     // TODO: Implement a process that runs on another thread in a non-blocking
                     // fashion and continually updates the sqlite database with cluster
                     // information, and retool this event to pull data from the database ...
-                    AppEvent::Refresh => self.view.update_widget_kube_data().await,
+                    AppEvent::Refresh => {
+                        if self.view.view_mode == ViewMode::AUDIT {
+                            self.load_audit().await;
+                        }
+                        // Picks up CRDs installed since the kinds were first listed.
+                        if self.view.view_mode == ViewMode::KINDS {
+                            self.view.data.discover_api_kinds().await;
+                        }
+                        self.view.update_widget_kube_data().await
+                    }
                     AppEvent::Quit => self.quit(),
+                    AppEvent::NavNodes => {
+                        self.view.view_mode = ViewMode::NODES;
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::NavKinds => {
+                        self.view.view_mode = ViewMode::KINDS;
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::NavStorage => {
+                        self.view.view_mode = match self.view.view_mode {
+                            ViewMode::PVCS => ViewMode::PVS,
+                            _ => ViewMode::PVCS,
+                        };
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::Cordon => self.set_current_node_unschedulable(true).await,
+                    AppEvent::Uncordon => self.set_current_node_unschedulable(false).await,
+                    AppEvent::Drain => self.start_drain(),
+                    AppEvent::DrainProgress(progress) => self.handle_drain_progress(progress),
+                    AppEvent::OpenDelete => self.open_delete().await,
+                    AppEvent::Delete => self.start_delete(),
+                    AppEvent::DeleteProgress(progress) => self.handle_delete_progress(progress),
+                    AppEvent::OperationItem(item) => {
+                        if let Some(Popup::Progress(operation)) = self.popup.as_mut() {
+                            operation.update(item.key, item.status, item.failed);
+                        }
+                    }
+                    AppEvent::Bulk(action) => self.start_bulk(action, mode_state).await,
+                    AppEvent::OperationDone(error) => {
+                        if let Some(Popup::Progress(operation)) = self.popup.as_mut() {
+                            operation.finish(error);
+                        }
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::NavHpas => {
+                        self.view.view_mode = ViewMode::HPAS;
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::OpenYaml => self.open_yaml().await,
+                    AppEvent::NavPolicy => {
+                        self.view.view_mode = ViewMode::POLICY;
+                        self.view.update_widget_kube_data().await;
+                    }
+                    AppEvent::ApplyHpaEdit => self.apply_hpa_edit().await,
+                    AppEvent::OpenScale => self.open_scale().await,
+                    AppEvent::Scale => self.apply_scale().await,
+                    AppEvent::SaveTablePrefs => self.save_table_prefs().await,
+                    AppEvent::NavEvents(scope) => self.open_events(scope).await,
+                    AppEvent::ToggleEventWatch => self.toggle_event_watch(),
+                    AppEvent::EventWatch(event) => self.handle_event_watch(event, mode_state),
+                    AppEvent::OpenSelector => self.open_selector().await,
+                    AppEvent::OpenFinder => self.open_finder().await,
+                    AppEvent::FinderJump => self.finder_jump().await,
+                    AppEvent::OpenCommand => self.open_command().await,
+                    AppEvent::RunCommand => self.run_command().await,
+                    AppEvent::ApplySelector => self.apply_selector(mode_state).await,
                     AppEvent::NavRight => match self.view.view_mode {
                         ViewMode::NS => {
                             self.transition_ns_to_pod_view(mode_state).await;
@@ -150,6 +415,29 @@ impl Kuco {
                             self.transition_cont_to_log_view(mode_state).await;
                         }
                         ViewMode::LOGS => {}
+                        ViewMode::NODES => {
+                            self.transition_node_to_node_pods_view(mode_state).await;
+                        }
+                        ViewMode::NODEPODS => {
+                            self.transition_node_pods_to_cont_view(mode_state).await;
+                        }
+                        ViewMode::ALLPODS => {
+                            self.transition_all_pods_to_cont_view(mode_state).await;
+                        }
+                        ViewMode::KINDS => {
+                            self.transition_kinds_to_resources_view(mode_state).await;
+                        }
+                        ViewMode::RESOURCES => {}
+                        ViewMode::PVCS => {}
+                        ViewMode::PVS => {}
+                        ViewMode::EVENTS => {
+                            self.transition_event_to_object_view(mode_state).await;
+                        }
+                        ViewMode::HPAS => {
+                            self.transition_hpa_to_target_view(mode_state).await;
+                        }
+                        ViewMode::POLICY => {}
+                        ViewMode::AUDIT => {}
                     },
                     AppEvent::NavLeft => match self.view.view_mode {
                         ViewMode::NS => {}
@@ -169,6 +457,14 @@ impl Kuco {
                             self.view.data.current_container_name = None;
                             mode_state.list_state.select(Some(0));
                         }
+                        ViewMode::LOGS if !self.view.data.merged_logs.is_empty() => {
+                            self.view.data.merged_logs.clear();
+                            self.view.view_mode =
+                                self.view.return_view_mode.take().unwrap_or(ViewMode::PODS);
+                            self.view.update_widget_kube_data().await;
+
+                            mode_state.list_state.select(Some(0));
+                        }
                         ViewMode::LOGS => {
                             self.view.view_mode = ViewMode::CONT;
 
@@ -177,6 +473,76 @@ impl Kuco {
                             self.view.data.current_log_line = None;
                             mode_state.list_state.select(Some(0));
                         }
+                        ViewMode::NODES => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_node_name = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::NODEPODS => {
+                            self.view.view_mode = ViewMode::NODES;
+                            self.view.update_widget_kube_data().await;
+
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::ALLPODS => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_pod_name = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::KINDS => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::RESOURCES => {
+                            self.view.view_mode = ViewMode::KINDS;
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_resource_name = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::PVCS | ViewMode::PVS => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_claim_name = None;
+                            self.view.data.current_volume_name = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::EVENTS => {
+                            self.stop_event_watch();
+                            self.view.view_mode =
+                                self.view.return_view_mode.take().unwrap_or(ViewMode::NS);
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_event_key = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::HPAS => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            self.view.data.current_hpa_name = None;
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::POLICY => {
+                            self.view.view_mode = ViewMode::NS;
+                            self.view.update_widget_kube_data().await;
+
+                            mode_state.list_state.select(Some(0));
+                        }
+                        ViewMode::AUDIT => {
+                            self.view.view_mode =
+                                self.view.return_view_mode.take().unwrap_or(ViewMode::NS);
+                            self.view.update_widget_kube_data().await;
+
+                            mode_state.list_state.select(Some(0));
+                        }
                     },
                 },
             }
@@ -185,197 +551,1791 @@ impl Kuco {
         Ok(())
     }
 
+    /// Inserts bracketed paste into whichever prompt is open.
+    pub fn handle_paste(&mut self, text: &str, mode_state: &mut KubeComponentState) {
+        match &mut self.popup {
+            Some(Popup::Finder(finder)) => return finder.paste(text),
+            Some(Popup::Yaml(viewer)) if viewer.searching => return viewer.paste(text),
+            _ => {}
+        }
+
+        match self.view.interact_mode {
+            InteractionMode::NORMAL => {}
+            InteractionMode::SEARCH => {
+                mode_state.search.editor.insert_str(text);
+                self.search(mode_state, self.cache.clone());
+            }
+            InteractionMode::SELECTOR => self.selector_prompt.editor.insert_str(text),
+            InteractionMode::COMMAND => {
+                self.command_prompt.editor.insert_str(text);
+                self.command_prompt.error = None;
+            }
+        }
+    }
+
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_events(
         &mut self,
         key_event: KeyEvent,
         mode_state: &mut KubeComponentState,
     ) -> color_eyre::Result<()> {
-        match self.view.interact_mode {
-            InteractionMode::NORMAL => {
-                // Handle key events
-                match key_event.code {
-                    KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::Quit),
-                    KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-                        self.events.send(AppEvent::Quit)
-                    }
+        if self.popup.is_some() {
+            self.keymap.reset();
+            return self.handle_popup_key_events(key_event);
+        }
+        self.status_message = None;
 
-                    // Refresh
-                    KeyCode::Char('r') => self.events.send(AppEvent::Refresh),
-
-                    // Modes
-                    KeyCode::Char('/') => self.view.interact_mode = InteractionMode::SEARCH,
-
-                    // Navigation
-                    KeyCode::Right | KeyCode::Char('l') => self.events.send(AppEvent::NavRight),
-                    KeyCode::Left | KeyCode::Char('h') => self.events.send(AppEvent::NavLeft),
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        // Check for list length (since display and list_state.selected are set on
-                        // initialization, I'm using unwrap() for now ... TODO: replace late with
-                        // something less sketchy.)
-                        if !self.view.display.clone().unwrap().is_empty()
-                            && mode_state.list_state.selected().unwrap()
-                                <= self.view.display.clone().unwrap().len() - 2_usize
-                        {
-                            mode_state.list_state.select_next()
-                        }
-                    }
-                    KeyCode::Down | KeyCode::Char('j') => mode_state.list_state.select_previous(),
+        // Init cache when search mode is turned on
+        if self.view.interact_mode == InteractionMode::SEARCH && self.cache.is_none() {
+            self.cache = self.view.display.clone();
+        }
 
-                    _ => {}
+        match self
+            .keymap
+            .resolve(&self.view.interact_mode, &self.view.view_mode, &key_event)
+        {
+            KeyResolution::Action(action) => self.handle_action(action, mode_state),
+            KeyResolution::Pending => {}
+            KeyResolution::Unbound => self.handle_prompt_input(&key_event, mode_state),
+        }
+        Ok(())
+    }
+
+    // Actions bound to a single view are ignored elsewhere, in case a binding was moved to a
+    // wider scope in the config.
+    fn handle_action(&mut self, action: Action, mode_state: &mut KubeComponentState) {
+        let view_mode = self.view.view_mode.clone();
+        let is_pod_view = matches!(
+            view_mode,
+            ViewMode::PODS | ViewMode::ALLPODS | ViewMode::NODEPODS
+        );
+        match action {
+            _ if action.mutates() && self.protection().readonly => {
+                self.status_message = Some(self.readonly_message())
+            }
+            // The current namespace stays on the last real one while the all namespaces row is
+            // selected, so views scoped to it would show a namespace the user did not pick.
+            Action::Events
+            | Action::ObjectEvents
+            | Action::Storage
+            | Action::Hpas
+            | Action::Policy
+            | Action::ViewYaml
+                if view_mode == ViewMode::NS
+                    && self
+                        .selected_entry(mode_state)
+                        .is_some_and(|ns| ns == ALL_NAMESPACES) =>
+            {
+                self.status_message = Some(format!(
+                    "select a namespace first, {} is not one",
+                    ALL_NAMESPACES
+                ));
+            }
+            Action::Quit => self.events.send(AppEvent::Quit),
+            Action::Refresh => self.events.send(AppEvent::Refresh),
+            Action::OpenFinder => self.events.send(AppEvent::OpenFinder),
+
+            // Modes
+            Action::Search => self.view.interact_mode = InteractionMode::SEARCH,
+            Action::Command => self.events.send(AppEvent::OpenCommand),
+
+            // Navigation
+            Action::NavRight => self.events.send(AppEvent::NavRight),
+            Action::NavLeft => self.events.send(AppEvent::NavLeft),
+            Action::Up => {
+                // Check for list length (since display and list_state.selected are set on
+                // initialization, I'm using unwrap() for now ... TODO: replace late with
+                // something less sketchy.)
+                if !self.view.display.clone().unwrap().is_empty()
+                    && mode_state.list_state.selected().unwrap()
+                        <= self.view.display.clone().unwrap().len() - 2_usize
+                {
+                    mode_state.list_state.select_next()
                 }
             }
-            InteractionMode::SEARCH => {
-                let matcher = Matcher::new(Config::DEFAULT.match_paths());
+            Action::Down => mode_state.list_state.select_previous(),
+            Action::Top => {
+                let len = self.view.display.as_ref().map_or(0, |d| d.len());
+                if len > 0 {
+                    mode_state.list_state.select(Some(len - 1));
+                }
+            }
+            Action::Bottom => mode_state.list_state.select_first(),
 
-                // Init cache when search mode is turned on
-                match self.cache {
-                    Some(_) => {}
-                    None => {
-                        self.cache = self.view.display.clone();
-                    }
+            // Cluster-scoped Views
+            Action::Nodes => self.events.send(AppEvent::NavNodes),
+            Action::Kinds => self.events.send(AppEvent::NavKinds),
+            Action::Storage => self.events.send(AppEvent::NavStorage),
+            Action::Hpas => self.events.send(AppEvent::NavHpas),
+            Action::Events => {
+                let scope = EventScope {
+                    namespace: self.current_namespace(),
+                    object: None,
+                };
+                self.events.send(AppEvent::NavEvents(scope));
+            }
+            Action::ObjectEvents => {
+                if let Some(object) = self.current_event_object() {
+                    let scope = EventScope {
+                        namespace: self.current_namespace(),
+                        object: Some(object),
+                    };
+                    self.events.send(AppEvent::NavEvents(scope));
                 }
+            }
 
-                match key_event.code {
-                    KeyCode::Esc => {
-                        self.view.interact_mode = InteractionMode::NORMAL;
-                        self.cache = None; // Delete cached display list
-                        self.events.send(AppEvent::Refresh);
-                    }
+            Action::ViewYaml => self.events.send(AppEvent::OpenYaml),
 
-                    KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-                        self.events.send(AppEvent::Quit)
-                    }
+            // Namespace Policy
+            Action::Policy if view_mode == ViewMode::NS => self.events.send(AppEvent::NavPolicy),
 
-                    // Navigation
-                    KeyCode::Right | KeyCode::Enter => {
-                        self.events.send(AppEvent::NavRight);
-                        self.view.interact_mode = InteractionMode::NORMAL;
-                    }
-                    KeyCode::Left => self.events.send(AppEvent::NavLeft),
-                    KeyCode::Up => {
-                        // Check for list length (since display and list_state.selected are set on
-                        // initialization, I'm using unwrap() for now ... TODO: replace late with
-                        // something less sketchy.)
-                        if !self.view.display.clone().unwrap().is_empty()
-                            && mode_state.list_state.selected().unwrap()
-                                <= self.view.display.clone().unwrap().len() - 2_usize
-                        {
-                            mode_state.list_state.select_next()
-                        }
-                    }
-                    KeyCode::Down => mode_state.list_state.select_previous(),
+            // Scaling
+            Action::EditHpa if view_mode == ViewMode::HPAS => {
+                if let Some(hpa) = self.view.data.current_hpa().cloned() {
+                    let namespace = self.current_namespace();
+                    let mut edit = HpaEdit::new(namespace.clone(), hpa);
+                    edit.confirm =
+                        TypedConfirm::required(self.protection().confirm_by_name, &namespace);
+                    self.popup = Some(Popup::HpaEdit(edit));
+                }
+            }
+            Action::Scale if view_mode == ViewMode::RESOURCES => {
+                self.events.send(AppEvent::OpenScale)
+            }
 
-                    // Search Entry
-                    KeyCode::Char(to_insert) => {
-                        // Check if search buffer is clear or not, and swap search state if it is.
-                        if !mode_state.search.input.is_empty() {
-                            self.search(mode_state, matcher, self.view.display.clone());
-                        }
+            // Pod Deletion
+            Action::Delete if is_pod_view => self.events.send(AppEvent::OpenDelete),
+
+            // Marks & Bulk Actions
+            Action::ToggleMark if is_pod_view => {
+                if let Some(pod) = self.selected_pod(mode_state) {
+                    mode_state.toggle_mark(pod);
+                }
+            }
+            Action::MarkRange if is_pod_view => {
+                if let Some(pod) = self.selected_pod(mode_state) {
+                    mode_state.mark_range(&self.displayed_pods(), pod);
+                }
+            }
+            Action::MarkAll if is_pod_view => mode_state.mark_all(&self.displayed_pods()),
+            Action::BulkActions if is_pod_view => self.open_bulk(mode_state),
+
+            // Label & Field Selectors
+            Action::Selector if matches!(view_mode, ViewMode::PODS | ViewMode::ALLPODS) => {
+                self.events.send(AppEvent::OpenSelector)
+            }
+
+            // Table Sorting & Columns
+            Action::SortNext => self.cycle_sort(false),
+            Action::SortPrev => self.cycle_sort(true),
+            Action::Columns => self.open_column_chooser(),
+
+            // Event Filters & Live Mode
+            Action::EventTypeFilter if view_mode == ViewMode::EVENTS => {
+                self.view.data.event_type_filter = self.view.data.event_type_filter.next();
+                self.refresh_events_display(mode_state);
+            }
+            Action::EventReasonFilter if view_mode == ViewMode::EVENTS => {
+                self.view.data.cycle_event_reason_filter();
+                self.refresh_events_display(mode_state);
+            }
+            Action::LiveEvents if view_mode == ViewMode::EVENTS => {
+                self.events.send(AppEvent::ToggleEventWatch)
+            }
+
+            // Node Maintenance
+            Action::Cordon if view_mode == ViewMode::NODES => self.events.send(AppEvent::Cordon),
+            Action::Uncordon if view_mode == ViewMode::NODES => {
+                self.events.send(AppEvent::Uncordon)
+            }
+            Action::Drain if view_mode == ViewMode::NODES => {
+                if let Some(node) = self.view.data.current_node_name.clone() {
+                    let confirm = TypedConfirm::required(self.protection().confirm_by_name, &node);
+                    self.popup = Some(Popup::drain_confirm(node, confirm));
+                }
+            }
 
-                        mode_state.search.input += &to_insert.to_string();
+            // Prompts
+            Action::Cancel => {
+                if self.view.interact_mode == InteractionMode::SEARCH {
+                    self.cache = None; // Delete cached display list
+                    self.events.send(AppEvent::Refresh);
+                }
+                self.view.interact_mode = InteractionMode::NORMAL;
+            }
+            Action::Submit => match self.view.interact_mode {
+                InteractionMode::NORMAL => self.events.send(AppEvent::NavRight),
+                InteractionMode::SEARCH => {
+                    mode_state.search.editor.push_history();
+                    self.events.send(AppEvent::NavRight);
+                    self.view.interact_mode = InteractionMode::NORMAL;
+                }
+                InteractionMode::SELECTOR => self.events.send(AppEvent::ApplySelector),
+                InteractionMode::COMMAND => self.events.send(AppEvent::RunCommand),
+            },
+            Action::Complete => {
+                let candidates = match self.view.interact_mode {
+                    InteractionMode::SELECTOR => {
+                        self.selector_prompt.complete(&self.view.data.pod_rows_list)
                     }
-                    KeyCode::Backspace => {
-                        let s = &mut mode_state.search.input;
-                        if !s.is_empty() {
-                            s.truncate(s.len() - 1);
-                            mode_state.search.input = s.to_string();
-                        }
-                        self.search(mode_state, matcher, self.cache.clone());
+                    InteractionMode::COMMAND => {
+                        let kinds: Vec<String> = self
+                            .view
+                            .data
+                            .api_kinds
+                            .iter()
+                            .map(|k| k.plural.clone())
+                            .collect();
+                        let names = command_names(&self.config.aliases, &kinds);
+                        self.command_prompt.complete(
+                            &names,
+                            &self.view.data.namespace_names_list,
+                            &self.config.aliases,
+                        )
                     }
-                    _ => {}
+                    _ => Vec::new(),
+                };
+                if !candidates.is_empty() {
+                    self.status_message = Some(candidates.join("  "));
                 }
             }
+            Action::MatchMode if self.view.interact_mode == InteractionMode::SEARCH => {
+                mode_state.search.mode = mode_state.search.mode.next();
+                self.search(mode_state, self.cache.clone());
+            }
+            _ => {}
         }
-        Ok(())
     }
 
-    // TODO: build a better implementation of this ...
-    fn search(
-        &mut self,
-        state: &mut KubeComponentState,
-        mut matcher: Matcher,
-        current_list: Option<Vec<String>>,
-    ) {
-        let pattern = &state.search.input;
-        if let Some(display) = current_list {
-            let matches = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart)
-                .match_list(display, &mut matcher);
-
-            state.list_state.select(Some(0));
-            self.view.display = Some(
-                matches
-                    .iter()
-                    .map(|matched_item| matched_item.0.to_owned())
-                    .collect(),
-            );
+    // Keys no binding claimed are typed into the open prompt.
+    fn handle_prompt_input(&mut self, key_event: &KeyEvent, mode_state: &mut KubeComponentState) {
+        match self.view.interact_mode {
+            InteractionMode::NORMAL => {}
+            InteractionMode::SEARCH => {
+                if mode_state.search.editor.handle_key(key_event) == EditOutcome::Changed {
+                    self.search(mode_state, self.cache.clone());
+                }
+            }
+            InteractionMode::SELECTOR => {
+                self.selector_prompt.editor.handle_key(key_event);
+            }
+            InteractionMode::COMMAND => {
+                if self.command_prompt.editor.handle_key(key_event) == EditOutcome::Changed {
+                    self.command_prompt.error = None;
+                }
+            }
         }
     }
 
-    /// Handles the tick event of the terminal.
-    ///
-    /// The tick event is where you can update the state of your application with any logic that
-    /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&mut self) {}
-
-    /// Set running to false to quit the application.
-    pub fn quit(&mut self) {
-        self.running = false;
-    }
+    fn handle_popup_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
+        if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+            self.events.send(AppEvent::Quit);
+            return Ok(());
+        }
 
-    pub fn refresh_logs_selection(&mut self, component_state: &KubeComponentState) {
-        let lo_index = component_state.list_state.selected();
-        let lo_list = &self.view.display.as_ref().unwrap();
+        // While a name is being typed out, keys go to it rather than the popup, except for an
+        // Enter that completes it.
+        if let Some(confirm) = self
+            .popup
+            .as_mut()
+            .and_then(Popup::typed_confirm_mut)
+            .filter(|confirm| confirm.typing)
+        {
+            let completed = key_event.code == KeyCode::Enter && confirm.is_confirmed();
+            match key_event.code {
+                KeyCode::Char(c) => confirm.input.push(c),
+                KeyCode::Backspace => {
+                    confirm.input.pop();
+                }
+                KeyCode::Esc => {
+                    confirm.typing = false;
+                    confirm.input.clear();
+                }
+                _ => {}
+            }
+            if !completed {
+                return Ok(());
+            }
+        }
 
-        let mut lo: &String = &"none".to_string();
-        if !lo_list.is_empty() {
-            lo = &lo_list[lo_index.unwrap()];
+        match self.popup.as_mut() {
+            Some(Popup::DrainConfirm {
+                options, confirm, ..
+            }) => match key_event.code {
+                KeyCode::Char('f') => options.force = !options.force,
+                KeyCode::Char('+') => options.timeout += DRAIN_TIMEOUT_STEP,
+                KeyCode::Char('-') if options.timeout > DRAIN_TIMEOUT_STEP => {
+                    options.timeout -= DRAIN_TIMEOUT_STEP
+                }
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    confirm_popup(&mut self.events, confirm, AppEvent::Drain)
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::HpaEdit(edit)) => match key_event.code {
+                KeyCode::Tab | KeyCode::BackTab => edit.editing_max = !edit.editing_max,
+                KeyCode::Char('+') | KeyCode::Up => edit.adjust(1),
+                KeyCode::Char('-') | KeyCode::Down => edit.adjust(-1),
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    confirm_popup(&mut self.events, &mut edit.confirm, AppEvent::ApplyHpaEdit)
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::Scale(scale)) => match key_event.code {
+                KeyCode::Char('+') | KeyCode::Up => scale.replicas += 1,
+                KeyCode::Char('-') | KeyCode::Down => scale.replicas = (scale.replicas - 1).max(0),
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    confirm_popup(&mut self.events, &mut scale.confirm, AppEvent::Scale)
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::Delete(delete)) => match key_event.code {
+                KeyCode::Char('+') | KeyCode::Up => delete.adjust_grace_period(GRACE_PERIOD_STEP),
+                KeyCode::Char('-') | KeyCode::Down => {
+                    delete.adjust_grace_period(-GRACE_PERIOD_STEP)
+                }
+                KeyCode::Char('f') => delete.options.force = !delete.options.force,
+                KeyCode::Char('p') => {
+                    delete.options.propagation = delete.options.propagation.next()
+                }
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    confirm_popup(&mut self.events, &mut delete.confirm, AppEvent::Delete)
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::Bulk(bulk)) => match (bulk.pending, key_event.code) {
+                (Some(BulkAction::Delete), KeyCode::Char('+') | KeyCode::Up) => {
+                    bulk.adjust_grace_period(GRACE_PERIOD_STEP)
+                }
+                (Some(BulkAction::Delete), KeyCode::Char('-') | KeyCode::Down) => {
+                    bulk.adjust_grace_period(-GRACE_PERIOD_STEP)
+                }
+                (Some(BulkAction::Delete), KeyCode::Char('f')) => {
+                    bulk.delete_options.force = !bulk.delete_options.force
+                }
+                (Some(BulkAction::Delete), KeyCode::Char('p')) => {
+                    bulk.delete_options.propagation = bulk.delete_options.propagation.next()
+                }
+                (Some(action), KeyCode::Char('y') | KeyCode::Enter) => {
+                    confirm_popup(&mut self.events, &mut bulk.confirm, AppEvent::Bulk(action))
+                }
+                (Some(_), KeyCode::Char('n') | KeyCode::Esc) => {
+                    bulk.pending = None;
+                    bulk.confirm = None;
+                }
+                (None, KeyCode::Char('d')) => self.pick_bulk_action(BulkAction::Delete),
+                (None, KeyCode::Char('r')) => self.pick_bulk_action(BulkAction::Restart),
+                (None, KeyCode::Char('l')) => self.pick_bulk_action(BulkAction::Logs),
+                (None, KeyCode::Char('y')) => self.pick_bulk_action(BulkAction::Export),
+                (None, KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q')) => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::Columns(chooser)) => match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => chooser.move_cursor(true),
+                KeyCode::Char('k') | KeyCode::Up => chooser.move_cursor(false),
+                KeyCode::Char(' ') | KeyCode::Char('x') => chooser.toggle(),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+                    let hidden = chooser.hidden.clone();
+                    self.popup = None;
+                    self.view
+                        .table_prefs
+                        .entry(prefs_key(&self.view.view_mode))
+                        .or_default()
+                        .hidden = hidden;
+                    self.events.send(AppEvent::SaveTablePrefs);
+                }
+                _ => {}
+            },
+            Some(Popup::Finder(finder)) => match key_event.code {
+                KeyCode::Esc => self.popup = None,
+                KeyCode::Enter => self.events.send(AppEvent::FinderJump),
+                KeyCode::Up => finder.move_cursor(false),
+                KeyCode::Down => finder.move_cursor(true),
+                KeyCode::Char('p') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    finder.move_cursor(false)
+                }
+                KeyCode::Char('n') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    finder.move_cursor(true)
+                }
+                _ => finder.handle_key(&key_event),
+            },
+            Some(Popup::Yaml(viewer)) if viewer.searching => match key_event.code {
+                KeyCode::Enter => viewer.stop_search(false),
+                KeyCode::Esc => viewer.stop_search(true),
+                _ => viewer.handle_search_key(&key_event),
+            },
+            Some(Popup::Yaml(viewer)) => match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => viewer.scroll_by(1),
+                KeyCode::Char('k') | KeyCode::Up => viewer.scroll_by(-1),
+                KeyCode::Char('d') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    viewer.scroll_by(PAGE_LINES as isize)
+                }
+                KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    viewer.scroll_by(-(PAGE_LINES as isize))
+                }
+                KeyCode::PageDown => viewer.scroll_by(PAGE_LINES as isize),
+                KeyCode::PageUp => viewer.scroll_by(-(PAGE_LINES as isize)),
+                KeyCode::Char('g') | KeyCode::Home => viewer.scroll = 0,
+                KeyCode::Char('G') | KeyCode::End => viewer.scroll_to_end(),
+                KeyCode::Char('/') => viewer.start_search(),
+                KeyCode::Char('n') => viewer.next_match(true),
+                KeyCode::Char('N') => viewer.next_match(false),
+                KeyCode::Char('m') => viewer.toggle_managed_fields(),
+                KeyCode::Char('c') => {
+                    let lines = viewer.manifest.lines().count();
+                    self.status_message = Some(match copy_to_clipboard(&viewer.manifest) {
+                        Ok(_) => format!("copied {} lines of YAML to the clipboard", lines),
+                        Err(e) => format!("failed to copy to the clipboard: {}", e),
+                    });
+                }
+                KeyCode::Esc | KeyCode::Char('q') => self.popup = None,
+                _ => {}
+            },
+            Some(Popup::Progress(_)) => match key_event.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.popup = None,
+                _ => {}
+            },
+            None => {}
         }
 
-        // Select Log Line
-        self.view.data.current_log_line = Some(lo.clone());
+        Ok(())
     }
 
-    pub fn refresh_containers_selection(&mut self, component_state: &KubeComponentState) {
-        let co_index = component_state.list_state.selected();
-        let co_list = &self.view.display.as_ref().unwrap();
-
-        let mut co: &String = &"none".to_string();
-        if !co_list.is_empty() {
-            co = &co_list[co_index.unwrap()];
-        }
+    async fn set_current_node_unschedulable(&mut self, unschedulable: bool) {
+        let (Some(client), Some(node)) = (
+            self.view.data.client(),
+            self.view.data.current_node_name.clone(),
+        ) else {
+            return;
+        };
 
-        self.view.data.current_container_name = Some(co.clone());
+        let action = if unschedulable {
+            "cordoned"
+        } else {
+            "uncordoned"
+        };
+        let result = set_unschedulable(client, &node, unschedulable).await;
+        let audit_action = if unschedulable { "cordon" } else { "uncordon" };
+        self.audit("", "Node", &node, audit_action, json!({}), &result)
+            .await;
+        self.status_message = Some(match result {
+            Ok(_) => format!("node '{}' {}", node, action),
+            Err(e) => {
+                tracing::error!("Failed to update node {}: {}", node, e);
+                format!("failed to update node '{}': {}", node, e)
+            }
+        });
     }
 
-    pub fn refresh_pods_selection(&mut self, component_state: &KubeComponentState) {
-        let po_index = component_state.list_state.selected();
-        let po_list = &self.view.display.as_ref().unwrap();
+    // The drain runs on its own task; progress is forwarded into the event loop so the popup
+    // can be redrawn as each pod changes state.
+    fn start_drain(&mut self) {
+        let Some(Popup::DrainConfirm { node, options, .. }) = self.popup.take() else {
+            return;
+        };
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
 
-        let mut po: &String = &"-".to_string();
-        if !po_list.is_empty() {
-            po = &po_list[po_index.unwrap()];
-        }
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let events = self.events.sender();
+        let drain_node_name = node.clone();
+        let db = self.arc_ctx.db.clone();
+        let entry = audit_entry(
+            self.view.data.context_name(),
+            "",
+            "Node",
+            &node,
+            "drain",
+            json!({ "force": options.force, "timeout_secs": options.timeout.as_secs() }),
+        );
+        tokio::spawn(async move {
+            let result = drain_node(client, &drain_node_name, options, progress_tx).await;
+            audit::record(&db, entry, &result).await;
+        });
+        tokio::spawn(async move {
+            while let Some(progress) = progress_rx.recv().await {
+                let _ = events.send(Event::App(AppEvent::DrainProgress(progress)));
+            }
+        });
 
-        self.view.data.current_pod_name = Some(po.clone());
+        self.popup = Some(Popup::Progress(OperationProgress::new(format!(
+            "Draining {}",
+            node
+        ))));
     }
 
-    pub fn refresh_namespace_selection(&mut self, component_state: &KubeComponentState) {
-        let ns_index = component_state.list_state.selected();
-        let ns_list = &self.view.display.as_ref().unwrap();
+    fn handle_drain_progress(&mut self, progress: DrainProgress) {
+        // The popup may have been hidden while the drain keeps running in the background.
+        let Some(Popup::Progress(operation)) = self.popup.as_mut() else {
+            return;
+        };
 
-        let mut ns: &String = &"default".to_string();
-        if !ns_list.is_empty() {
-            ns = &ns_list[ns_index.unwrap()];
+        match progress {
+            DrainProgress::Pod {
+                namespace,
+                name,
+                status,
+            } => operation.update_drain(format!("{}/{}", namespace, name), status),
+            DrainProgress::Done(error) => operation.finish(error),
+        }
+    }
+
+    async fn open_delete(&mut self) {
+        let (Some(client), Some(name)) = (
+            self.view.data.client(),
+            self.view.data.current_pod_name.clone(),
+        ) else {
+            return;
+        };
+        let namespace = self.current_namespace();
+
+        let owner = match get_pod_owner(client, &namespace, &name).await {
+            Ok(owner) => owner,
+            Err(e) => {
+                self.status_message = Some(format!("failed to look up pod '{}': {}", name, e));
+                return;
+            }
+        };
+
+        let confirm = TypedConfirm::required(self.protection().confirm_by_name, &namespace);
+        self.popup = Some(Popup::Delete(PodDelete {
+            namespace,
+            name,
+            owner,
+            options: DeleteOptions::default(),
+            confirm,
+        }));
+    }
+
+    // Like a drain, the delete and the wait for its replacement run on their own task.
+    fn start_delete(&mut self) {
+        let Some(Popup::Delete(delete)) = self.popup.take() else {
+            return;
+        };
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let events = self.events.sender();
+        let db = self.arc_ctx.db.clone();
+        let entry = audit_entry(
+            self.view.data.context_name(),
+            &delete.namespace,
+            "Pod",
+            &delete.name,
+            "delete",
+            delete_parameters(&delete.options),
+        );
+        let title = format!("Deleting {}/{}", delete.namespace, delete.name);
+        tokio::spawn(async move {
+            let result = delete_pod(
+                client,
+                &delete.namespace,
+                &delete.name,
+                delete.options,
+                &progress_tx,
+            )
+            .await;
+            audit::record(&db, entry, &result).await;
+        });
+        let events_done = events.clone();
+        tokio::spawn(async move {
+            while let Some(progress) = progress_rx.recv().await {
+                let _ = events.send(Event::App(AppEvent::DeleteProgress(progress)));
+            }
+            let _ = events_done.send(Event::App(AppEvent::OperationDone(None)));
+        });
+
+        self.popup = Some(Popup::Progress(OperationProgress::new(title)));
+    }
+
+    fn handle_delete_progress(&mut self, progress: DeleteProgress) {
+        let Some(Popup::Progress(operation)) = self.popup.as_mut() else {
+            return;
+        };
+        let key = format!("{}/{}", progress.namespace, progress.name);
+        operation.update_delete(key, progress.status);
+    }
+
+    fn selected_entry(&self, mode_state: &KubeComponentState) -> Option<String> {
+        let index = mode_state.list_state.selected()?;
+        self.view.display.as_ref()?.get(index).cloned()
+    }
+
+    fn prune_marks(&self, mode_state: &mut KubeComponentState) {
+        if !mode_state.marks.is_empty() {
+            mode_state.retain_marks(&self.view.listed_pods());
+        }
+    }
+
+    fn selected_pod(&self, mode_state: &KubeComponentState) -> Option<PodRef> {
+        self.view.pod_ref(&self.selected_entry(mode_state)?)
+    }
+
+    /// The pods in the display list, i.e. whatever the search left.
+    fn displayed_pods(&self) -> Vec<PodRef> {
+        self.view
+            .display
+            .iter()
+            .flatten()
+            .filter_map(|key| self.view.pod_ref(key))
+            .collect()
+    }
+
+    /// Open the bulk popup for the marked pods, or the selected one when none are marked.
+    fn open_bulk(&mut self, mode_state: &KubeComponentState) {
+        let pods: Vec<PodRef> = if mode_state.marks.is_empty() {
+            self.selected_pod(mode_state).into_iter().collect()
+        } else {
+            mode_state.marks.iter().cloned().collect()
+        };
+        let targets: Vec<(String, String)> = pods
+            .into_iter()
+            .map(|pod| (pod.namespace, pod.name))
+            .collect();
+
+        if !targets.is_empty() {
+            self.popup = Some(Popup::Bulk(BulkMenu::new(targets)));
+        }
+    }
+
+    // Read-only actions run straight away; changes wait for a confirmation, with the namespaces
+    // to type out in contexts that ask for it.
+    fn pick_bulk_action(&mut self, action: BulkAction) {
+        if !action.mutates() {
+            self.events.send(AppEvent::Bulk(action));
+            return;
+        }
+
+        let protection = self.protection();
+        if protection.readonly {
+            self.status_message = Some(self.readonly_message());
+            return;
+        }
+        let Some(Popup::Bulk(bulk)) = self.popup.as_mut() else {
+            return;
+        };
+        bulk.confirm =
+            TypedConfirm::required(protection.confirm_by_name, &bulk.namespaces().join(","));
+        bulk.pending = Some(action);
+    }
+
+    async fn start_bulk(&mut self, action: BulkAction, mode_state: &mut KubeComponentState) {
+        let Some(Popup::Bulk(bulk)) = self.popup.take() else {
+            return;
+        };
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+
+        match action {
+            BulkAction::Delete => self.start_bulk_delete(client, bulk.targets, bulk.delete_options),
+            BulkAction::Restart => self.start_bulk_restart(client, bulk.targets),
+            BulkAction::Logs => self.open_merged_logs(bulk.targets).await,
+            BulkAction::Export => self.start_export(client, bulk.targets),
+        }
+
+        // The marked pods are on their way out, so the marks would only linger on stale names.
+        if action.mutates() {
+            mode_state.marks.clear();
+            mode_state.mark_anchor = None;
+        }
+    }
+
+    // Every pod is deleted on its own task, all reporting into the one progress popup.
+    fn start_bulk_delete(
+        &mut self,
+        client: Client,
+        targets: Vec<(String, String)>,
+        options: DeleteOptions,
+    ) {
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let events = self.events.sender();
+        let title = format!("Deleting {} pods", targets.len());
+
+        for (namespace, name) in targets {
+            let entry = audit_entry(
+                self.view.data.context_name(),
+                &namespace,
+                "Pod",
+                &name,
+                "delete",
+                delete_parameters(&options),
+            );
+            let client = client.clone();
+            let db = self.arc_ctx.db.clone();
+            let options = options.clone();
+            let progress_tx = progress_tx.clone();
+            tokio::spawn(async move {
+                let result = delete_pod(client, &namespace, &name, options, &progress_tx).await;
+                audit::record(&db, entry, &result).await;
+            });
+        }
+        drop(progress_tx);
+
+        let events_done = events.clone();
+        tokio::spawn(async move {
+            while let Some(progress) = progress_rx.recv().await {
+                let _ = events.send(Event::App(AppEvent::DeleteProgress(progress)));
+            }
+            let _ = events_done.send(Event::App(AppEvent::OperationDone(None)));
+        });
+
+        self.popup = Some(Popup::Progress(OperationProgress::new(title)));
+    }
+
+    // Pods sharing a workload restart it once, and each of them reports how that went.
+    fn start_bulk_restart(&mut self, client: Client, targets: Vec<(String, String)>) {
+        let events = self.events.sender();
+        let db = self.arc_ctx.db.clone();
+        let context = self.view.data.context_name().map(str::to_owned);
+        let title = format!("Restarting the owners of {} pods", targets.len());
+
+        tokio::spawn(async move {
+            let report = |key: String, status: String, failed: bool| {
+                let item = ProgressItem {
+                    key,
+                    status,
+                    failed,
+                };
+                let _ = events.send(Event::App(AppEvent::OperationItem(item)));
+            };
+
+            // (namespace, kind, name) of each workload, with the pods it owns.
+            let mut workloads: Vec<((String, String, String), Vec<String>)> = Vec::new();
+            for (namespace, pod) in targets {
+                let key = format!("{}/{}", namespace, pod);
+                let owner = match get_pod_owner(client.clone(), &namespace, &pod).await {
+                    Ok(owner) => owner,
+                    Err(e) => {
+                        report(key, format!("failed: {}", e), true);
+                        continue;
+                    }
+                };
+                let Some((kind, name)) = owner.as_ref().and_then(PodOwner::workload) else {
+                    report(
+                        key,
+                        "skipped: no Deployment, StatefulSet or DaemonSet owns it".to_owned(),
+                        true,
+                    );
+                    continue;
+                };
+
+                report(
+                    key.clone(),
+                    format!("restarting {} '{}' ...", kind, name),
+                    false,
+                );
+                let workload = (namespace.clone(), kind.to_owned(), name.to_owned());
+                match workloads.iter_mut().find(|(w, _)| *w == workload) {
+                    Some((_, pods)) => pods.push(key),
+                    None => workloads.push((workload, vec![key])),
+                }
+            }
+
+            for ((namespace, kind, name), pods) in workloads {
+                let result = restart_workload(client.clone(), &namespace, &kind, &name).await;
+                let (status, failed) = match &result {
+                    Ok(_) => (format!("restarted {} '{}'", kind, name), false),
+                    Err(e) => (
+                        format!("failed to restart {} '{}': {}", kind, name, e),
+                        true,
+                    ),
+                };
+                let entry = audit_entry(
+                    context.as_deref(),
+                    &namespace,
+                    &kind,
+                    &name,
+                    "restart",
+                    json!({ "pods": pods }),
+                );
+                audit::record(&db, entry, &result).await;
+
+                for pod in pods {
+                    report(pod, status.clone(), failed);
+                }
+            }
+
+            let _ = events.send(Event::App(AppEvent::OperationDone(None)));
+        });
+
+        self.popup = Some(Popup::Progress(OperationProgress::new(title)));
+    }
+
+    async fn open_merged_logs(&mut self, targets: Vec<(String, String)>) {
+        self.view.data.merged_logs = targets;
+        self.view.return_view_mode = Some(self.view.view_mode.clone());
+        self.view.view_mode = ViewMode::LOGS;
+        self.view.update_widget_kube_data().await;
+    }
+
+    /// Write the manifests of the pods to one multi-document YAML file in the working directory.
+    fn start_export(&mut self, client: Client, targets: Vec<(String, String)>) {
+        let events = self.events.sender();
+        let path = format!("kuco-export-{}.yaml", Local::now().format("%Y%m%d-%H%M%S"));
+        let title = format!("Exporting {} pods to {}", targets.len(), path);
+
+        tokio::spawn(async move {
+            let mut documents: Vec<String> = Vec::new();
+            for (namespace, pod) in targets {
+                let manifest = match get_pod_manifest(client.clone(), &namespace, &pod).await {
+                    Ok(manifest) => serde_yaml::to_string(&manifest).map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                let (status, failed) = match manifest {
+                    Ok(document) => {
+                        documents.push(document);
+                        ("exported".to_owned(), false)
+                    }
+                    Err(e) => (format!("failed: {}", e), true),
+                };
+                let item = ProgressItem {
+                    key: format!("{}/{}", namespace, pod),
+                    status,
+                    failed,
+                };
+                let _ = events.send(Event::App(AppEvent::OperationItem(item)));
+            }
+
+            let error = if documents.is_empty() {
+                Some("nothing was exported".to_owned())
+            } else {
+                std::fs::write(&path, documents.join("---\n"))
+                    .err()
+                    .map(|e| format!("failed to write {}: {}", path, e))
+            };
+            let _ = events.send(Event::App(AppEvent::OperationDone(error)));
+        });
+
+        self.popup = Some(Popup::Progress(OperationProgress::new(title)));
+    }
+
+    async fn apply_hpa_edit(&mut self) {
+        let Some(Popup::HpaEdit(edit)) = self.popup.take() else {
+            return;
+        };
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+        if !edit.is_changed() {
+            return;
+        }
+
+        let result = set_hpa_replicas(
+            client,
+            &edit.namespace,
+            &edit.hpa.name,
+            edit.min_replicas,
+            edit.max_replicas,
+        )
+        .await;
+        let parameters = json!({
+            "min_replicas": edit.min_replicas,
+            "max_replicas": edit.max_replicas,
+            "previous_min_replicas": edit.hpa.min_replicas,
+            "previous_max_replicas": edit.hpa.max_replicas,
+        });
+        self.audit(
+            &edit.namespace,
+            "HorizontalPodAutoscaler",
+            &edit.hpa.name,
+            "edit",
+            parameters,
+            &result,
+        )
+        .await;
+        self.status_message = Some(match result {
+            Ok(_) => format!(
+                "hpa '{}' now scales between {} and {} replicas",
+                edit.hpa.name, edit.min_replicas, edit.max_replicas
+            ),
+            Err(e) => {
+                tracing::error!("Failed to update hpa {}: {}", edit.hpa.name, e);
+                format!("failed to update hpa '{}': {}", edit.hpa.name, e)
+            }
+        });
+        self.view.update_widget_kube_data().await;
+    }
+
+    // Looks up the current replica count and any autoscaler targeting the workload up front, so
+    // the popup can warn before anything is changed.
+    async fn open_scale(&mut self) {
+        let (Some(client), Some(kind), Some(name)) = (
+            self.view.data.client(),
+            self.view.data.current_kind().cloned(),
+            self.view.data.current_resource_name.clone(),
+        ) else {
+            return;
+        };
+        if !SCALABLE_KINDS.contains(&kind.kind.as_str()) {
+            self.status_message = Some(format!("{} cannot be scaled", kind.kind));
+            return;
+        }
+        let namespace = self.current_namespace();
+
+        let current_replicas = match get_replicas(client.clone(), &kind, &namespace, &name).await {
+            Ok(replicas) => replicas,
+            Err(e) => {
+                self.status_message = Some(format!("failed to read scale of '{}': {}", name, e));
+                return;
+            }
+        };
+        let hpa = find_hpa_for(client, &namespace, &kind.kind, &name)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to look up hpa for {}: {}", name, e);
+                None
+            });
+
+        let confirm = TypedConfirm::required(self.protection().confirm_by_name, &namespace);
+        self.popup = Some(Popup::Scale(ScaleEdit {
+            kind,
+            namespace,
+            name,
+            current_replicas,
+            replicas: current_replicas,
+            hpa,
+            confirm,
+        }));
+    }
+
+    async fn apply_scale(&mut self) {
+        let Some(Popup::Scale(scale)) = self.popup.take() else {
+            return;
+        };
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+
+        let result = set_replicas(
+            client,
+            &scale.kind,
+            &scale.namespace,
+            &scale.name,
+            scale.replicas,
+        )
+        .await;
+        let parameters = json!({
+            "replicas": scale.replicas,
+            "previous_replicas": scale.current_replicas,
+        });
+        self.audit(
+            &scale.namespace,
+            &scale.kind.kind,
+            &scale.name,
+            "scale",
+            parameters,
+            &result,
+        )
+        .await;
+        self.status_message = Some(match result {
+            Ok(_) => format!(
+                "{} '{}' scaled to {}",
+                scale.kind.kind, scale.name, scale.replicas
+            ),
+            Err(e) => {
+                tracing::error!("Failed to scale {}: {}", scale.name, e);
+                format!("failed to scale '{}': {}", scale.name, e)
+            }
+        });
+        self.view.update_widget_kube_data().await;
+    }
+
+    fn current_table_columns(&self) -> Option<Vec<String>> {
+        let display = self.view.display.clone().unwrap_or_default();
+        table_for_view(&self.view.view_mode, &self.view.data, &display).map(|t| t.header)
+    }
+
+    // `o` steps through the visible columns, `O` flips the direction of the current sort.
+    fn cycle_sort(&mut self, flip_direction: bool) {
+        let Some(columns) = self.current_table_columns() else {
+            return;
+        };
+        let prefs = self
+            .view
+            .table_prefs
+            .entry(prefs_key(&self.view.view_mode))
+            .or_default();
+
+        if flip_direction {
+            prefs.descending = !prefs.descending;
+        } else {
+            let visible: Vec<String> = columns
+                .into_iter()
+                .filter(|c| !prefs.hidden.contains(c))
+                .collect();
+            prefs.cycle_sort(&visible);
+        }
+
+        self.status_message = Some(match &prefs.sort_by {
+            Some(column) => format!(
+                "sorted by {} ({})",
+                column,
+                if prefs.descending {
+                    "descending"
+                } else {
+                    "ascending"
+                }
+            ),
+            None => "unsorted".to_owned(),
+        });
+        self.view.sort_display();
+        self.events.send(AppEvent::SaveTablePrefs);
+    }
+
+    fn open_column_chooser(&mut self) {
+        let Some(columns) = self.current_table_columns() else {
+            return;
+        };
+
+        self.popup = Some(Popup::Columns(ColumnChooser {
+            columns,
+            hidden: self.view.current_table_prefs().hidden,
+            cursor: 0,
+        }));
+    }
+
+    async fn save_table_prefs(&mut self) {
+        if let Err(e) = self
+            .arc_ctx
+            .db
+            .set_json(
+                VIEW_PREFS_TABLE.to_owned(),
+                TABLE_PREFS_KEY.to_owned(),
+                &self.view.table_prefs,
+            )
+            .await
+        {
+            tracing::error!("Failed to save table preferences: {}", e);
+        }
+    }
+
+    async fn open_selector(&mut self) {
+        let history = self
+            .arc_ctx
+            .db
+            .get_history(SELECTOR_HISTORY)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load selector history: {}", e);
+                Vec::new()
+            });
+
+        self.selector_prompt =
+            SelectorPrompt::new(self.view.data.pod_selector.to_string(), history);
+        self.view.interact_mode = InteractionMode::SELECTOR;
+    }
+
+    // An empty prompt clears the selector; anything else is only remembered once the API server
+    // has accepted it.
+    async fn apply_selector(&mut self, component_state: &mut KubeComponentState) {
+        self.view.interact_mode = InteractionMode::NORMAL;
+        let input = self.selector_prompt.editor.text().trim().to_owned();
+        let selector = PodSelector::parse(&input);
+
+        if let Err(e) = self.view.data.apply_pod_selector(selector).await {
+            tracing::error!("Failed to apply pod selector '{}': {}", input, e);
+            self.status_message = Some(format!("invalid selector '{}': {}", input, e));
+            return;
+        }
+        self.view.update_widget_kube_data().await;
+        component_state.list_state.select(Some(0));
+
+        if input.is_empty() {
+            self.status_message = Some("selector cleared".to_owned());
+        } else {
+            if let Err(e) = self
+                .arc_ctx
+                .db
+                .push_history(SELECTOR_HISTORY, &input, HISTORY_LIMIT)
+                .await
+            {
+                tracing::error!("Failed to save selector history: {}", e);
+            }
+            self.status_message = Some(format!(
+                "{} pods match '{}'",
+                self.view
+                    .display
+                    .as_ref()
+                    .map(|d| d.len())
+                    .unwrap_or_default(),
+                input
+            ));
+        }
+    }
+
+    async fn open_finder(&mut self) {
+        let entries = match self.view.data.finder_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!("Failed to load finder entries: {}", e);
+                self.status_message = Some(format!("failed to load cache: {}", e));
+                return;
+            }
+        };
+        let usage = self
+            .arc_ctx
+            .db
+            .get_json(VIEW_PREFS_TABLE.to_owned(), FINDER_USAGE_KEY.to_owned())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load finder usage: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        self.popup = Some(Popup::Finder(Finder::new(entries, usage)));
+    }
+
+    // Lands on the picked entry in the view listing it: namespaces in NS, pods in PODS and
+    // containers in CONT.
+    async fn finder_jump(&mut self) {
+        let Some(Popup::Finder(mut finder)) = self.popup.take() else {
+            return;
+        };
+        let Some(entry) = finder.selected().cloned() else {
+            return;
+        };
+
+        *finder.usage.entry(entry.key()).or_default() += 1;
+        if let Err(e) = self
+            .arc_ctx
+            .db
+            .set_json(
+                VIEW_PREFS_TABLE.to_owned(),
+                FINDER_USAGE_KEY.to_owned(),
+                &finder.usage,
+            )
+            .await
+        {
+            tracing::error!("Failed to save finder usage: {}", e);
+        }
+
+        let data = &mut self.view.data;
+        let (view_mode, selection) = match entry {
+            FinderEntry::Namespace(namespace) => {
+                data.current_namespace_name = Some(namespace.clone());
+                (ViewMode::NS, namespace)
+            }
+            FinderEntry::Pod { namespace, pod } => {
+                data.current_namespace_name = Some(namespace);
+                data.current_pod_name = Some(pod.clone());
+                (ViewMode::PODS, pod)
+            }
+            FinderEntry::Container {
+                namespace,
+                pod,
+                container,
+            } => {
+                data.current_namespace_name = Some(namespace);
+                data.current_pod_name = Some(pod);
+                data.current_container_name = Some(container.clone());
+                (ViewMode::CONT, container)
+            }
+        };
+
+        self.jump_to(view_mode, Some(selection)).await;
+    }
+
+    /// Open the view given on the command line: the namespace's pods, or a pod's containers or
+    /// logs. The target has already been checked against the cluster.
+    pub async fn open_target(&mut self, target: LaunchTarget) {
+        let data = &mut self.view.data;
+        data.current_namespace_name = Some(target.namespace);
+        data.current_pod_name = target.pod.clone();
+        data.current_container_name = target.container.clone();
+
+        let view_mode = match (target.pod, target.container) {
+            (Some(_), Some(_)) => ViewMode::LOGS,
+            (Some(_), None) => ViewMode::CONT,
+            (None, _) => ViewMode::PODS,
+        };
+        self.jump_to(view_mode, None).await;
+    }
+
+    // Open `view_mode` as if navigated to from scratch, landing on `selection` if given.
+    async fn jump_to(&mut self, view_mode: ViewMode, selection: Option<String>) {
+        self.stop_event_watch();
+        self.view.return_view_mode = None;
+        self.view.data.merged_logs.clear();
+        self.view.pending_selection = selection;
+        self.view.view_mode = view_mode;
+        self.view.update_widget_kube_data().await;
+    }
+
+    async fn open_command(&mut self) {
+        let history = self
+            .arc_ctx
+            .db
+            .get_history(COMMAND_HISTORY)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to load command history: {}", e);
+                Vec::new()
+            });
+        let contexts = self.view.data.get_contexts().await.unwrap_or_else(|e| {
+            tracing::error!("Failed to load kubeconfig contexts: {}", e);
+            Vec::new()
+        });
+
+        self.command_prompt = CommandPrompt::new(history, contexts);
+        self.view.interact_mode = InteractionMode::COMMAND;
+    }
+
+    // A command that cannot be run keeps the prompt open with the reason next to the input.
+    async fn run_command(&mut self) {
+        let input = self.command_prompt.editor.text().trim().to_owned();
+        let result = match parse(&input, &self.config.aliases) {
+            Ok(command) => self.execute_command(command).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            self.command_prompt.error = Some(e);
+            return;
+        }
+
+        self.view.interact_mode = InteractionMode::NORMAL;
+        if let Err(e) = self
+            .arc_ctx
+            .db
+            .push_history(COMMAND_HISTORY, &input, HISTORY_LIMIT)
+            .await
+        {
+            tracing::error!("Failed to save command history: {}", e);
+        }
+    }
+
+    async fn execute_command(&mut self, command: Command) -> Result<(), String> {
+        match command {
+            Command::Quit => self.events.send(AppEvent::Quit),
+            Command::View(ViewMode::EVENTS) => {
+                let scope = EventScope {
+                    namespace: self.current_namespace(),
+                    object: None,
+                };
+                self.open_events(scope).await;
+            }
+            Command::View(view_mode) => self.jump_to(view_mode, None).await,
+            Command::Pods(namespace) => {
+                if let Some(namespace) = namespace {
+                    self.select_namespace(namespace)?;
+                }
+                self.jump_to(ViewMode::PODS, None).await;
+            }
+            Command::Namespace(None) => {
+                let selection = self.view.data.current_namespace_name.clone();
+                self.jump_to(ViewMode::NS, selection).await;
+            }
+            Command::Namespace(Some(namespace)) => {
+                self.select_namespace(namespace)?;
+                self.jump_to(ViewMode::PODS, None).await;
+            }
+            Command::Context(None) => {
+                self.status_message = Some(format!(
+                    "context: {}",
+                    self.view.data.context_name().unwrap_or("<default>")
+                ));
+            }
+            Command::Context(Some(name)) => {
+                if !self.command_prompt.contexts.contains(&name) {
+                    return Err(format!("no context '{}' in kubeconfig", name));
+                }
+                if let Err(e) = self.view.data.switch_context(&name).await {
+                    tracing::error!("Failed to switch to context '{}': {}", name, e);
+                    return Err(format!("failed to switch to '{}': {}", name, e));
+                }
+                self.jump_to(ViewMode::NS, None).await;
+                self.status_message = Some(format!("switched to context {}", name));
+            }
+            Command::Audit(filter) => {
+                let filter = parse_filter(filter.as_deref().unwrap_or_default())?;
+                self.open_audit(filter).await;
+            }
+            Command::Resource(name) => {
+                let data = &mut self.view.data;
+                data.update_api_kinds_list().await;
+                let Some(kind) = data
+                    .api_kinds
+                    .iter()
+                    .find(|k| k.plural == name || k.key() == name || k.kind.to_lowercase() == name)
+                else {
+                    return Err(CommandError::Unknown(name).to_string());
+                };
+
+                data.current_kind_name = Some(kind.key());
+                data.current_resource_name = None;
+                self.jump_to(ViewMode::RESOURCES, None).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn select_namespace(&mut self, namespace: String) -> Result<(), String> {
+        if !self.view.data.namespace_names_list.contains(&namespace) {
+            return Err(format!("no namespace '{}'", namespace));
+        }
+        self.view.data.current_namespace_name = Some(namespace);
+        self.view.data.current_pod_name = None;
+        Ok(())
+    }
+
+    /// Record a change made in the current context in the audit log.
+    async fn audit<T, E: Display>(
+        &self,
+        namespace: &str,
+        kind: &str,
+        name: &str,
+        action: &str,
+        parameters: Value,
+        result: &Result<T, E>,
+    ) {
+        let context = self.view.data.context_name();
+        let entry = audit_entry(context, namespace, kind, name, action, parameters);
+        audit::record(&self.arc_ctx.db, entry, result).await;
+    }
+
+    fn readonly_message(&self) -> String {
+        match self.view.data.context_name() {
+            Some(context) if !self.readonly => {
+                format!("context '{}' is read-only: changes are disabled", context)
+            }
+            _ => "read-only mode: changes are disabled".to_owned(),
+        }
+    }
+
+    /// What `--readonly` and the protection rules for the current context allow.
+    pub fn protection(&self) -> Protection {
+        let mut protection = self.config.protection(self.view.data.context_name());
+        protection.readonly |= self.readonly;
+        protection
+    }
+
+    fn current_namespace(&self) -> String {
+        self.view
+            .data
+            .current_namespace_name
+            .clone()
+            .unwrap_or("default".to_owned())
+    }
+
+    /// The `(kind, name)` of the object selected in the current view, if it can have Events.
+    fn current_event_object(&self) -> Option<(String, String)> {
+        let data = &self.view.data;
+        let (kind, name) = match self.view.view_mode {
+            ViewMode::PODS | ViewMode::NODEPODS | ViewMode::ALLPODS => {
+                ("Pod".to_owned(), data.current_pod_name.clone())
+            }
+            ViewMode::NODES => ("Node".to_owned(), data.current_node_name.clone()),
+            ViewMode::PVCS => (
+                "PersistentVolumeClaim".to_owned(),
+                data.current_claim_name.clone(),
+            ),
+            ViewMode::PVS => (
+                "PersistentVolume".to_owned(),
+                data.current_volume_name.clone(),
+            ),
+            ViewMode::HPAS => (
+                "HorizontalPodAutoscaler".to_owned(),
+                data.current_hpa_name.clone(),
+            ),
+            ViewMode::RESOURCES => (
+                data.current_kind()?.kind.clone(),
+                data.current_resource_name.clone(),
+            ),
+            _ => return None,
+        };
+
+        Some((kind, name?))
+    }
+
+    /// The `(kind, name)` of the object selected in the current view, if it is one. Containers
+    /// are not objects of their own, so their pod stands in for them.
+    fn current_object(&self) -> Option<(String, String)> {
+        let data = &self.view.data;
+        match self.view.view_mode {
+            ViewMode::NS => data
+                .current_namespace_name
+                .clone()
+                .filter(|namespace| namespace != ALL_NAMESPACES)
+                .map(|namespace| ("Namespace".to_owned(), namespace)),
+            ViewMode::CONT => Some(("Pod".to_owned(), data.current_pod_name.clone()?)),
+            _ => self.current_event_object(),
+        }
+    }
+
+    async fn open_yaml(&mut self) {
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+        let Some((kind, name)) = self.current_object() else {
+            self.status_message = Some("no object selected to show as YAML".to_owned());
+            return;
+        };
+
+        // The resource browser knows the exact kind; elsewhere it is looked up by name, like
+        // jumps to the resource browser are.
+        let api_kind = match self.view.view_mode {
+            ViewMode::RESOURCES => self.view.data.current_kind().cloned(),
+            _ => {
+                self.view.data.update_api_kinds_list().await;
+                self.view.data.api_kind_named(&kind).cloned()
+            }
+        };
+        let Some(api_kind) = api_kind else {
+            self.status_message = Some(format!("no browsable kind for '{}'", kind));
+            return;
+        };
+
+        let namespace = self.current_namespace();
+        match get_object(client, &api_kind, &namespace, &name).await {
+            Ok(object) => self.popup = Some(Popup::Yaml(YamlViewer::new(object))),
+            Err(e) => {
+                self.status_message = Some(format!("failed to fetch {} '{}': {}", kind, name, e))
+            }
+        }
+    }
+
+    async fn open_events(&mut self, scope: EventScope) {
+        self.stop_event_watch();
+        if self.view.view_mode != ViewMode::EVENTS {
+            self.view.return_view_mode = Some(self.view.view_mode.clone());
+        }
+
+        self.view.data.event_scope = scope;
+        self.view.data.event_type_filter = EventTypeFilter::default();
+        self.view.data.event_reason_filter = None;
+        self.view.view_mode = ViewMode::EVENTS;
+        self.view.update_widget_kube_data().await;
+    }
+
+    async fn open_audit(&mut self, filter: AuditFilter) {
+        self.stop_event_watch();
+        if self.view.view_mode != ViewMode::AUDIT {
+            self.view.return_view_mode = Some(self.view.view_mode.clone());
+        }
+
+        self.view.data.audit_filter = filter;
+        self.load_audit().await;
+        self.view.view_mode = ViewMode::AUDIT;
+        self.view.update_widget_kube_data().await;
+    }
+
+    async fn load_audit(&mut self) {
+        match self
+            .arc_ctx
+            .db
+            .get_audit(&self.view.data.audit_filter)
+            .await
+        {
+            Ok(entries) => self.view.data.audit = entries,
+            Err(e) => {
+                tracing::error!("Failed to load the audit log: {}", e);
+                self.status_message = Some(format!("failed to load the audit log: {}", e));
+            }
+        }
+    }
+
+    fn refresh_events_display(&mut self, component_state: &mut KubeComponentState) {
+        self.view.display = Some(self.view.data.get_events());
+        self.view.sort_display();
+        component_state.list_state.select(Some(0));
+    }
+
+    // The watch and the forwarder into the event loop share one task, so aborting it stops both.
+    fn toggle_event_watch(&mut self) {
+        if self.stop_event_watch() {
+            self.status_message = Some("live events off".to_owned());
+            return;
+        }
+        let Some(client) = self.view.data.client() else {
+            return;
+        };
+
+        let (watch_tx, mut watch_rx) = tokio::sync::mpsc::unbounded_channel();
+        let events = self.events.sender();
+        let scope = self.view.data.event_scope.clone();
+        self.event_watch = Some(tokio::spawn(async move {
+            let forward = async {
+                while let Some(event) = watch_rx.recv().await {
+                    if events
+                        .send(Event::App(AppEvent::EventWatch(event)))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            };
+            tokio::join!(watch_events(client, scope, watch_tx), forward);
+        }));
+        self.status_message = Some("live events on".to_owned());
+    }
+
+    fn stop_event_watch(&mut self) -> bool {
+        match self.event_watch.take() {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn handle_event_watch(
+        &mut self,
+        change: EventChange,
+        component_state: &mut KubeComponentState,
+    ) {
+        if self.view.view_mode != ViewMode::EVENTS {
+            self.stop_event_watch();
+            return;
+        }
+
+        self.view.data.events.merge(change);
+
+        // Leave an in-progress search alone; otherwise keep the selection on the same event as
+        // new ones arrive.
+        if self.view.interact_mode == InteractionMode::NORMAL {
+            self.view.display = Some(self.view.data.get_events());
+            self.view.sort_display();
+
+            if let Some(index) = self
+                .view
+                .data
+                .current_event_key
+                .as_ref()
+                .and_then(|key| self.view.display.as_ref()?.iter().position(|e| e == key))
+            {
+                component_state.list_state.select(Some(index));
+            }
+        }
+    }
+
+    // Every keystroke matches against the full list the search started from, so that deleting
+    // characters widens the results again.
+    fn search(&mut self, state: &mut KubeComponentState, current_list: Option<Vec<String>>) {
+        let Some(display) = current_list else {
+            return;
+        };
+        state.list_state.select(Some(0));
+
+        if state.search.editor.is_empty() {
+            self.view.search_matches.clear();
+            self.view.display = Some(display);
+            return;
+        }
+
+        match match_items(state.search.mode, state.search.editor.text(), &display) {
+            Ok(matches) => {
+                self.view.display = Some(matches.iter().map(|m| m.item.clone()).collect());
+                self.view.search_matches =
+                    matches.into_iter().map(|m| (m.item, m.indices)).collect();
+            }
+            // Leave the results alone while a regex is only half typed.
+            Err(e) => self.status_message = Some(format!("invalid regex: {}", e)),
+        }
+    }
+
+    /// Handles the tick event of the terminal.
+    ///
+    /// The tick event is where you can update the state of your application with any logic that
+    /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+        if self.ticks % CONFIG_CHECK_TICKS == 0 {
+            self.reload_config_if_changed();
+        }
+    }
+
+    /// Pick up edits to the config file. Only aliases, key bindings and the log tail take effect
+    /// right away; the database and sync settings are kept as they were at startup.
+    fn reload_config_if_changed(&mut self) {
+        let Some(path) = self.config.source.clone() else {
+            return;
+        };
+        let modified = modified_at(&path);
+        if modified == self.config_modified {
+            return;
+        }
+        self.config_modified = modified;
+
+        let reloaded = KucoConfig::load(Some(&path))
+            .and_then(|config| Keymap::new(&config.keys).map(|keymap| (config, keymap)));
+        let (config, keymap) = match reloaded {
+            Ok(reloaded) => reloaded,
+            Err(e) => {
+                tracing::error!("Failed to reload config: {}", e);
+                self.status_message = Some(format!(
+                    "config not reloaded: {}",
+                    e.to_string().replace('\n', "; ")
+                ));
+                return;
+            }
+        };
+
+        let needs_restart = self.config.needs_restart(&config);
+        self.keymap = keymap;
+        self.view.data.logs.tail_lines = config.logs.tail_lines;
+        self.config = KucoConfig {
+            db: self.config.db.clone(),
+            sync: self.config.sync.clone(),
+            daemon: self.config.daemon.clone(),
+            ..config
+        };
+
+        self.status_message = Some(if needs_restart {
+            "config reloaded; db, sync and daemon settings apply after restart".to_owned()
+        } else {
+            "config reloaded".to_owned()
+        });
+    }
+
+    /// Set running to false to quit the application.
+    pub fn quit(&mut self) {
+        self.running = false;
+    }
+
+    pub fn refresh_logs_selection(&mut self, component_state: &KubeComponentState) {
+        let lo_index = component_state.list_state.selected();
+        let lo_list = &self.view.display.as_ref().unwrap();
+
+        let mut lo: &String = &"none".to_string();
+        if !lo_list.is_empty() {
+            lo = &lo_list[lo_index.unwrap()];
+        }
+
+        // Select Log Line
+        self.view.data.current_log_line = Some(lo.clone());
+    }
+
+    pub fn refresh_containers_selection(&mut self, component_state: &KubeComponentState) {
+        let co_index = component_state.list_state.selected();
+        let co_list = &self.view.display.as_ref().unwrap();
+
+        let mut co: &String = &"none".to_string();
+        if !co_list.is_empty() {
+            co = &co_list[co_index.unwrap()];
+        }
+
+        self.view.data.current_container_name = Some(co.clone());
+    }
+
+    pub fn refresh_pods_selection(&mut self, component_state: &KubeComponentState) {
+        let po_index = component_state.list_state.selected();
+        let po_list = &self.view.display.as_ref().unwrap();
+
+        let mut po: &String = &"-".to_string();
+        if !po_list.is_empty() {
+            po = &po_list[po_index.unwrap()];
+        }
+
+        self.view.data.current_pod_name = Some(po.clone());
+    }
+
+    pub fn refresh_namespace_selection(&mut self, component_state: &KubeComponentState) {
+        let ns_index = component_state.list_state.selected();
+        let ns_list = &self.view.display.as_ref().unwrap();
+
+        let mut ns: &String = &"default".to_string();
+        if !ns_list.is_empty() {
+            ns = &ns_list[ns_index.unwrap()];
+        }
+
+        // The all namespaces pseudo-entry is not a namespace; keep the last real one.
+        if ns == ALL_NAMESPACES {
+            return;
         }
 
         // Select Namespace
         self.view.data.current_namespace_name = Some(ns.clone());
     }
 
+    pub fn refresh_nodes_selection(&mut self, component_state: &KubeComponentState) {
+        let no_index = component_state.list_state.selected().unwrap_or(0);
+        let no_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_node_name = no_list.get(no_index).cloned();
+    }
+
+    pub fn refresh_kinds_selection(&mut self, component_state: &KubeComponentState) {
+        let ki_index = component_state.list_state.selected().unwrap_or(0);
+        let ki_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_kind_name = ki_list.get(ki_index).cloned();
+    }
+
+    pub fn refresh_resources_selection(&mut self, component_state: &KubeComponentState) {
+        let re_index = component_state.list_state.selected().unwrap_or(0);
+        let re_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_resource_name = re_list.get(re_index).cloned();
+    }
+
+    pub fn refresh_claims_selection(&mut self, component_state: &KubeComponentState) {
+        let pvc_index = component_state.list_state.selected().unwrap_or(0);
+        let pvc_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_claim_name = pvc_list.get(pvc_index).cloned();
+    }
+
+    pub fn refresh_volumes_selection(&mut self, component_state: &KubeComponentState) {
+        let pv_index = component_state.list_state.selected().unwrap_or(0);
+        let pv_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_volume_name = pv_list.get(pv_index).cloned();
+    }
+
+    pub fn refresh_events_selection(&mut self, component_state: &KubeComponentState) {
+        let ev_index = component_state.list_state.selected().unwrap_or(0);
+        let ev_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_event_key = ev_list.get(ev_index).cloned();
+    }
+
+    pub fn refresh_hpas_selection(&mut self, component_state: &KubeComponentState) {
+        let hpa_index = component_state.list_state.selected().unwrap_or(0);
+        let hpa_list = self.view.display.as_ref().unwrap();
+
+        self.view.data.current_hpa_name = hpa_list.get(hpa_index).cloned();
+    }
+
+    // Rows in the node pods view are "namespace/pod" keys, so selecting one also moves the
+    // current namespace to wherever that pod lives.
+    pub fn refresh_node_pods_selection(&mut self, component_state: &KubeComponentState) {
+        let np_index = component_state.list_state.selected().unwrap_or(0);
+        let np_list = self.view.display.as_ref().unwrap();
+
+        if let Some((ns, po)) = np_list.get(np_index).and_then(|key| key.split_once('/')) {
+            self.view.data.current_namespace_name = Some(ns.to_owned());
+            self.view.data.current_pod_name = Some(po.to_owned());
+        }
+    }
+
+    // Same "namespace/pod" keys as the node pods view.
+    pub fn refresh_all_pods_selection(&mut self, component_state: &KubeComponentState) {
+        self.refresh_node_pods_selection(component_state);
+    }
+
     pub async fn transition_ns_to_pod_view(&mut self, component_state: &KubeComponentState) {
         tracing::debug!("VIEW: {:?}", self.view.display.clone());
         tracing::debug!("STATE: {:?}", component_state.list_state);
+        let selected = component_state
+            .list_state
+            .selected()
+            .and_then(|i| self.view.display.as_ref()?.get(i));
+        if selected.is_some_and(|ns| ns == ALL_NAMESPACES) {
+            self.view.view_mode = ViewMode::ALLPODS;
+            self.view.update_widget_kube_data().await; // Update View
+            return;
+        }
+
         self.refresh_namespace_selection(component_state); // Update Current Namespace
         self.view.update_widget_kube_data().await; // Update View
         self.view.view_mode = ViewMode::PODS;
@@ -394,8 +2354,122 @@ impl Kuco {
         self.view.update_widget_kube_data().await; // Update View
     }
 
+    pub async fn transition_node_to_node_pods_view(
+        &mut self,
+        component_state: &KubeComponentState,
+    ) {
+        self.refresh_nodes_selection(component_state); // Update Current Node Name
+        self.view.view_mode = ViewMode::NODEPODS;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    pub async fn transition_node_pods_to_cont_view(
+        &mut self,
+        component_state: &KubeComponentState,
+    ) {
+        self.refresh_node_pods_selection(component_state); // Update Current Namespace & Pod
+        self.view.view_mode = ViewMode::CONT;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    pub async fn transition_all_pods_to_cont_view(&mut self, component_state: &KubeComponentState) {
+        self.refresh_all_pods_selection(component_state); // Update Current Namespace & Pod
+        self.view.view_mode = ViewMode::CONT;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    pub async fn transition_kinds_to_resources_view(
+        &mut self,
+        component_state: &KubeComponentState,
+    ) {
+        self.refresh_kinds_selection(component_state); // Update Current Kind
+        self.view.view_mode = ViewMode::RESOURCES;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    // Jump to wherever the selected event's involved object is listed. Kinds without a view of
+    // their own are opened in the resource browser.
+    pub async fn transition_event_to_object_view(&mut self, component_state: &KubeComponentState) {
+        self.refresh_events_selection(component_state); // Update Current Event
+        let Some(event) = self.view.data.current_event().cloned() else {
+            return;
+        };
+
+        if !event.object_namespace.is_empty() {
+            self.view.data.current_namespace_name = Some(event.object_namespace.clone());
+        }
+
+        let data = &mut self.view.data;
+        let view_mode = match event.object_kind.as_str() {
+            "Pod" => {
+                data.current_pod_name = Some(event.object_name.clone());
+                ViewMode::PODS
+            }
+            "Node" => {
+                data.current_node_name = Some(event.object_name.clone());
+                ViewMode::NODES
+            }
+            "PersistentVolumeClaim" => {
+                data.current_claim_name = Some(event.object_name.clone());
+                ViewMode::PVCS
+            }
+            "PersistentVolume" => {
+                data.current_volume_name = Some(event.object_name.clone());
+                ViewMode::PVS
+            }
+            "Namespace" => {
+                data.current_namespace_name = Some(event.object_name.clone());
+                ViewMode::NS
+            }
+            kind => {
+                if !self.select_resource(kind, &event.object_name).await {
+                    return;
+                }
+                ViewMode::RESOURCES
+            }
+        };
+
+        self.stop_event_watch();
+        self.view.return_view_mode = None;
+        self.view.pending_selection = Some(event.object_name);
+        self.view.view_mode = view_mode;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    pub async fn transition_hpa_to_target_view(&mut self, component_state: &KubeComponentState) {
+        self.refresh_hpas_selection(component_state); // Update Current HPA
+        let Some(hpa) = self.view.data.current_hpa().cloned() else {
+            return;
+        };
+        if !self
+            .select_resource(&hpa.target_kind, &hpa.target_name)
+            .await
+        {
+            return;
+        }
+
+        self.view.pending_selection = Some(hpa.target_name);
+        self.view.view_mode = ViewMode::RESOURCES;
+        self.view.update_widget_kube_data().await; // Update View
+    }
+
+    // Point the resource browser at `name` under the discovered kind called `kind`.
+    async fn select_resource(&mut self, kind: &str, name: &str) -> bool {
+        let data = &mut self.view.data;
+        data.update_api_kinds_list().await;
+        let Some(api_kind) = data.api_kind_named(kind) else {
+            self.status_message = Some(format!("no browsable kind for '{}'", kind));
+            return false;
+        };
+
+        data.current_kind_name = Some(api_kind.key());
+        data.current_resource_name = Some(name.to_owned());
+        true
+    }
+
     pub async fn transition_cont_to_log_view(&mut self, component_state: &KubeComponentState) {
         self.refresh_containers_selection(component_state); // Update Current Container Name
+        self.view.data.merged_logs.clear();
         self.view.view_mode = ViewMode::LOGS;
         self.view.update_widget_kube_data().await; // Update View
     }
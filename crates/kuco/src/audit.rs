@@ -0,0 +1,84 @@
+/*
+ * Recording the changes made from KuCo in the audit log of the persistent DB, and the filter
+ * syntax shared by the `:audit` view and `kuco audit`.
+ */
+
+use std::fmt::Display;
+
+use chrono::Utc;
+use kuco_k8s_backend::age::parse_duration_secs;
+use kuco_sqlite_backend::{AuditEntry, AuditFilter, SqliteDb, audit::AUDIT_OK};
+use serde_json::Value;
+
+use crate::constants::DEFAULT_CONTEXT_NAME;
+
+/// An entry for `action` on an object, stamped with the current time. Its outcome is filled in
+/// by [`record`] once the change was made.
+pub fn audit_entry(
+    context: Option<&str>,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+    action: &str,
+    parameters: Value,
+) -> AuditEntry {
+    AuditEntry {
+        id: 0,
+        at: Utc::now().timestamp(),
+        context: context.unwrap_or(DEFAULT_CONTEXT_NAME).to_owned(),
+        namespace: namespace.to_owned(),
+        kind: kind.to_owned(),
+        name: name.to_owned(),
+        action: action.to_owned(),
+        parameters: parameters.to_string(),
+        outcome: String::new(),
+    }
+}
+
+/// Append `entry` with the outcome of `result`. The change has been made either way, so a log
+/// that cannot be written is reported in the tracing output rather than to the caller.
+pub async fn record<T, E: Display>(db: &SqliteDb, mut entry: AuditEntry, result: &Result<T, E>) {
+    entry.outcome = match result {
+        Ok(_) => AUDIT_OK.to_owned(),
+        Err(e) => e.to_string(),
+    };
+
+    if let Err(e) = db.record_audit(&entry).await {
+        tracing::error!("{}: {:?}", e, entry);
+    }
+}
+
+/// Parse comma separated `field=value` pairs, e.g. `ns=default,action=scale,since=2h`. Fields
+/// are `ctx`, `ns`, `kind`, `name`, `action`, `outcome` (`ok` or `failed`) and `since`.
+pub fn parse_filter(input: &str) -> Result<AuditFilter, String> {
+    let mut filter = AuditFilter::default();
+
+    for pair in input.split(',').filter(|pair| !pair.trim().is_empty()) {
+        let Some((field, value)) = pair.split_once('=') else {
+            return Err(format!("expected field=value, got '{}'", pair));
+        };
+        let value = value.trim().to_owned();
+        match field.trim() {
+            "ctx" | "context" => filter.context = Some(value),
+            "ns" | "namespace" => filter.namespace = Some(value),
+            "kind" => filter.kind = Some(value),
+            "name" => filter.name = Some(value),
+            "action" => filter.action = Some(value),
+            "outcome" => {
+                filter.succeeded = Some(match value.as_str() {
+                    "ok" => true,
+                    "failed" => false,
+                    _ => return Err(format!("outcome is 'ok' or 'failed', not '{}'", value)),
+                })
+            }
+            "since" => {
+                let secs = parse_duration_secs(&value)
+                    .ok_or_else(|| format!("invalid duration '{}', e.g. 30m or 2d", value))?;
+                filter.since = Some(Utc::now().timestamp() - secs);
+            }
+            other => return Err(format!("unknown audit field '{}'", other)),
+        }
+    }
+
+    Ok(filter)
+}
@@ -0,0 +1,260 @@
+/*
+ * Command-line options. Anything naming an object in the cluster is looked up before the TUI
+ * starts, so that a typo fails with a message on the terminal instead of opening an empty view.
+ * With a subcommand KuCo runs headless instead, see [`crate::headless`] and [`crate::daemon`].
+ */
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use color_eyre::eyre::{Result, eyre};
+use k8s_openapi::api::core::v1::{Namespace, Pod};
+use kube::{Api, Client};
+use kuco_k8s_backend::context::KubeContext;
+
+use crate::config::KucoConfig;
+
+#[derive(Debug, Parser)]
+#[command(name = "kuco", version, about = "kuco - kubernetes console tui")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<HeadlessCommand>,
+
+    /// Config file to read instead of the one in the config dir.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Kubeconfig file to read contexts from.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub kubeconfig: Option<PathBuf>,
+
+    /// Kubeconfig context to start in, instead of its current context.
+    #[arg(long, global = true)]
+    pub context: Option<String>,
+
+    /// Start in the pod list of this namespace.
+    #[arg(short, long)]
+    pub namespace: Option<String>,
+
+    /// Start in the logs of this pod; needs --namespace.
+    #[arg(short, long, requires = "namespace")]
+    pub pod: Option<String>,
+
+    /// Container of --pod to show the logs of; may be left out for single container pods.
+    #[arg(short, long, requires = "pod")]
+    pub container: Option<String>,
+
+    /// Disable every action that changes the cluster.
+    #[arg(long)]
+    pub readonly: bool,
+
+    /// Persistent database to use, overriding the config.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub db_path: Option<PathBuf>,
+
+    /// File to write tracing output to.
+    #[arg(
+        long,
+        value_name = "PATH",
+        default_value = "logs/tracing.log",
+        global = true
+    )]
+    pub log_file: PathBuf,
+
+    /// Fill the cache once at startup instead of syncing it in the background.
+    #[arg(long)]
+    pub no_sync: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HeadlessCommand {
+    /// Print namespaces, pods or containers, from the persisted cache while it is fresh.
+    #[command(subcommand)]
+    Ls(ListCommand),
+    /// Print the logs of a container, always fetched from the API server.
+    Logs {
+        #[arg(short, long)]
+        namespace: String,
+        #[arg(short, long)]
+        pod: String,
+        /// May be left out for single container pods.
+        #[arg(short, long)]
+        container: Option<String>,
+        /// Lines from the end of the log, instead of `logs.tail_lines` from the config.
+        #[arg(long, value_name = "LINES")]
+        tail: Option<i64>,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Print the audit log of changes made from KuCo, e.g. to export it.
+    Audit {
+        /// Comma separated filters as in `:audit`, e.g. `ctx=prod,action=scale,since=7d`.
+        filter: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Sync every kubeconfig context in the background for all KuCo instances to share.
+    Daemon {
+        /// Socket to listen on, instead of `daemon.socket` from the config.
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+#[command(
+    after_help = "Exits with status 3 when stale cached data was printed, because the API \
+server could not be reached or --cache-only was given."
+)]
+pub enum ListCommand {
+    Namespaces {
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+    Pods {
+        /// Only the pods of this namespace, instead of every namespace.
+        #[arg(short, long)]
+        namespace: Option<String>,
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+    Containers {
+        #[arg(short, long)]
+        namespace: String,
+        #[arg(short, long)]
+        pod: String,
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CacheArgs {
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Cached data older than this many seconds is fetched from the API server again.
+    #[arg(long, value_name = "SECS", default_value_t = 60)]
+    pub max_age: i64,
+
+    /// Never contact the API server; stale cached data is printed as is.
+    #[arg(long)]
+    pub cache_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Table,
+}
+
+/// Where the TUI opens, from --namespace, --pod and --container once checked against the cluster.
+#[derive(Debug, Clone)]
+pub struct LaunchTarget {
+    pub namespace: String,
+    pub pod: Option<String>,
+    pub container: Option<String>,
+}
+
+impl Cli {
+    /// The config from --config or the usual places, with --db-path applied over it.
+    pub fn load_config(&self) -> Result<KucoConfig> {
+        let config_path = KucoConfig::locate(self.config.clone());
+        if let Some(path) = &config_path {
+            tracing::info!("Config file will be: {}", path.display());
+        }
+        let mut config = KucoConfig::load(config_path.as_deref())?;
+        if let Some(db_path) = &self.db_path {
+            config.db.path = Some(db_path.clone());
+        }
+
+        Ok(config)
+    }
+
+    /// A client for --context from --kubeconfig, each defaulting to what kubectl would use.
+    pub async fn kube_context(&self) -> Result<KubeContext> {
+        let mut kube_context = KubeContext::default();
+        let init_result = if self.kubeconfig.is_some() || self.context.is_some() {
+            kube_context
+                .init_context_from(self.kubeconfig.clone(), self.context.clone())
+                .await
+        } else {
+            kube_context.init_context().await
+        };
+        init_result.map_err(|e| {
+            tracing::error!("Failed to initialize Kubernetes context: {}", e);
+            eyre!("K8s context init failed: {}", e)
+        })?;
+
+        Ok(kube_context)
+    }
+
+    /// Look up the namespace, pod and container given on the command line. A pod without
+    /// --container opens its logs when it has a single container, and its container list if not.
+    pub async fn resolve_target(&self, client: Client) -> Result<Option<LaunchTarget>> {
+        let Some(namespace) = self.namespace.clone() else {
+            return Ok(None);
+        };
+
+        let namespaces: Api<Namespace> = Api::all(client.clone());
+        let found = namespaces
+            .get_opt(&namespace)
+            .await
+            .map_err(|e| eyre!("Failed to look up namespace '{}': {}", namespace, e))?;
+        if found.is_none() {
+            return Err(eyre!("Namespace '{}' does not exist", namespace));
+        }
+
+        let Some(pod_name) = self.pod.clone() else {
+            return Ok(Some(LaunchTarget {
+                namespace,
+                pod: None,
+                container: None,
+            }));
+        };
+
+        let pods: Api<Pod> = Api::namespaced(client, &namespace);
+        let pod = pods
+            .get_opt(&pod_name)
+            .await
+            .map_err(|e| eyre!("Failed to look up pod '{}/{}': {}", namespace, pod_name, e))?
+            .ok_or_else(|| {
+                eyre!(
+                    "Pod '{}' does not exist in namespace '{}'",
+                    pod_name,
+                    namespace
+                )
+            })?;
+        let spec = pod.spec.unwrap_or_default();
+        let regular: Vec<String> = spec.containers.into_iter().map(|c| c.name).collect();
+        let containers: Vec<String> = spec
+            .init_containers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| c.name)
+            .chain(regular.iter().cloned())
+            .collect();
+
+        let container = match &self.container {
+            Some(name) if containers.contains(name) => Some(name.clone()),
+            Some(name) => {
+                return Err(eyre!(
+                    "Pod '{}/{}' has no container '{}' (containers: {})",
+                    namespace,
+                    pod_name,
+                    name,
+                    containers.join(", ")
+                ));
+            }
+            None if regular.len() == 1 => regular.first().cloned(),
+            None => None,
+        };
+
+        Ok(Some(LaunchTarget {
+            namespace,
+            pod: Some(pod_name),
+            container,
+        }))
+    }
+}
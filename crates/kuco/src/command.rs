@@ -0,0 +1,220 @@
+/*
+ * ':' commands, k9s style: the registry of known commands, parsing what was typed (after
+ * expanding user aliases), and the candidates used to complete names and arguments.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::app::ViewMode;
+
+/// What a command's single argument refers to, and so what it is completed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgKind {
+    None,
+    Namespace,
+    Context,
+    /// Free text, like the field filters of `:audit`.
+    Filter,
+}
+
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub arg: ArgKind,
+}
+
+pub const COMMANDS: [CommandSpec; 12] = [
+    CommandSpec {
+        name: "pods",
+        aliases: &["po", "pod"],
+        arg: ArgKind::Namespace,
+    },
+    CommandSpec {
+        name: "ns",
+        aliases: &["namespace", "namespaces"],
+        arg: ArgKind::Namespace,
+    },
+    CommandSpec {
+        name: "nodes",
+        aliases: &["no", "node"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "kinds",
+        aliases: &["api-resources"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "pvc",
+        aliases: &["pvcs", "persistentvolumeclaims"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "pv",
+        aliases: &["pvs", "persistentvolumes"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "events",
+        aliases: &["ev", "event"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "hpa",
+        aliases: &["hpas", "horizontalpodautoscalers"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "policy",
+        aliases: &["quota", "limits", "pdb"],
+        arg: ArgKind::None,
+    },
+    CommandSpec {
+        name: "audit",
+        aliases: &["audit-log"],
+        arg: ArgKind::Filter,
+    },
+    CommandSpec {
+        name: "ctx",
+        aliases: &["context", "contexts"],
+        arg: ArgKind::Context,
+    },
+    CommandSpec {
+        name: "quit",
+        aliases: &["q", "q!"],
+        arg: ArgKind::None,
+    },
+];
+
+/// kubectl short names of kinds without a view of their own, opened in the resource browser.
+const KIND_SHORT_NAMES: [(&str, &str); 13] = [
+    ("deploy", "deployments"),
+    ("sts", "statefulsets"),
+    ("ds", "daemonsets"),
+    ("rs", "replicasets"),
+    ("svc", "services"),
+    ("cm", "configmaps"),
+    ("ing", "ingresses"),
+    ("cj", "cronjobs"),
+    ("sa", "serviceaccounts"),
+    ("ep", "endpoints"),
+    ("netpol", "networkpolicies"),
+    ("sc", "storageclasses"),
+    ("crd", "customresourcedefinitions"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    View(ViewMode),
+    /// Pods of the given namespace, or of the current one.
+    Pods(Option<String>),
+    /// Switch to a namespace and list its pods, or open the namespace list.
+    Namespace(Option<String>),
+    /// Switch to a kubeconfig context, or report the current one.
+    Context(Option<String>),
+    /// The audit log, narrowed by a filter like `ns=default,action=scale`.
+    Audit(Option<String>),
+    /// Anything else is taken for a kind name and looked up among the discovered kinds.
+    Resource(String),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    Empty,
+    Unknown(String),
+    UnexpectedArgument(String),
+    TooManyArguments(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Empty => write!(f, "no command given"),
+            CommandError::Unknown(name) => write!(f, "unknown command '{}'", name),
+            CommandError::UnexpectedArgument(name) => {
+                write!(f, "'{}' does not take an argument", name)
+            }
+            CommandError::TooManyArguments(name) => {
+                write!(f, "'{}' takes a single argument", name)
+            }
+        }
+    }
+}
+
+pub fn find_spec(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS
+        .iter()
+        .find(|spec| spec.name == name || spec.aliases.contains(&name))
+}
+
+/// Replace a leading user alias with what it stands for. Aliases are not expanded recursively,
+/// so one can never loop into another.
+pub fn expand_alias(input: &str, aliases: &HashMap<String, String>) -> String {
+    let input = input.trim().trim_start_matches(':');
+    let (head, rest) = input.split_once(' ').unwrap_or((input, ""));
+
+    match aliases.get(head) {
+        Some(expansion) => format!("{} {}", expansion, rest).trim().to_owned(),
+        None => input.to_owned(),
+    }
+}
+
+pub fn parse(input: &str, aliases: &HashMap<String, String>) -> Result<Command, CommandError> {
+    let expanded = expand_alias(input, aliases);
+    let mut words = expanded.split_whitespace();
+    let Some(head) = words.next() else {
+        return Err(CommandError::Empty);
+    };
+    let arg = words.next().map(|a| a.to_owned());
+    if words.next().is_some() {
+        return Err(CommandError::TooManyArguments(head.to_owned()));
+    }
+
+    let Some(spec) = find_spec(head) else {
+        if arg.is_some() {
+            return Err(CommandError::UnexpectedArgument(head.to_owned()));
+        }
+        let kind = KIND_SHORT_NAMES
+            .iter()
+            .find(|(short, _)| *short == head)
+            .map(|(_, plural)| *plural)
+            .unwrap_or(head);
+        return Ok(Command::Resource(kind.to_owned()));
+    };
+    if spec.arg == ArgKind::None && arg.is_some() {
+        return Err(CommandError::UnexpectedArgument(head.to_owned()));
+    }
+
+    Ok(match spec.name {
+        "pods" => Command::Pods(arg),
+        "ns" => Command::Namespace(arg),
+        "ctx" => Command::Context(arg),
+        "audit" => Command::Audit(arg),
+        "nodes" => Command::View(ViewMode::NODES),
+        "kinds" => Command::View(ViewMode::KINDS),
+        "pvc" => Command::View(ViewMode::PVCS),
+        "pv" => Command::View(ViewMode::PVS),
+        "events" => Command::View(ViewMode::EVENTS),
+        "hpa" => Command::View(ViewMode::HPAS),
+        "policy" => Command::View(ViewMode::POLICY),
+        _ => Command::Quit,
+    })
+}
+
+/// Every name a command can be typed as: commands and their aliases, kind short names, the
+/// user's aliases and the discovered kinds.
+pub fn command_names(aliases: &HashMap<String, String>, kinds: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = COMMANDS
+        .iter()
+        .flat_map(|spec| std::iter::once(spec.name).chain(spec.aliases.iter().copied()))
+        .chain(KIND_SHORT_NAMES.iter().map(|(short, _)| *short))
+        .map(|name| name.to_owned())
+        .chain(aliases.keys().cloned())
+        .chain(kinds.iter().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
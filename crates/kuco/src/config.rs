@@ -0,0 +1,348 @@
+/*
+ * User configuration, read from `config.toml` in the XDG config dir (`~/.config/kuco` on
+ * Linux), with `KUCO_*` environment variables overriding individual settings.
+ *
+ * Every setting is optional. Aliases, key bindings, protection rules and the log tail are
+ * reloaded while KuCo runs; the database, sync and daemon settings are only read at startup.
+ */
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use directories::ProjectDirs;
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, de::Error};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Points at a config file to use instead of the one in the config dir.
+const CONFIG_PATH_ENV: &str = "KUCO_CONFIG";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KucoConfig {
+    pub db: DbConfig,
+    pub sync: SyncConfig,
+    pub logs: LogsConfig,
+    pub daemon: DaemonConfig,
+    /// `:` command aliases, e.g. `sys = "ns kube-system"`.
+    pub aliases: HashMap<String, String>,
+    /// Key binding overrides per scope, e.g. `[keys.normal]` with `"ctrl-d" = "quit"`. See
+    /// [`crate::keymap`] for the scopes and key syntax.
+    pub keys: HashMap<String, HashMap<String, String>>,
+    /// Safety rules for some contexts, as `[[protect]]` tables. See [`ProtectRule`].
+    pub protect: Vec<ProtectRule>,
+
+    /// The file this was loaded from, if any.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DbConfig {
+    /// The persistent database. Defaults to `~/.kuco/user_kube_data.db`.
+    pub path: Option<PathBuf>,
+    #[serde(deserialize_with = "timeout_secs")]
+    pub timeout_secs: f64,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            timeout_secs: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SyncConfig {
+    /// Between syncs of namespaces, nodes and pods.
+    #[serde(deserialize_with = "positive_u64")]
+    pub namespaces_interval_secs: u64,
+    /// Between syncs of the containers of one namespace.
+    #[serde(deserialize_with = "positive_u64")]
+    pub containers_interval_secs: u64,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            namespaces_interval_secs: 5,
+            containers_interval_secs: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    /// Lines fetched from the end of a container's log.
+    #[serde(deserialize_with = "positive_i64")]
+    pub tail_lines: i64,
+}
+
+impl Default for LogsConfig {
+    fn default() -> Self {
+        Self { tail_lines: 200 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// Socket `kuco daemon` listens on. Defaults to `kuco/daemon.sock` in `$XDG_RUNTIME_DIR`,
+    /// or `~/.kuco/daemon.sock` without one.
+    pub socket: Option<PathBuf>,
+}
+
+/// Safety settings for the contexts matching `context`, e.g. `context = "prod-*"` with
+/// `readonly = true`. Every matching rule applies.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtectRule {
+    /// Context name, where `*` matches any run of characters.
+    pub context: String,
+    /// Refuse every action that changes the cluster.
+    #[serde(default)]
+    pub readonly: bool,
+    /// Destructive actions are only confirmed once the namespace is typed out, or the node
+    /// name for node actions.
+    #[serde(default)]
+    pub confirm_by_name: bool,
+    /// Header banner text. Defaults to the context name.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Banner colour, a name like `red` or a `#rrggbb` value.
+    #[serde(default = "default_banner_color", deserialize_with = "color")]
+    pub color: Color,
+}
+
+impl ProtectRule {
+    pub fn matches(&self, context: &str) -> bool {
+        glob_match(&self.context, context)
+    }
+}
+
+fn default_banner_color() -> Color {
+    Color::Red
+}
+
+/// What the protection rules say about one context.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Protection {
+    pub readonly: bool,
+    pub confirm_by_name: bool,
+    /// Text and colour of the header banner, for contexts matched by a rule.
+    pub banner: Option<(String, Color)>,
+}
+
+// Validation happens while deserializing, so that errors point at the offending value.
+fn positive<T: PartialOrd + Default + Copy>(value: T) -> Result<T, &'static str> {
+    if value > T::default() {
+        Ok(value)
+    } else {
+        Err("must be greater than 0")
+    }
+}
+
+/// Longest timeout accepted, well short of what a `Duration` can hold.
+const MAX_TIMEOUT_SECS: f64 = 3600.0;
+
+fn timeout(value: f64) -> Result<f64, &'static str> {
+    if !value.is_finite() || value > MAX_TIMEOUT_SECS {
+        return Err("must be at most 3600 seconds");
+    }
+    positive(value)
+}
+
+fn timeout_secs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    timeout(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn positive_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    positive(u64::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn positive_i64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    positive(i64::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Color::from_str(&name).map_err(|_| D::Error::custom(format!("unknown colour '{}'", name)))
+}
+
+// `*` matches any run of characters, everything else itself.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut text) = text.strip_prefix(prefix) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let suffix = parts.pop().unwrap_or_default();
+    for part in parts {
+        match text.find(part) {
+            Some(i) => text = &text[i + part.len()..],
+            None => return false,
+        }
+    }
+    text.len() >= suffix.len() && text.ends_with(suffix)
+}
+
+impl KucoConfig {
+    /// The config file to read: `flag` (`--config`), then `$KUCO_CONFIG`, then `config.toml` in
+    /// the config dir, then the one in `~/.kuco` older versions read. `None` when there is none.
+    pub fn locate(flag: Option<PathBuf>) -> Option<PathBuf> {
+        if let Some(path) = flag.or_else(|| env::var_os(CONFIG_PATH_ENV).map(PathBuf::from)) {
+            return Some(path);
+        }
+
+        let xdg =
+            ProjectDirs::from("", "", "kuco").map(|dirs| dirs.config_dir().join(CONFIG_FILE_NAME));
+        let legacy = dirs_next::home_dir().map(|home| home.join(".kuco").join(CONFIG_FILE_NAME));
+        [xdg, legacy]
+            .into_iter()
+            .flatten()
+            .find(|path| path.exists())
+    }
+
+    /// Load the config at `path` (defaults without one) and apply the environment overrides.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = match path {
+            Some(path) => Self::load_file(path)?,
+            None => Self::default(),
+        };
+        config.apply_env()?;
+
+        Ok(config)
+    }
+
+    fn load_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read config file {}", path.display()))?;
+
+        let mut config: Self = toml::from_str(&contents).map_err(|e| {
+            let location = match e.span() {
+                Some(span) => {
+                    let before = &contents[..span.start];
+                    let line = before.matches('\n').count() + 1;
+                    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                    let column = before[line_start..].chars().count() + 1;
+                    format!("{}:{}:{}", path.display(), line, column)
+                }
+                None => path.display().to_string(),
+            };
+            eyre!("{}: {}", location, e.message())
+        })?;
+        config.source = Some(path.to_path_buf());
+
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) -> Result<()> {
+        if let Some(path) = env::var_os("KUCO_DB_PATH") {
+            self.db.path = Some(PathBuf::from(path));
+        }
+        override_from_env("KUCO_DB_TIMEOUT_SECS", &mut self.db.timeout_secs, timeout)?;
+        override_from_env(
+            "KUCO_SYNC_NAMESPACES_INTERVAL_SECS",
+            &mut self.sync.namespaces_interval_secs,
+            positive,
+        )?;
+        override_from_env(
+            "KUCO_SYNC_CONTAINERS_INTERVAL_SECS",
+            &mut self.sync.containers_interval_secs,
+            positive,
+        )?;
+        override_from_env("KUCO_LOGS_TAIL_LINES", &mut self.logs.tail_lines, positive)?;
+        if let Some(path) = env::var_os("KUCO_DAEMON_SOCKET") {
+            self.daemon.socket = Some(PathBuf::from(path));
+        }
+
+        Ok(())
+    }
+
+    /// The persistent database: `db.path`, or `~/.kuco/user_kube_data.db`.
+    pub fn db_path(&self) -> Result<PathBuf> {
+        match (&self.db.path, dirs_next::home_dir()) {
+            (Some(path), _) => Ok(path.clone()),
+            (None, Some(home)) => Ok(home.join(".kuco").join("user_kube_data.db")),
+            (None, None) => {
+                let error_message = "Critical Error: Could not determine the home directory. Application cannot continue.";
+                tracing::error!("{}", error_message);
+
+                Err(eyre!(error_message))
+            }
+        }
+    }
+
+    /// The socket of the sync daemon, whether or not one is listening.
+    pub fn daemon_socket(&self) -> Result<PathBuf> {
+        if let Some(path) = &self.daemon.socket {
+            return Ok(path.clone());
+        }
+        let runtime_dir = ProjectDirs::from("", "", "kuco")
+            .and_then(|dirs| dirs.runtime_dir().map(Path::to_path_buf));
+        match (runtime_dir, dirs_next::home_dir()) {
+            (Some(dir), _) => Ok(dir.join("daemon.sock")),
+            (None, Some(home)) => Ok(home.join(".kuco").join("daemon.sock")),
+            (None, None) => Err(eyre!("Could not determine where the daemon socket lives")),
+        }
+    }
+
+    /// The protection rules matching `context` merged together. The banner comes from the
+    /// first matching rule.
+    pub fn protection(&self, context: Option<&str>) -> Protection {
+        let Some(context) = context else {
+            return Protection::default();
+        };
+
+        let mut protection = Protection::default();
+        for rule in self.protect.iter().filter(|rule| rule.matches(context)) {
+            protection.readonly |= rule.readonly;
+            protection.confirm_by_name |= rule.confirm_by_name;
+            protection.banner.get_or_insert_with(|| {
+                let label = rule.label.clone().unwrap_or_else(|| context.to_owned());
+                (label, rule.color)
+            });
+        }
+
+        protection
+    }
+
+    /// Whether `other` differs in settings only read at startup.
+    pub fn needs_restart(&self, other: &Self) -> bool {
+        self.db != other.db || self.sync != other.sync || self.daemon != other.daemon
+    }
+}
+
+fn override_from_env<T>(
+    name: &str,
+    setting: &mut T,
+    validate: fn(T) -> Result<T, &'static str>,
+) -> Result<()>
+where
+    T: FromStr,
+{
+    let Ok(value) = env::var(name) else {
+        return Ok(());
+    };
+    let parsed = value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| eyre!("{}: invalid value '{}'", name, value))?;
+    *setting = validate(parsed).map_err(|e| eyre!("{}: {}", name, e))?;
+
+    Ok(())
+}
@@ -1,6 +1,30 @@
 pub const KUCO_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const KUCO_CACHE_TABLE: &str = "kv_cache";
+pub const VIEW_PREFS_TABLE: &str = "view_prefs";
 
 pub const NS_NAMES_CACHE_KEY: &str = "all_namespaces";
+pub const NODES_CACHE_KEY: &str = "all_nodes";
 pub const POD_NAMES_CACHE_KEY: &str = "pods_";
+pub const POD_ROWS_CACHE_KEY: &str = "pod_rows_";
+pub const ALL_POD_ROWS_CACHE_KEY: &str = "all_pod_rows";
 pub const CONT_NAMES_CACHE_KEY: &str = "cont_";
+pub const POD_SELECTOR_CACHE_KEY: &str = "pod_selector";
+pub const CONTEXTS_CACHE_KEY: &str = "kube_contexts";
+pub const CURRENT_CONTEXT_CACHE_KEY: &str = "current_context";
+
+// Synced data kept in the persistent DB for headless commands, under `<context>/<key>`.
+pub const PERSISTED_NS_KEY: &str = "namespaces";
+pub const PERSISTED_PODS_KEY: &str = "pods";
+pub const PERSISTED_CONT_KEY: &str = "containers/";
+/// Stands in for the context name when the client was not built from a named context.
+pub const DEFAULT_CONTEXT_NAME: &str = "default";
+
+/// Pseudo-entry at the top of the Namespaces column opening the pods of every namespace.
+pub const ALL_NAMESPACES: &str = "<all namespaces>";
+
+pub const TABLE_PREFS_KEY: &str = "table_prefs";
+pub const FINDER_USAGE_KEY: &str = "finder_usage";
+
+pub const SELECTOR_HISTORY: &str = "pod_selector";
+pub const COMMAND_HISTORY: &str = "command";
+pub const HISTORY_LIMIT: i64 = 20;
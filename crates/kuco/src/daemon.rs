@@ -0,0 +1,638 @@
+/*
+ * `kuco daemon`: a single sync loop per kubeconfig context, shared by every KuCo instance instead
+ * of each polling the cluster on its own. The daemon keeps an in-memory cache per context, writes
+ * the persistent DB like the in-process sync does, and serves its caches over a Unix socket.
+ *
+ * The protocol is JSON lines, each carrying the protocol `version`. Requests are
+ *   {"version":1,"op":"hello"}                              -> hello, with the synced contexts
+ *   {"version":1,"op":"get","context":"dev","key":"k"}      -> value, null when not cached
+ *   {"version":1,"op":"snapshot","context":"dev"}           -> snapshot, every cached key
+ *   {"version":1,"op":"snapshot","context":"dev","since":3} -> snapshot, keys changed after 3
+ *   {"version":1,"op":"subscribe","context":"dev"}          -> subscribed, then changed ...
+ * and whatever cannot be served is answered with an error. A subscription takes over its
+ * connection; reads during one go over connections of their own.
+ */
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use kuco_k8s_backend::{
+    context::{KubeContext, list_contexts},
+    pods::{PodData, PodSelector},
+};
+use kuco_sqlite_backend::{KucoSqliteStore, SqliteCache, SqliteDb};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+    signal::unix::{SignalKind, signal},
+    sync::{RwLock, watch},
+};
+
+use crate::cli::Cli;
+use crate::config::SyncConfig;
+use crate::constants::{
+    ALL_POD_ROWS_CACHE_KEY, CURRENT_CONTEXT_CACHE_KEY, KUCO_CACHE_TABLE, NS_NAMES_CACHE_KEY,
+    POD_NAMES_CACHE_KEY, POD_ROWS_CACHE_KEY, POD_SELECTOR_CACHE_KEY,
+};
+use crate::sync::{cache_pod_lists, periodic_multistage_cache_sync};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Bounds of the backoff between checks for a daemon while syncing in-process.
+const DAEMON_RETRY_MIN: Duration = Duration::from_secs(5);
+const DAEMON_RETRY_MAX: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    Hello,
+    Get {
+        context: String,
+        key: String,
+    },
+    /// With `since`, only the keys that changed after that revision.
+    Snapshot {
+        context: String,
+        #[serde(default)]
+        since: Option<u64>,
+    },
+    Subscribe {
+        context: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Hello {
+        contexts: Vec<String>,
+    },
+    Value {
+        value: Option<serde_json::Value>,
+    },
+    Snapshot {
+        revision: u64,
+        entries: HashMap<String, serde_json::Value>,
+    },
+    /// The revision counts finished sync passes of the context.
+    Subscribed {
+        revision: u64,
+    },
+    Changed {
+        revision: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Message<T> {
+    version: u32,
+    #[serde(flatten)]
+    body: T,
+}
+
+async fn send<W: AsyncWrite + Unpin, T: Serialize>(writer: &mut W, body: T) -> Result<()> {
+    let message = Message {
+        version: PROTOCOL_VERSION,
+        body,
+    };
+    let mut line = serde_json::to_vec(&message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+
+    Ok(())
+}
+
+fn parse<T: DeserializeOwned>(line: &str) -> Result<T> {
+    let message: Message<serde_json::Value> =
+        serde_json::from_str(line).wrap_err("Malformed message")?;
+    if message.version != PROTOCOL_VERSION {
+        return Err(eyre!(
+            "Unsupported protocol version {} (expected {})",
+            message.version,
+            PROTOCOL_VERSION
+        ));
+    }
+
+    serde_json::from_value(message.body).wrap_err("Malformed message")
+}
+
+/*
+ * Server
+ */
+
+struct SyncedContext {
+    cache: Arc<SqliteCache>,
+    /// Bumped once `entries` caught up with a finished sync pass.
+    revision: watch::Receiver<u64>,
+    entries: Arc<RwLock<HashMap<String, TrackedEntry>>>,
+}
+
+/// A cached value, with the revision it last changed at.
+struct TrackedEntry {
+    revision: u64,
+    value: serde_json::Value,
+}
+
+type SyncedContexts = Arc<HashMap<String, SyncedContext>>;
+
+/// Sync every context of the kubeconfig and serve them on `socket` until interrupted.
+pub async fn run(cli: &Cli, socket: Option<PathBuf>) -> Result<i32> {
+    let config = cli.load_config()?;
+    let socket = match socket {
+        Some(socket) => socket,
+        None => config.daemon_socket()?,
+    };
+    let listener = bind(&socket).await?;
+
+    let db = SqliteDb::new(config.db_path()?, config.db.timeout_secs)
+        .await
+        .wrap_err("Sqlite cache init failed")?;
+    let arc_db = Arc::new(db);
+
+    let (names, _) = list_contexts(cli.kubeconfig.as_deref())?;
+    let mut contexts: HashMap<String, SyncedContext> = HashMap::new();
+    for name in names {
+        let mut kube_ctx = KubeContext {
+            kubeconfig: cli.kubeconfig.clone(),
+            ..Default::default()
+        };
+        if let Err(e) = kube_ctx.init_named_context(&name).await {
+            tracing::warn!("Daemon: skipping context '{}': {}", name, e);
+            continue;
+        }
+
+        let cache = SqliteCache::new_in_memory()
+            .await
+            .wrap_err("Sqlite cache init failed")?;
+        let arc_cache = Arc::new(cache);
+        let (synced_tx, synced_rx) = watch::channel(0);
+        let (revision_tx, revision_rx) = watch::channel(0);
+        let entries = Arc::new(RwLock::new(HashMap::new()));
+        tokio::spawn(periodic_multistage_cache_sync(
+            Arc::new(kube_ctx),
+            arc_cache.clone(),
+            arc_db.clone(),
+            config.sync.clone(),
+            Some(synced_tx),
+        ));
+        tokio::spawn(track_changes(
+            arc_cache.clone(),
+            synced_rx,
+            entries.clone(),
+            revision_tx,
+        ));
+        contexts.insert(
+            name,
+            SyncedContext {
+                cache: arc_cache,
+                revision: revision_rx,
+                entries,
+            },
+        );
+    }
+    if contexts.is_empty() {
+        let _ = std::fs::remove_file(&socket);
+        return Err(eyre!("No context of the kubeconfig could be synced"));
+    }
+
+    tracing::info!(
+        "Daemon syncing {} contexts, listening on {}",
+        contexts.len(),
+        socket.display()
+    );
+
+    let contexts: SyncedContexts = Arc::new(contexts);
+    let mut terminate = signal(SignalKind::terminate())?;
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve(stream, contexts.clone()));
+                }
+                Err(e) => tracing::error!("Daemon: failed to accept a connection: {}", e),
+            },
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+
+    tracing::info!("Daemon shutting down.");
+    std::fs::remove_file(&socket)
+        .wrap_err_with(|| format!("Failed to remove socket {}", socket.display()))?;
+
+    Ok(0)
+}
+
+// After every sync pass, record which cached values changed and at what revision, so subscribers
+// only fetch those.
+async fn track_changes(
+    cache: Arc<SqliteCache>,
+    mut synced: watch::Receiver<u64>,
+    entries: Arc<RwLock<HashMap<String, TrackedEntry>>>,
+    revision: watch::Sender<u64>,
+) {
+    while synced.changed().await.is_ok() {
+        let current = *synced.borrow_and_update();
+        let values = match read_cache(&cache).await {
+            Ok(values) => values,
+            Err(e) => {
+                tracing::error!("Daemon: failed to read the cache: {}", e);
+                continue;
+            }
+        };
+
+        let mut entries = entries.write().await;
+        for (key, value) in values {
+            if entries.get(&key).is_none_or(|entry| entry.value != value) {
+                entries.insert(
+                    key,
+                    TrackedEntry {
+                        revision: current,
+                        value,
+                    },
+                );
+            }
+        }
+        // Published under the lock, so a snapshot never sees a revision ahead of its entries.
+        revision.send_replace(current);
+    }
+}
+
+async fn read_cache(cache: &SqliteCache) -> Result<HashMap<String, serde_json::Value>> {
+    let mut values = HashMap::new();
+    for key in cache
+        .get_keys(KUCO_CACHE_TABLE.to_owned(), String::new())
+        .await?
+    {
+        if let Some(value) = cache
+            .get_json(KUCO_CACHE_TABLE.to_owned(), key.clone())
+            .await?
+        {
+            values.insert(key, value);
+        }
+    }
+
+    Ok(values)
+}
+
+// A socket file left behind by a daemon that died is replaced; one that still answers is not.
+async fn bind(socket: &Path) -> Result<UnixListener> {
+    if socket.exists() {
+        if UnixStream::connect(socket).await.is_ok() {
+            return Err(eyre!(
+                "A daemon is already listening on {}",
+                socket.display()
+            ));
+        }
+        std::fs::remove_file(socket)
+            .wrap_err_with(|| format!("Failed to remove stale socket {}", socket.display()))?;
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    UnixListener::bind(socket).wrap_err_with(|| format!("Failed to listen on {}", socket.display()))
+}
+
+async fn serve(stream: UnixStream, contexts: SyncedContexts) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let response = match parse::<Request>(&line) {
+            Ok(Request::Subscribe { context }) => {
+                subscribe(&mut writer, &contexts, &context).await;
+                return;
+            }
+            Ok(request) => respond(request, &contexts)
+                .await
+                .unwrap_or_else(|e| Response::Error {
+                    message: e.to_string(),
+                }),
+            Err(e) => Response::Error {
+                message: format!("{:#}", e),
+            },
+        };
+        if send(&mut writer, response).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn respond(request: Request, contexts: &SyncedContexts) -> Result<Response> {
+    let synced = |context: &str| {
+        contexts
+            .get(context)
+            .ok_or_else(|| eyre!("Context '{}' is not synced by this daemon", context))
+    };
+
+    Ok(match request {
+        Request::Hello => {
+            let mut names: Vec<String> = contexts.keys().cloned().collect();
+            names.sort();
+            Response::Hello { contexts: names }
+        }
+        Request::Get { context, key } => {
+            let cache = &synced(&context)?.cache;
+            let value = cache.get_json(KUCO_CACHE_TABLE.to_owned(), key).await?;
+            Response::Value { value }
+        }
+        Request::Snapshot { context, since } => {
+            let synced = synced(&context)?;
+            let entries = synced.entries.read().await;
+            let revision = *synced.revision.borrow();
+            let entries = entries
+                .iter()
+                .filter(|(_, entry)| since.is_none_or(|since| entry.revision > since))
+                .map(|(key, entry)| (key.clone(), entry.value.clone()))
+                .collect();
+            Response::Snapshot { revision, entries }
+        }
+        // Subscriptions take over their connection in `serve` and never get here.
+        Request::Subscribe { .. } => Response::Error {
+            message: "subscribe must be the first request".to_owned(),
+        },
+    })
+}
+
+async fn subscribe<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    contexts: &SyncedContexts,
+    context: &str,
+) {
+    let Some(synced) = contexts.get(context) else {
+        let message = format!("Context '{}' is not synced by this daemon", context);
+        let _ = send(writer, Response::Error { message }).await;
+        return;
+    };
+
+    let mut revision = synced.revision.clone();
+    let current = *revision.borrow_and_update();
+    if send(writer, Response::Subscribed { revision: current })
+        .await
+        .is_err()
+    {
+        return;
+    }
+    while revision.changed().await.is_ok() {
+        let current = *revision.borrow_and_update();
+        if send(writer, Response::Changed { revision: current })
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/*
+ * Client
+ */
+
+/// Send one request to the daemon at `socket`, on a connection of its own.
+pub async fn request(socket: &Path, request: Request) -> Result<Response> {
+    let stream = UnixStream::connect(socket).await?;
+    let (reader, mut writer) = stream.into_split();
+    send(&mut writer, request).await?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await?
+        .ok_or_else(|| eyre!("The daemon closed the connection"))?;
+    match parse(&line)? {
+        Response::Error { message } => Err(eyre!(message)),
+        response => Ok(response),
+    }
+}
+
+/// Keep the TUI's cache filled from a daemon syncing its current context, if one is listening on
+/// `socket`. Without one, or once it goes away, the cluster is synced in-process as usual until
+/// the daemon answers again, which is checked on a backoff.
+pub async fn sync_from_daemon_or_cluster<S: KucoSqliteStore + Clone + 'static>(
+    socket: PathBuf,
+    arc_kube_ctx: Arc<KubeContext>,
+    arc_cache_store: Arc<S>,
+    arc_db_store: Arc<SqliteDb>,
+    intervals: SyncConfig,
+) {
+    let cache_store: &S = arc_cache_store.as_ref();
+
+    loop {
+        let context = current_context(cache_store)
+            .await
+            .or_else(|| arc_kube_ctx.name.clone());
+        let Some(context) = context else {
+            // Without a context name there is nothing to ask a daemon for.
+            periodic_multistage_cache_sync(
+                arc_kube_ctx,
+                arc_cache_store,
+                arc_db_store,
+                intervals,
+                None,
+            )
+            .await;
+            return;
+        };
+
+        // Returns once the TUI switched context, to follow that one instead.
+        let Err(e) = follow_daemon(&socket, &arc_kube_ctx, &context, cache_store).await else {
+            continue;
+        };
+        tracing::info!("Not using a sync daemon for '{}': {}", context, e);
+
+        let in_process = tokio::spawn(periodic_multistage_cache_sync(
+            arc_kube_ctx.clone(),
+            arc_cache_store.clone(),
+            arc_db_store.clone(),
+            intervals.clone(),
+            None,
+        ));
+        let mut backoff = DAEMON_RETRY_MIN;
+        loop {
+            tokio::time::sleep(backoff).await;
+            let context = current_context(cache_store)
+                .await
+                .or_else(|| arc_kube_ctx.name.clone());
+            if let Some(context) = context {
+                if daemon_syncs(&socket, &context).await {
+                    break;
+                }
+            }
+            backoff = (backoff * 2).min(DAEMON_RETRY_MAX);
+        }
+        in_process.abort();
+        tracing::info!("A sync daemon answers again, syncing through it.");
+    }
+}
+
+async fn daemon_syncs(socket: &Path, context: &str) -> bool {
+    matches!(
+        request(socket, Request::Hello).await,
+        Ok(Response::Hello { contexts }) if contexts.iter().any(|c| c == context)
+    )
+}
+
+async fn current_context<S: KucoSqliteStore>(cache_store: &S) -> Option<String> {
+    cache_store
+        .get_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            CURRENT_CONTEXT_CACHE_KEY.to_owned(),
+        )
+        .await
+        .unwrap_or_default()
+}
+
+async fn follow_daemon<S: KucoSqliteStore>(
+    socket: &Path,
+    base_ctx: &KubeContext,
+    context: &str,
+    cache_store: &S,
+) -> Result<()> {
+    let stream = UnixStream::connect(socket).await?;
+    let (reader, mut writer) = stream.into_split();
+    send(
+        &mut writer,
+        Request::Subscribe {
+            context: context.to_owned(),
+        },
+    )
+    .await?;
+    let mut lines = BufReader::new(reader).lines();
+
+    // Only needed to list pods by the TUI's selector, which the daemon knows nothing about.
+    let mut selector_ctx: Option<KubeContext> = None;
+    // The revision mirrored last, and the selector it was mirrored under.
+    let mut mirrored: Option<(u64, PodSelector)> = None;
+    let mut context_check = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = line?.ok_or_else(|| eyre!("The daemon closed the connection"))?;
+                match parse(&line)? {
+                    Response::Subscribed { revision } => {
+                        tracing::info!("Attached to the sync daemon for '{}' at revision {}", context, revision);
+                    }
+                    Response::Changed { .. } => {}
+                    Response::Error { message } => return Err(eyre!(message)),
+                    response => return Err(eyre!("Unexpected response {:?}", response)),
+                }
+                mirror_snapshot(
+                    socket,
+                    base_ctx,
+                    &mut selector_ctx,
+                    &mut mirrored,
+                    context,
+                    cache_store,
+                )
+                .await?;
+            }
+            _ = context_check.tick() => {
+                if current_context(cache_store).await.is_some_and(|c| c != context) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn is_pod_list_key(key: &str) -> bool {
+    key == ALL_POD_ROWS_CACHE_KEY
+        || key.starts_with(POD_NAMES_CACHE_KEY)
+        || key.starts_with(POD_ROWS_CACHE_KEY)
+}
+
+// Copy what changed in the daemon's cache of `context` since the `mirrored` revision over the
+// TUI's, except for what the TUI publishes itself. While the TUI narrows the pod lists with a
+// selector, those are listed here instead.
+async fn mirror_snapshot<S: KucoSqliteStore>(
+    socket: &Path,
+    base_ctx: &KubeContext,
+    selector_ctx: &mut Option<KubeContext>,
+    mirrored: &mut Option<(u64, PodSelector)>,
+    context: &str,
+    cache_store: &S,
+) -> Result<()> {
+    let selector: PodSelector = cache_store
+        .get_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            POD_SELECTOR_CACHE_KEY.to_owned(),
+        )
+        .await?
+        .unwrap_or_default();
+
+    // A new selector changes which keys are mirrored, so everything is copied again.
+    let since = mirrored
+        .as_ref()
+        .filter(|(_, mirrored_selector)| *mirrored_selector == selector)
+        .map(|(revision, _)| *revision);
+    let Response::Snapshot { revision, entries } = request(
+        socket,
+        Request::Snapshot {
+            context: context.to_owned(),
+            since,
+        },
+    )
+    .await?
+    else {
+        return Err(eyre!("Unexpected response to a snapshot request"));
+    };
+
+    // The TUI may have switched context while the snapshot was on its way.
+    if current_context(cache_store)
+        .await
+        .is_some_and(|c| c != context)
+    {
+        return Ok(());
+    }
+
+    for (key, value) in &entries {
+        if key == CURRENT_CONTEXT_CACHE_KEY || key == POD_SELECTOR_CACHE_KEY {
+            continue;
+        }
+        if !selector.is_empty() && is_pod_list_key(key) {
+            continue;
+        }
+        cache_store
+            .set_json(KUCO_CACHE_TABLE.to_owned(), key.clone(), value)
+            .await?;
+    }
+
+    if !selector.is_empty() {
+        if selector_ctx.as_ref().and_then(|c| c.name.as_deref()) != Some(context) {
+            let mut kube_ctx = KubeContext {
+                kubeconfig: base_ctx.kubeconfig.clone(),
+                ..Default::default()
+            };
+            kube_ctx.init_named_context(context).await?;
+            *selector_ctx = Some(kube_ctx);
+        }
+        let client = selector_ctx
+            .as_ref()
+            .and_then(|c| c.client.clone())
+            .expect("Kube client not initialized");
+
+        let ns_names: Vec<String> = cache_store
+            .get_json(KUCO_CACHE_TABLE.to_owned(), NS_NAMES_CACHE_KEY.to_owned())
+            .await?
+            .unwrap_or_default();
+        let mut pods = PodData::default();
+        match pods.update_list_all(client, &selector).await {
+            Ok(_) => cache_pod_lists(cache_store, &ns_names, &pods).await?,
+            Err(e) => tracing::error!("Failed to list pods by selector: {}", e),
+        }
+    }
+    *mirrored = Some((revision, selector));
+
+    Ok(())
+}
@@ -4,19 +4,36 @@
 
 use chrono::{DateTime, Local};
 use color_eyre::{Result, eyre::WrapErr};
+use kube::Client;
 use ratatui::widgets::ListState;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 
 use kuco_k8s_backend::{
+    autoscaling::{HpaData, HpaInfo},
     containers::ContainerData,
     context::KubeContext,
+    discovery::{ApiKind, discover_kinds},
+    dynamic::DynamicData,
+    events::{EventData, EventInfo, EventScope},
     logs::LogData,
     namespaces::NamespaceData,
-    pods::{PodData, PodInfo},
+    nodes::{NodeInfo, get_pods_on_node},
+    pods::{PodData, PodInfo, PodSelector},
+    policy::PolicyData,
+    storage::StorageData,
 };
-use kuco_sqlite_backend::{KucoSqliteStore, SqliteCache};
+use kuco_sqlite_backend::{AuditEntry, AuditFilter, KucoSqliteStore, SqliteCache};
 
-use crate::constants::KUCO_CACHE_TABLE;
+use crate::constants::{
+    ALL_NAMESPACES, ALL_POD_ROWS_CACHE_KEY, CONT_NAMES_CACHE_KEY, CONTEXTS_CACHE_KEY,
+    CURRENT_CONTEXT_CACHE_KEY, KUCO_CACHE_TABLE, NODES_CACHE_KEY, NS_NAMES_CACHE_KEY,
+    POD_NAMES_CACHE_KEY, POD_ROWS_CACHE_KEY, POD_SELECTOR_CACHE_KEY,
+};
+use crate::finder::FinderEntry;
+use crate::line_editor::LineEditor;
+use crate::search::MatchMode;
+use crate::sync::cache_pod_lists;
 
 /*
  * Create a generic Kube Component State Structure.
@@ -24,13 +41,29 @@ use crate::constants::KUCO_CACHE_TABLE;
 
 #[derive(Debug, Clone, Default)]
 pub struct Search {
-    pub input: String,
+    /// The query, with this view's earlier searches as its history.
+    pub editor: LineEditor,
+    pub mode: MatchMode,
 }
 
 #[derive(Clone, Debug)]
 pub struct KubeComponentState {
     pub list_state: ListState,
     pub search: Search,
+    /// Pods marked for a bulk action. Kept as fully qualified pods rather than indexes or
+    /// display keys, so marks stay on the same objects when the list is refreshed or reordered
+    /// and never carry over to a same-named pod in another namespace or cluster.
+    pub marks: BTreeSet<PodRef>,
+    /// The pod last toggled, where a range mark starts from.
+    pub mark_anchor: Option<PodRef>,
+}
+
+/// A pod in a particular cluster, as marked in the pod views.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PodRef {
+    pub context: String,
+    pub namespace: String,
+    pub name: String,
 }
 
 impl KubeComponentState {
@@ -38,6 +71,87 @@ impl KubeComponentState {
         KubeComponentState {
             search: Search::default(),
             list_state: ListState::default(),
+            marks: BTreeSet::new(),
+            mark_anchor: None,
+        }
+    }
+
+    pub fn toggle_mark(&mut self, pod: PodRef) {
+        if !self.marks.remove(&pod) {
+            self.marks.insert(pod.clone());
+        }
+        self.mark_anchor = Some(pod);
+    }
+
+    /// Mark everything between the anchor and `pod` in `display`, both ends included. Without
+    /// an anchor in view, only `pod` is marked.
+    pub fn mark_range(&mut self, display: &[PodRef], pod: PodRef) {
+        let Some(end) = display.iter().position(|entry| *entry == pod) else {
+            return;
+        };
+        let start = self
+            .mark_anchor
+            .as_ref()
+            .and_then(|anchor| display.iter().position(|entry| entry == anchor))
+            .unwrap_or(end);
+
+        self.marks
+            .extend(display[start.min(end)..=start.max(end)].iter().cloned());
+        self.mark_anchor = Some(pod);
+    }
+
+    /// Mark every entry of `display`, i.e. whatever the search left, or unmark them all when
+    /// they already are.
+    pub fn mark_all(&mut self, display: &[PodRef]) {
+        if display.iter().all(|entry| self.marks.contains(entry)) {
+            for entry in display {
+                self.marks.remove(entry);
+            }
+        } else {
+            self.marks.extend(display.iter().cloned());
+        }
+    }
+
+    /// Drop the marks on pods no longer in `listed`, so pods that went away, or were marked in
+    /// another namespace or context, are never acted on unseen.
+    pub fn retain_marks(&mut self, listed: &[PodRef]) {
+        self.marks.retain(|pod| listed.contains(pod));
+        if self
+            .mark_anchor
+            .as_ref()
+            .is_some_and(|anchor| !self.marks.contains(anchor))
+        {
+            self.mark_anchor = None;
+        }
+    }
+}
+
+/*
+ * Event Filters
+ */
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum EventTypeFilter {
+    #[default]
+    All,
+    Warning,
+    Normal,
+}
+
+impl EventTypeFilter {
+    pub fn next(&self) -> Self {
+        match self {
+            EventTypeFilter::All => EventTypeFilter::Warning,
+            EventTypeFilter::Warning => EventTypeFilter::Normal,
+            EventTypeFilter::Normal => EventTypeFilter::All,
+        }
+    }
+
+    pub fn matches(&self, event: &EventInfo) -> bool {
+        match self {
+            EventTypeFilter::All => true,
+            EventTypeFilter::Warning => event.type_ == "Warning",
+            EventTypeFilter::Normal => event.type_ == "Normal",
         }
     }
 }
@@ -59,12 +173,55 @@ pub struct KubeData {
     pub current_pod_name: Option<String>,
     pub current_container_name: Option<String>,
     pub current_log_line: Option<String>,
+    pub current_node_name: Option<String>,
+    pub current_kind_name: Option<String>,
+    pub current_resource_name: Option<String>,
+    pub current_claim_name: Option<String>,
+    pub current_volume_name: Option<String>,
+    pub current_event_key: Option<String>,
+    pub current_hpa_name: Option<String>,
 
     pub current_pod_info: PodInfo,
 
+    // Cluster-scoped node data, and the "namespace/pod" keys of pods on the current node.
+    pub node_list: Vec<NodeInfo>,
+    pub node_pods_list: Vec<String>,
+
+    // Discovered API kinds, and objects of the currently selected kind.
+    pub api_kinds: Vec<ApiKind>,
+    pub dynamic: DynamicData,
+
+    // PersistentVolumeClaims in the current namespace and cluster-wide PersistentVolumes.
+    pub storage: StorageData,
+
+    // Events for the current scope, and the filters narrowing which of them are displayed.
+    pub events: EventData,
+    pub event_scope: EventScope,
+    pub event_type_filter: EventTypeFilter,
+    pub event_reason_filter: Option<String>,
+
+    // HorizontalPodAutoscalers in the current namespace.
+    pub hpas: HpaData,
+
+    // Quotas, LimitRanges and PodDisruptionBudgets of the current namespace.
+    pub policy: PolicyData,
+
+    // Pods whose logs are shown interleaved in the logs view, as (namespace, pod), instead of
+    // the current container's.
+    pub merged_logs: Vec<(String, String)>,
+
+    // Audit log entries matching the filter of the audit view, loaded from the persistent DB.
+    pub audit: Vec<AuditEntry>,
+    pub audit_filter: AuditFilter,
+
     // TODO: Refactor old components into new ones from cache
     pub namespace_names_list: Vec<String>,
     pub pod_names_list: Vec<String>,
+    pub pod_rows_list: Vec<PodInfo>,
+    pub all_pod_rows_list: Vec<PodInfo>,
+
+    // Label/field selector narrowing pod lists, shared with the sync task through the cache.
+    pub pod_selector: PodSelector,
 
     // TODO: Refactor old struct members
     pub namespaces: NamespaceData,
@@ -86,6 +243,27 @@ impl KubeData {
             namespaces: NamespaceData::new(),
             current_namespace_name: None,
             current_log_line: None,
+            current_node_name: None,
+            node_list: Vec::new(),
+            node_pods_list: Vec::new(),
+            current_kind_name: None,
+            current_resource_name: None,
+            api_kinds: Vec::new(),
+            dynamic: DynamicData::default(),
+            current_claim_name: None,
+            current_volume_name: None,
+            storage: StorageData::default(),
+            current_event_key: None,
+            events: EventData::default(),
+            event_scope: EventScope::default(),
+            event_type_filter: EventTypeFilter::default(),
+            event_reason_filter: None,
+            current_hpa_name: None,
+            hpas: HpaData::default(),
+            policy: PolicyData::default(),
+            merged_logs: Vec::new(),
+            audit: Vec::new(),
+            audit_filter: AuditFilter::default(),
             pods: PodData::default(),
             current_pod_info: PodInfo::default(),
             current_pod_name: None,
@@ -94,11 +272,28 @@ impl KubeData {
             logs: LogData::new(),
             namespace_names_list: Vec::new(),
             pod_names_list: Vec::new(),
+            pod_rows_list: Vec::new(),
+            all_pod_rows_list: Vec::new(),
+            pod_selector: PodSelector::default(),
         }
     }
 
+    pub fn client(&self) -> Option<Client> {
+        self.context.client.clone()
+    }
+
+    pub fn set_context(&mut self, context: KubeContext) {
+        self.context = context;
+    }
+
+    pub fn context_name(&self) -> Option<&str> {
+        self.context.name.as_deref()
+    }
+
     pub fn get_namespaces(&mut self) -> Vec<String> {
-        self.namespace_names_list.clone()
+        let mut namespaces = vec![ALL_NAMESPACES.to_owned()];
+        namespaces.extend(self.namespace_names_list.iter().cloned());
+        namespaces
     }
 
     pub fn get_pods(&mut self) -> Vec<String> {
@@ -106,6 +301,117 @@ impl KubeData {
         // self.pods.names.clone()
     }
 
+    pub fn get_all_pods(&self) -> Vec<String> {
+        self.all_pod_rows_list
+            .iter()
+            .map(|p| format!("{}/{}", p.namespace, p.name))
+            .collect()
+    }
+
+    pub fn get_nodes(&mut self) -> Vec<String> {
+        self.node_list.iter().map(|n| n.name.clone()).collect()
+    }
+
+    pub fn get_node_pods(&mut self) -> Vec<String> {
+        self.node_pods_list.clone()
+    }
+
+    pub fn get_kinds(&mut self) -> Vec<String> {
+        self.api_kinds.iter().map(|k| k.key()).collect()
+    }
+
+    pub fn get_resources(&mut self) -> Vec<String> {
+        self.dynamic.names.clone()
+    }
+
+    pub fn get_claims(&mut self) -> Vec<String> {
+        self.storage.claims.iter().map(|c| c.name.clone()).collect()
+    }
+
+    pub fn get_volumes(&mut self) -> Vec<String> {
+        self.storage
+            .volumes
+            .iter()
+            .map(|v| v.name.clone())
+            .collect()
+    }
+
+    pub fn get_events(&self) -> Vec<String> {
+        self.events
+            .list
+            .iter()
+            .filter(|e| self.event_type_filter.matches(e))
+            .filter(|e| {
+                self.event_reason_filter
+                    .as_ref()
+                    .is_none_or(|reason| &e.reason == reason)
+            })
+            .map(|e| e.key())
+            .collect()
+    }
+
+    pub fn get_hpas(&mut self) -> Vec<String> {
+        self.hpas.list.iter().map(|h| h.name.clone()).collect()
+    }
+
+    pub fn get_policy(&mut self) -> Vec<String> {
+        self.policy.keys()
+    }
+
+    /// Newest entries first, as the list is drawn bottom to top.
+    pub fn get_audit(&self) -> Vec<String> {
+        self.audit.iter().rev().map(|entry| entry.key()).collect()
+    }
+
+    pub fn current_hpa(&self) -> Option<&HpaInfo> {
+        let name = self.current_hpa_name.as_ref()?;
+        self.hpas.list.iter().find(|h| &h.name == name)
+    }
+
+    pub fn current_event(&self) -> Option<&EventInfo> {
+        let key = self.current_event_key.as_ref()?;
+        self.events.list.iter().find(|e| &e.key() == key)
+    }
+
+    /// Step the reason filter through every reason the type filter leaves listed, then back to
+    /// none.
+    pub fn cycle_event_reason_filter(&mut self) {
+        let mut reasons: Vec<&String> = self
+            .events
+            .list
+            .iter()
+            .filter(|e| self.event_type_filter.matches(e))
+            .map(|e| &e.reason)
+            .collect();
+        reasons.sort();
+        reasons.dedup();
+
+        let next = match &self.event_reason_filter {
+            None => reasons.first().copied(),
+            Some(current) => reasons
+                .iter()
+                .position(|r| *r == current)
+                .and_then(|i| reasons.get(i + 1))
+                .copied(),
+        };
+        self.event_reason_filter = next.cloned();
+    }
+
+    pub fn current_kind(&self) -> Option<&ApiKind> {
+        let key = self.current_kind_name.as_ref()?;
+        self.api_kinds.iter().find(|k| &k.key() == key)
+    }
+
+    /// The discovered kind called `kind`. Where several groups serve one, like `Event` in both
+    /// core/v1 and events.k8s.io, the core group wins; discovery already picked each group's
+    /// preferred version.
+    pub fn api_kind_named(&self, kind: &str) -> Option<&ApiKind> {
+        self.api_kinds
+            .iter()
+            .filter(|k| k.kind == kind)
+            .min_by_key(|k| !k.group.is_empty())
+    }
+
     pub fn get_logs(&mut self) -> Vec<String> {
         self.logs.lines.clone()
     }
@@ -188,6 +494,13 @@ impl KubeData {
     }
 
     pub async fn update_logs_lines_list(&mut self) {
+        if !self.merged_logs.is_empty() {
+            if let Some(client) = self.client() {
+                let _ = self.logs.update_merged(client, &self.merged_logs).await;
+            }
+            return;
+        }
+
         let ns: String = match &self.current_namespace_name {
             Some(s) => s.to_owned(),
             None => "default".to_owned(),
@@ -257,6 +570,403 @@ impl KubeData {
         };
     }
 
+    pub async fn update_nodes_list(&mut self) -> Result<()> {
+        let store = &self.arc_ctx;
+
+        let fetched_nodes: Vec<NodeInfo> = store
+            .get_json::<Vec<NodeInfo>>(KUCO_CACHE_TABLE.to_owned(), NODES_CACHE_KEY.to_owned())
+            .await
+            .wrap_err_with(|| format!("Failed to get JSON for key '{}'", NODES_CACHE_KEY))?
+            .unwrap_or_default();
+
+        self.node_list = fetched_nodes;
+
+        Ok(())
+    }
+
+    // Pods on a node span namespaces, so they are fetched live with a field selector rather
+    // than assembled from the per-namespace cache entries.
+    pub async fn update_node_pods_list(&mut self) {
+        match &self.current_node_name {
+            Some(node) => {
+                match get_pods_on_node(
+                    self.context
+                        .client
+                        .clone() // TODO: check if there is a way to avoid cloning ...
+                        .expect("[ERROR] Client is None."),
+                    node,
+                )
+                .await
+                {
+                    Ok(pods) => {
+                        self.node_pods_list = pods
+                            .into_iter()
+                            .map(|(ns, po)| format!("{}/{}", ns, po))
+                            .collect();
+                    }
+                    Err(e) => tracing::error!("Failed to list pods on node {}: {}", node, e),
+                }
+            }
+            None => {
+                tracing::warn!(
+                    "No current node selected. Nothing to do. Could be a potential bug. ;)"
+                );
+            }
+        };
+    }
+
+    // Discovery is comparatively expensive and rarely changes, so it only runs the first time the
+    // kind picker is opened, and again when it is refreshed.
+    pub async fn update_api_kinds_list(&mut self) {
+        if !self.api_kinds.is_empty() {
+            return;
+        }
+
+        self.discover_api_kinds().await;
+    }
+
+    /// Run discovery again, keeping the kinds found earlier if it fails.
+    pub async fn discover_api_kinds(&mut self) {
+        match discover_kinds(
+            self.context
+                .client
+                .clone() // TODO: check if there is a way to avoid cloning ...
+                .expect("[ERROR] Client is None."),
+        )
+        .await
+        {
+            Ok(kinds) => self.api_kinds = kinds,
+            Err(e) => tracing::error!("Failed to discover API kinds: {}", e),
+        }
+    }
+
+    pub async fn update_resources_list(&mut self) {
+        let ns: String = match &self.current_namespace_name {
+            Some(s) => s.to_owned(),
+            None => "default".to_owned(),
+        };
+
+        let Some(kind) = self.current_kind().cloned() else {
+            tracing::warn!("No current kind selected. Nothing to do. Could be a potential bug. ;)");
+            return;
+        };
+
+        if let Err(e) = self
+            .dynamic
+            .update(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+                &kind,
+                &ns,
+            )
+            .await
+        {
+            tracing::error!("Failed to list {}: {}", kind.key(), e);
+            self.dynamic = DynamicData::default();
+        }
+    }
+
+    pub async fn update_claims_list(&mut self) {
+        let ns: String = match &self.current_namespace_name {
+            Some(s) => s.to_owned(),
+            None => "default".to_owned(),
+        };
+
+        if let Err(e) = self
+            .storage
+            .update_claims(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+                &ns,
+            )
+            .await
+        {
+            tracing::error!("Failed to list persistent volume claims in {}: {}", ns, e);
+        }
+    }
+
+    pub async fn update_volumes_list(&mut self) {
+        if let Err(e) = self
+            .storage
+            .update_volumes(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+            )
+            .await
+        {
+            tracing::error!("Failed to list persistent volumes: {}", e);
+        }
+    }
+
+    pub async fn update_hpas_list(&mut self) {
+        let ns: String = match &self.current_namespace_name {
+            Some(s) => s.to_owned(),
+            None => "default".to_owned(),
+        };
+
+        if let Err(e) = self
+            .hpas
+            .update(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+                &ns,
+            )
+            .await
+        {
+            tracing::error!("Failed to list horizontal pod autoscalers in {}: {}", ns, e);
+        }
+    }
+
+    pub async fn update_policy(&mut self) {
+        let ns: String = match &self.current_namespace_name {
+            Some(s) => s.to_owned(),
+            None => "default".to_owned(),
+        };
+
+        if let Err(e) = self
+            .policy
+            .update(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+                &ns,
+            )
+            .await
+        {
+            tracing::error!("Failed to list policies in {}: {}", ns, e);
+        }
+    }
+
+    // Events are listed live for the scope rather than cached, so that opening the view (or a
+    // refresh) always reflects the latest occurrences.
+    pub async fn update_events_list(&mut self) {
+        if let Err(e) = self
+            .events
+            .update(
+                self.context
+                    .client
+                    .clone() // TODO: check if there is a way to avoid cloning ...
+                    .expect("[ERROR] Client is None."),
+                &self.event_scope,
+            )
+            .await
+        {
+            tracing::error!(
+                "Failed to list events in {}: {}",
+                self.event_scope.namespace,
+                e
+            );
+        }
+    }
+
+    /// Switch pod lists over to `selector`. Pods are listed right away, so a selector the API
+    /// server rejects is reported here and never replaces the active one.
+    pub async fn apply_pod_selector(&mut self, selector: PodSelector) -> Result<()> {
+        let mut pods = PodData::default();
+        pods.update_list_all(
+            self.context
+                .client
+                .clone() // TODO: check if there is a way to avoid cloning ...
+                .expect("[ERROR] Client is None."),
+            &selector,
+        )
+        .await?;
+
+        let store = self.arc_ctx.as_ref();
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                POD_SELECTOR_CACHE_KEY.to_owned(),
+                &selector,
+            )
+            .await?;
+        cache_pod_lists(store, &self.namespace_names_list, &pods).await?;
+
+        self.pod_selector = selector;
+        self.update_pods_names_list().await?;
+        self.update_all_pods_list().await?;
+
+        Ok(())
+    }
+
+    /// Kubeconfig context names, as cached by the sync task.
+    pub async fn get_contexts(&self) -> Result<Vec<String>> {
+        Ok(self
+            .arc_ctx
+            .get_json(KUCO_CACHE_TABLE.to_owned(), CONTEXTS_CACHE_KEY.to_owned())
+            .await?
+            .unwrap_or_default())
+    }
+
+    // Point the TUI, and through the cache the sync task, at another kubeconfig context. The new
+    // cluster is listed before anything is replaced, so an unreachable context changes nothing.
+    // The new context is published before the cache is emptied of the previous cluster, so a
+    // sync still listing that one drops its results instead of writing them back, and the cache
+    // is then refilled with what was just listed.
+    pub async fn switch_context(&mut self, name: &str) -> Result<()> {
+        let mut context = KubeContext {
+            kubeconfig: self.context.kubeconfig.clone(),
+            ..Default::default()
+        };
+        context.init_named_context(name).await?;
+        let client = context.client.clone().expect("[ERROR] Client is None.");
+
+        let mut pods = PodData::default();
+        pods.update_list_all(client.clone(), &self.pod_selector)
+            .await?;
+        let mut namespaces = NamespaceData::new();
+        namespaces.update(client).await?;
+
+        let store = self.arc_ctx.as_ref();
+        let contexts = self.get_contexts().await?;
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                CURRENT_CONTEXT_CACHE_KEY.to_owned(),
+                &name.to_owned(),
+            )
+            .await?;
+        store.clear_all_kv(KUCO_CACHE_TABLE.to_owned()).await?;
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                CURRENT_CONTEXT_CACHE_KEY.to_owned(),
+                &name.to_owned(),
+            )
+            .await?;
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                CONTEXTS_CACHE_KEY.to_owned(),
+                &contexts,
+            )
+            .await?;
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                POD_SELECTOR_CACHE_KEY.to_owned(),
+                &self.pod_selector,
+            )
+            .await?;
+        store
+            .set_json(
+                KUCO_CACHE_TABLE.to_owned(),
+                NS_NAMES_CACHE_KEY.to_owned(),
+                &namespaces.names,
+            )
+            .await?;
+        cache_pod_lists(store, &namespaces.names, &pods).await?;
+
+        self.context = context;
+        self.current_namespace_name = None;
+        self.current_pod_name = None;
+        self.current_container_name = None;
+        self.current_log_line = None;
+        self.current_node_name = None;
+        self.current_kind_name = None;
+        self.current_resource_name = None;
+        self.current_claim_name = None;
+        self.current_volume_name = None;
+        self.current_event_key = None;
+        self.current_hpa_name = None;
+        self.current_pod_info = PodInfo::default();
+        self.node_list.clear();
+        self.api_kinds.clear();
+
+        self.update_namespaces_names_list().await?;
+        self.update_pods_names_list().await?;
+        self.update_all_pods_list().await?;
+
+        Ok(())
+    }
+
+    /// Every namespace, pod and container the sync task has cached so far.
+    ///
+    /// The cache keeps the lists of namespaces and the containers of pods that have since gone
+    /// away, so pods and containers are only listed under what the last sync still found.
+    pub async fn finder_entries(&self) -> Result<Vec<FinderEntry>> {
+        let store = &self.arc_ctx;
+
+        let namespaces: Vec<String> = store
+            .get_json(KUCO_CACHE_TABLE.to_owned(), NS_NAMES_CACHE_KEY.to_owned())
+            .await?
+            .unwrap_or_default();
+        let mut entries: Vec<FinderEntry> = namespaces
+            .iter()
+            .cloned()
+            .map(FinderEntry::Namespace)
+            .collect();
+
+        let mut pods_listed: HashSet<(String, String)> = HashSet::new();
+        for namespace in &namespaces {
+            let key = format!("{}{}", POD_NAMES_CACHE_KEY, namespace);
+            let pods: Vec<String> = store
+                .get_json(KUCO_CACHE_TABLE.to_owned(), key)
+                .await?
+                .unwrap_or_default();
+            for pod in pods {
+                pods_listed.insert((namespace.clone(), pod.clone()));
+                entries.push(FinderEntry::Pod {
+                    namespace: namespace.clone(),
+                    pod,
+                });
+            }
+        }
+
+        // Container keys are "cont_<namespace>_<pod>"; namespace names cannot contain '_'.
+        for key in store
+            .get_keys(KUCO_CACHE_TABLE.to_owned(), CONT_NAMES_CACHE_KEY.to_owned())
+            .await?
+        {
+            let Some((namespace, pod)) = key[CONT_NAMES_CACHE_KEY.len()..].split_once('_') else {
+                continue;
+            };
+            let (namespace, pod) = (namespace.to_owned(), pod.to_owned());
+            if !pods_listed.contains(&(namespace.clone(), pod.clone())) {
+                continue;
+            }
+            let containers: Vec<String> = store
+                .get_json(KUCO_CACHE_TABLE.to_owned(), key)
+                .await?
+                .unwrap_or_default();
+            entries.extend(
+                containers
+                    .into_iter()
+                    .map(|container| FinderEntry::Container {
+                        namespace: namespace.clone(),
+                        pod: pod.clone(),
+                        container,
+                    }),
+            );
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn update_all_pods_list(&mut self) -> Result<()> {
+        let store = &self.arc_ctx;
+
+        self.all_pod_rows_list = store
+            .get_json::<Vec<PodInfo>>(
+                KUCO_CACHE_TABLE.to_owned(),
+                ALL_POD_ROWS_CACHE_KEY.to_owned(),
+            )
+            .await
+            .wrap_err_with(|| format!("Failed to get JSON for key '{}'", ALL_POD_ROWS_CACHE_KEY))?
+            .unwrap_or_default();
+
+        Ok(())
+    }
+
     pub async fn update_pods_names_list(&mut self) -> Result<()> {
         let ns: String = match &self.current_namespace_name {
             Some(s) => s.to_owned(),
@@ -275,6 +985,16 @@ impl KubeData {
 
         self.pod_names_list = fetched_pods;
 
+        let rows_key_name = format!("{}{}", POD_ROWS_CACHE_KEY, ns);
+
+        let fetched_rows: Vec<PodInfo> = store
+            .get_json::<Vec<PodInfo>>(KUCO_CACHE_TABLE.to_owned(), rows_key_name.clone())
+            .await
+            .wrap_err_with(|| format!("Failed to get JSON for key '{}'", rows_key_name))?
+            .unwrap_or_default();
+
+        self.pod_rows_list = fetched_rows;
+
         Ok(())
     }
 }
@@ -285,6 +1005,17 @@ pub struct KubeWidgetState {
     pub pods_state: KubeComponentState,
     pub containers_state: KubeComponentState,
     pub logs_state: KubeComponentState,
+    pub nodes_state: KubeComponentState,
+    pub node_pods_state: KubeComponentState,
+    pub all_pods_state: KubeComponentState,
+    pub kinds_state: KubeComponentState,
+    pub resources_state: KubeComponentState,
+    pub pvcs_state: KubeComponentState,
+    pub pvs_state: KubeComponentState,
+    pub events_state: KubeComponentState,
+    pub hpas_state: KubeComponentState,
+    pub policy_state: KubeComponentState,
+    pub audit_state: KubeComponentState,
 }
 
 impl Default for KubeWidgetState {
@@ -300,6 +1031,17 @@ impl KubeWidgetState {
             pods_state: KubeComponentState::new(),
             containers_state: KubeComponentState::new(),
             logs_state: KubeComponentState::new(),
+            nodes_state: KubeComponentState::new(),
+            node_pods_state: KubeComponentState::new(),
+            all_pods_state: KubeComponentState::new(),
+            kinds_state: KubeComponentState::new(),
+            resources_state: KubeComponentState::new(),
+            pvcs_state: KubeComponentState::new(),
+            pvs_state: KubeComponentState::new(),
+            events_state: KubeComponentState::new(),
+            hpas_state: KubeComponentState::new(),
+            policy_state: KubeComponentState::new(),
+            audit_state: KubeComponentState::new(),
         }
     }
 }
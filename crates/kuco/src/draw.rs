@@ -1,12 +1,31 @@
+use chrono::{Local, TimeZone};
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Paragraph},
+    widgets::{Block, BorderType, Clear, Paragraph, Wrap},
 };
 
-use crate::{app::*, constants::KUCO_VERSION, data::KubeComponentState};
+use crate::{
+    app::*,
+    constants::{ALL_NAMESPACES, KUCO_VERSION},
+    data::{EventTypeFilter, KubeComponentState},
+    keymap::Action,
+    popup::Popup,
+    yaml::YamlViewer,
+};
+
+/// Center a rect of `percent_x` by `percent_y` of `area`.
+fn centered_rect(area: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Percentage(percent_x)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Percentage(percent_y)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
 
 impl Kuco {
     pub fn draw_view(&mut self, f: &mut Frame<'_>, mode_state: &mut KubeComponentState) {
@@ -38,24 +57,37 @@ impl Kuco {
         let bot_chunk = chunks[3];
 
         // Interaction Mode Display
-        let mode: &str;
+        let mode: String;
         let col: Color;
         match self.view.interact_mode {
             InteractionMode::NORMAL => {
-                mode = "NORMAL";
+                mode = "NORMAL".to_owned();
                 col = Color::White;
             }
             InteractionMode::SEARCH => {
-                mode = "SEARCH";
+                mode = format!("SEARCH:{}", mode_state.search.mode.label());
                 col = Color::Cyan;
             }
+            InteractionMode::SELECTOR => {
+                mode = "SELECTOR".to_owned();
+                col = Color::Magenta;
+            }
+            InteractionMode::COMMAND => {
+                mode = "COMMAND".to_owned();
+                col = Color::Green;
+            }
         }
 
         // Input Display Configuration
-        let mut search_input_string = mode_state.search.input.as_str();
+        let editor = match self.view.interact_mode {
+            InteractionMode::NORMAL => None,
+            InteractionMode::SEARCH => Some(&mode_state.search.editor),
+            InteractionMode::SELECTOR => Some(&self.selector_prompt.editor),
+            InteractionMode::COMMAND => Some(&self.command_prompt.editor),
+        };
 
         // TODO: Make this more elegant later ...
-        let mut navigation = "".to_owned();
+        let mut navigation = String::new();
         if self.view.interact_mode == InteractionMode::NORMAL {
             navigation = match self.view.view_mode {
                 ViewMode::NS => self
@@ -100,6 +132,9 @@ impl Kuco {
                         .unwrap_or("".to_owned());
                     format!("{} > {} > {}", ns, po, co)
                 }
+                ViewMode::LOGS if !self.view.data.merged_logs.is_empty() => {
+                    format!("logs > {} pods", self.view.data.merged_logs.len())
+                }
                 ViewMode::LOGS => {
                     let ns = self
                         .view
@@ -121,12 +156,216 @@ impl Kuco {
                         .unwrap_or("".to_owned());
                     format!("{} > {} > {}", ns, po, co)
                 }
+                ViewMode::NODES => self
+                    .view
+                    .data
+                    .current_node_name
+                    .clone()
+                    .unwrap_or("".to_owned()),
+                ViewMode::NODEPODS => {
+                    let no = self
+                        .view
+                        .data
+                        .current_node_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let ns = self
+                        .view
+                        .data
+                        .current_namespace_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let po = self
+                        .view
+                        .data
+                        .current_pod_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("{} > {}/{}", no, ns, po)
+                }
+                ViewMode::ALLPODS => {
+                    let ns = self
+                        .view
+                        .data
+                        .current_namespace_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let po = self
+                        .view
+                        .data
+                        .current_pod_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("{} > {}/{}", ALL_NAMESPACES, ns, po)
+                }
+                ViewMode::KINDS => self
+                    .view
+                    .data
+                    .current_kind_name
+                    .clone()
+                    .unwrap_or("".to_owned()),
+                ViewMode::RESOURCES => {
+                    let ki = self
+                        .view
+                        .data
+                        .current_kind_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let re = self
+                        .view
+                        .data
+                        .current_resource_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    match self.view.data.current_kind() {
+                        Some(kind) if kind.namespaced => {
+                            let ns = self
+                                .view
+                                .data
+                                .current_namespace_name
+                                .clone()
+                                .unwrap_or("".to_owned());
+                            format!("{} > {} > {}", ns, ki, re)
+                        }
+                        _ => format!("{} > {}", ki, re),
+                    }
+                }
+                ViewMode::PVCS => {
+                    let ns = self
+                        .view
+                        .data
+                        .current_namespace_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let pvc = self
+                        .view
+                        .data
+                        .current_claim_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("{} > pvc > {}", ns, pvc)
+                }
+                ViewMode::PVS => {
+                    let pv = self
+                        .view
+                        .data
+                        .current_volume_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("pv > {}", pv)
+                }
+                ViewMode::POLICY => {
+                    let ns = self
+                        .view
+                        .data
+                        .current_namespace_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("{} > policy", ns)
+                }
+                ViewMode::HPAS => {
+                    let ns = self
+                        .view
+                        .data
+                        .current_namespace_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    let hpa = self
+                        .view
+                        .data
+                        .current_hpa_name
+                        .clone()
+                        .unwrap_or("".to_owned());
+                    format!("{} > hpa > {}", ns, hpa)
+                }
+                ViewMode::EVENTS => {
+                    let data = &self.view.data;
+                    let mut nav = match &data.event_scope.object {
+                        Some((kind, name)) => {
+                            format!("events > {}/{}", kind.to_lowercase(), name)
+                        }
+                        None => format!("{} > events", data.event_scope.namespace),
+                    };
+                    if data.event_type_filter != EventTypeFilter::All {
+                        nav += &format!(" [type: {:?}]", data.event_type_filter);
+                    }
+                    if let Some(reason) = &data.event_reason_filter {
+                        nav += &format!(" [reason: {}]", reason);
+                    }
+                    if self.event_watch.is_some() {
+                        nav += " [live]";
+                    }
+                    nav
+                }
+                ViewMode::AUDIT => {
+                    let filter = &self.view.data.audit_filter;
+                    let mut nav = "audit".to_owned();
+                    let fields = [
+                        ("ctx", &filter.context),
+                        ("ns", &filter.namespace),
+                        ("kind", &filter.kind),
+                        ("name", &filter.name),
+                        ("action", &filter.action),
+                    ];
+                    for (field, value) in fields {
+                        if let Some(value) = value {
+                            nav += &format!(" [{}: {}]", field, value);
+                        }
+                    }
+                    match filter.succeeded {
+                        Some(true) => nav += " [outcome: ok]",
+                        Some(false) => nav += " [outcome: failed]",
+                        None => {}
+                    }
+                    if let Some(since) = filter
+                        .since
+                        .and_then(|at| Local.timestamp_opt(at, 0).single())
+                    {
+                        nav += &format!(" [since: {}]", since.format("%Y-%m-%d %H:%M"));
+                    }
+                    nav
+                }
             };
-            search_input_string = &navigation;
+            if !mode_state.marks.is_empty() {
+                navigation += &format!("  [{} marked]", mode_state.marks.len());
+            }
         };
 
-        let input = format!("[ {} ] {}", mode, search_input_string,);
-        let input = Paragraph::new(input).style(Style::default().fg(col));
+        let input_line = match editor {
+            Some(editor) => {
+                let mut line = editor.line(Style::default());
+                line.spans.insert(0, Span::from(format!("[ {} ] ", mode)));
+                if self.view.interact_mode == InteractionMode::COMMAND {
+                    line.spans.insert(1, Span::from(":"));
+                    if let Some(error) = &self.command_prompt.error {
+                        line.spans.push(Span::styled(
+                            format!("  {}", error),
+                            Style::default().fg(Color::Red),
+                        ));
+                    }
+                }
+                line
+            }
+            None => {
+                let mut line = Line::from(format!("[ {} ] {}", mode, navigation));
+                let pending = self.keymap.pending();
+                if !pending.is_empty() {
+                    line.spans.push(Span::styled(
+                        format!("  {}", pending),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                line
+            }
+        };
+        let mut input_lines = vec![input_line];
+        if let Some(message) = &self.status_message {
+            input_lines.push(Line::styled(
+                message.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        let input = Paragraph::new(Text::from(input_lines)).style(Style::default().fg(col));
         let input_block =
             input.block(Block::default().title(format!("{:─>width$}", "", width = 12)));
 
@@ -144,11 +383,46 @@ impl Kuco {
             .fg(Color::Black)
             .bg(Color::White)
             .add_modifier(Modifier::ITALIC | Modifier::BOLD);
-        let title = Paragraph::new(Text::from(Span::styled(
+        let mut heading = vec![Span::styled(
             format!("KuCo v{}", KUCO_VERSION),
             heading_style,
-        )))
-        .alignment(Alignment::Left);
+        )];
+        // Protected contexts get a banner that stays up whatever the view, so that nobody
+        // mistakes production for a scratch cluster.
+        let protection = self.protection();
+        match (&protection.banner, self.view.data.context_name()) {
+            (Some((label, color)), _) => {
+                heading.push(Span::raw(" "));
+                heading.push(Span::styled(
+                    format!(" {} ", label),
+                    Style::new()
+                        .fg(Color::Black)
+                        .bg(*color)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            (None, Some(context)) => heading.push(Span::styled(
+                format!(" {}", context),
+                Style::new().fg(Color::DarkGray),
+            )),
+            (None, None) => {}
+        }
+        if protection.readonly {
+            heading.push(Span::styled(
+                " READ-ONLY",
+                Style::new()
+                    .fg(protection.banner.map_or(Color::Red, |(_, color)| color))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        let mut title_lines = vec![Line::from(heading)];
+        if !self.view.data.pod_selector.is_empty() {
+            title_lines.push(Line::styled(
+                format!("selector: {}", self.view.data.pod_selector),
+                Style::new().fg(Color::Magenta),
+            ));
+        }
+        let title = Paragraph::new(Text::from(title_lines)).alignment(Alignment::Left);
         f.render_widget(&title, title_block);
 
         // Define Refresh Header
@@ -169,7 +443,13 @@ impl Kuco {
                     format!("last refreshed at {:#}", self.view.data.last_refreshed_at),
                     refresh_style,
                 ),
-                Line::styled("press 'r' to refresh".to_string(), help_style),
+                Line::styled(
+                    match self.keymap.keys_for(&self.view.view_mode, Action::Refresh) {
+                        Some(keys) => format!("press '{}' to refresh", keys),
+                        None => String::new(),
+                    },
+                    help_style,
+                ),
             ];
 
             Paragraph::new(Text::from(text)).alignment(Alignment::Right)
@@ -186,5 +466,48 @@ impl Kuco {
 
         // Render Input Block
         f.render_widget(input_block, bot_chunk);
+
+        // Render Popup Over Everything Else
+        match &mut self.popup {
+            Some(Popup::Yaml(viewer)) => draw_yaml(f, viewer),
+            Some(popup) => draw_popup(f, popup),
+            None => {}
+        }
     }
 }
+
+// Manifests are long and wide, so the viewer gets most of the screen and lines are cut rather
+// than wrapped, keeping one line of YAML to one row.
+fn draw_yaml(f: &mut Frame<'_>, viewer: &mut YamlViewer) {
+    let area = centered_rect(f.area(), 90, 90);
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(format!(" {} ", viewer.title()))
+        .style(Style::default().fg(Color::White));
+
+    let lines = viewer.lines(area.height.saturating_sub(2) as usize);
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(Text::from(lines)).block(block), area);
+}
+
+fn draw_popup(f: &mut Frame<'_>, popup: &Popup) {
+    let area = centered_rect(f.area(), 70, 60);
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(popup.title())
+        .style(Style::default().fg(Color::White));
+
+    // Keep the tail of long progress lists visible, since that is where failures end up.
+    let lines = popup.lines();
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = lines.len().saturating_sub(visible) as u16;
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
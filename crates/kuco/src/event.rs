@@ -1,9 +1,16 @@
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
+use kuco_k8s_backend::{
+    delete::DeleteProgress,
+    drain::DrainProgress,
+    events::{EventChange, EventScope},
+};
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+use crate::popup::{BulkAction, ProgressItem};
+
 /// The frequency at which tick events are emitted.
 const TICK_FPS: f64 = 30.0;
 
@@ -37,6 +44,64 @@ pub enum AppEvent {
     Quit,
     NavRight,
     NavLeft,
+    /// Jump to the cluster-scoped Nodes column.
+    NavNodes,
+    /// Open the API kind picker for browsing any discovered resource.
+    NavKinds,
+    /// Open the storage view, toggling between claims and volumes when already there.
+    NavStorage,
+    /// Mark the selected node unschedulable.
+    Cordon,
+    /// Mark the selected node schedulable again.
+    Uncordon,
+    /// Start draining the node confirmed in the drain popup.
+    Drain,
+    /// Per-pod progress reported by a running drain.
+    DrainProgress(DrainProgress),
+    /// Open the delete popup for the selected pod, looking up its owner.
+    OpenDelete,
+    /// Delete the pod confirmed in the delete popup.
+    Delete,
+    /// A step of a running pod delete.
+    DeleteProgress(DeleteProgress),
+    /// The result so far for one item of a background operation shown in the progress popup.
+    OperationItem(ProgressItem),
+    /// A background operation shown in the progress popup has finished, with its error if any.
+    OperationDone(Option<String>),
+    /// Run the action picked in the bulk popup on the marked pods.
+    Bulk(BulkAction),
+    /// Fetch the object selected in the current view and open it in the YAML viewer.
+    OpenYaml,
+    /// Open the quota, limit range and disruption budget panel for the selected namespace.
+    NavPolicy,
+    /// Open the HorizontalPodAutoscalers in the current namespace.
+    NavHpas,
+    /// Apply the replica bounds confirmed in the HPA edit popup.
+    ApplyHpaEdit,
+    /// Open the scale popup for the selected workload.
+    OpenScale,
+    /// Apply the replica count confirmed in the scale popup.
+    Scale,
+    /// Persist the table sort and column preferences.
+    SaveTablePrefs,
+    /// Open the Events timeline for a namespace or a single object.
+    NavEvents(EventScope),
+    /// Start or stop watching the current Events scope.
+    ToggleEventWatch,
+    /// An Event added, modified or deleted while watching.
+    EventWatch(EventChange),
+    /// Open the pod selector prompt, loading the selector history.
+    OpenSelector,
+    /// Apply the label/field selector typed into the selector prompt.
+    ApplySelector,
+    /// Open the global finder over every cached namespace, pod and container.
+    OpenFinder,
+    /// Jump to the entry picked in the finder.
+    FinderJump,
+    /// Open the `:` command prompt, loading the command history and kubeconfig contexts.
+    OpenCommand,
+    /// Run the command typed into the command prompt.
+    RunCommand,
 }
 
 /// Terminal event handler.
@@ -79,6 +144,11 @@ impl EventHandler {
             .ok_or_eyre("Failed to receive event")
     }
 
+    /// Returns a sender that background tasks can use to queue events for the main loop.
+    pub fn sender(&self) -> mpsc::UnboundedSender<Event> {
+        self.sender.clone()
+    }
+
     /// Queue an app event to be sent to the event receiver.
     ///
     /// This is useful for sending events to the event handler which will be processed by the next
@@ -0,0 +1,184 @@
+/*
+ * Global fuzzy finder over every cached namespace, pod and container.
+ */
+
+use std::collections::HashMap;
+
+use nucleo_matcher::{
+    Config, Matcher, Utf32Str,
+    pattern::{CaseMatching, Normalization, Pattern},
+};
+use ratatui::{
+    crossterm::event::KeyEvent,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+};
+
+use crate::line_editor::{EditOutcome, LineEditor};
+
+/// Results shown at once; the popup keeps its height whatever the cache holds.
+const MAX_RESULTS: usize = 15;
+
+/// Weight of the log-scaled number of times an entry was jumped to, relative to match scores.
+const USAGE_WEIGHT: f64 = 24.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FinderEntry {
+    Namespace(String),
+    Pod {
+        namespace: String,
+        pod: String,
+    },
+    Container {
+        namespace: String,
+        pod: String,
+        container: String,
+    },
+}
+
+impl FinderEntry {
+    /// The path matched against, and the key usage is counted under.
+    pub fn key(&self) -> String {
+        match self {
+            FinderEntry::Namespace(namespace) => namespace.clone(),
+            FinderEntry::Pod { namespace, pod } => format!("{}/{}", namespace, pod),
+            FinderEntry::Container {
+                namespace,
+                pod,
+                container,
+            } => format!("{}/{}/{}", namespace, pod, container),
+        }
+    }
+
+    fn tag(&self) -> &'static str {
+        match self {
+            FinderEntry::Namespace(_) => "ns ",
+            FinderEntry::Pod { .. } => "pod",
+            FinderEntry::Container { .. } => "co ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Finder {
+    pub query: LineEditor,
+    pub entries: Vec<FinderEntry>,
+    /// How often each entry key has been jumped to.
+    pub usage: HashMap<String, u32>,
+    /// Indexes into `entries`, best match first.
+    pub results: Vec<usize>,
+    pub cursor: usize,
+}
+
+impl Finder {
+    pub fn new(entries: Vec<FinderEntry>, usage: HashMap<String, u32>) -> Self {
+        let mut finder = Self {
+            entries,
+            usage,
+            ..Self::default()
+        };
+        finder.update_results();
+        finder
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) {
+        if self.query.handle_key(key) == EditOutcome::Changed {
+            self.update_results();
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        self.query.insert_str(text);
+        self.update_results();
+    }
+
+    pub fn move_cursor(&mut self, down: bool) {
+        if down {
+            self.cursor = (self.cursor + 1).min(self.results.len().saturating_sub(1));
+        } else {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn selected(&self) -> Option<&FinderEntry> {
+        self.results.get(self.cursor).map(|&i| &self.entries[i])
+    }
+
+    // Rank by match score blended with how often an entry was picked before; ties go to the
+    // shorter path, which is usually the less nested entry.
+    fn update_results(&mut self) {
+        let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+        let pattern = Pattern::parse(
+            self.query.text(),
+            CaseMatching::Ignore,
+            Normalization::Smart,
+        );
+        let mut buf = Vec::new();
+
+        let mut scored: Vec<(usize, u32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let key = entry.key();
+                let score = pattern.score(Utf32Str::new(&key, &mut buf), &mut matcher)?;
+                let used = self.usage.get(&key).copied().unwrap_or_default();
+                let bonus = (USAGE_WEIGHT * (used as f64).ln_1p()) as u32;
+                Some((i, score + bonus, key.len()))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+
+        self.results = scored.into_iter().map(|(i, _, _)| i).collect();
+        self.cursor = 0;
+    }
+
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            {
+                let mut query = self.query.line(Style::new());
+                query.spans.insert(0, Span::from("> ").bold());
+                query
+            },
+            Line::from(""),
+        ];
+
+        // Scroll the results so the cursor stays in view.
+        let start = self.cursor.saturating_sub(MAX_RESULTS - 1);
+        for (i, &entry_index) in self
+            .results
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(MAX_RESULTS)
+        {
+            let entry = &self.entries[entry_index];
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{} {} ",
+                        if i == self.cursor { ">" } else { " " },
+                        entry.tag()
+                    ),
+                    Style::new().fg(Color::DarkGray),
+                ),
+                Span::styled(entry.key(), Style::new().fg(Color::Magenta)),
+            ]);
+            lines.push(if i == self.cursor { line.bold() } else { line });
+        }
+        if self.results.is_empty() {
+            lines.push(Line::from("  no matches").italic());
+        }
+
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(format!(
+                "{}/{}    [Up/Down] move    [Enter] jump    [Esc] close",
+                self.results.len(),
+                self.entries.len()
+            ))
+            .italic(),
+        );
+        lines
+    }
+}
@@ -0,0 +1,331 @@
+/*
+ * Subcommands run without the TUI, for scripting. `kuco ls` prints what the sync task persisted
+ * while it is younger than `--max-age`, and otherwise asks the API server and persists the
+ * answer for the next call. `kuco logs` always asks the API server, as logs are never cached.
+ * `kuco audit` prints the audit log from the persistent DB.
+ */
+
+use chrono::{Local, TimeZone, Utc};
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use k8s_openapi::api::core::v1::Pod;
+use kube::{Api, api::LogParams};
+use kuco_k8s_backend::{
+    age::format_duration_secs,
+    containers::ContainerData,
+    context::KubeContext,
+    namespaces::NamespaceData,
+    pods::{PodData, PodInfo, PodSelector},
+};
+use kuco_sqlite_backend::{AuditEntry, KucoSqliteStore, SqliteDb};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::audit::parse_filter;
+use crate::cli::{CacheArgs, Cli, HeadlessCommand, ListCommand, OutputFormat};
+use crate::constants::{
+    KUCO_CACHE_TABLE, PERSISTED_CONT_KEY, PERSISTED_NS_KEY, PERSISTED_PODS_KEY,
+};
+use crate::daemon;
+use crate::sync::persisted_key;
+
+/// Exit status when cached data older than `--max-age` was printed, because the API server
+/// could not be reached or `--cache-only` was given.
+pub const EXIT_STALE: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Source {
+    Cache,
+    Api,
+    Stale,
+}
+
+struct Headless {
+    kube_ctx: KubeContext,
+    db: SqliteDb,
+}
+
+/// Run `command` and return the exit status for it.
+pub async fn run(cli: &Cli, command: &HeadlessCommand) -> Result<i32> {
+    if let HeadlessCommand::Daemon { socket } = command {
+        return daemon::run(cli, socket.clone()).await;
+    }
+
+    let config = cli.load_config()?;
+    let db = SqliteDb::new(config.db_path()?, config.db.timeout_secs)
+        .await
+        .wrap_err("Sqlite cache init failed")?;
+
+    // The audit log is all in the persistent DB, so it does not need the cluster.
+    if let HeadlessCommand::Audit { filter, output } = command {
+        let filter = parse_filter(filter.as_deref().unwrap_or_default()).map_err(|e| eyre!(e))?;
+        let entries = db.get_audit(&filter).await?;
+        print_output(*output, &entries, || audit_table(&entries))?;
+        return Ok(0);
+    }
+
+    let headless = Headless {
+        kube_ctx: cli.kube_context().await?,
+        db,
+    };
+
+    match command {
+        HeadlessCommand::Ls(ListCommand::Namespaces { cache }) => {
+            let (names, source) = headless
+                .cached_or_fetched(PERSISTED_NS_KEY, cache, || async {
+                    let mut namespaces = NamespaceData::new();
+                    namespaces.update(headless.client()).await?;
+                    Ok(namespaces.names)
+                })
+                .await?;
+            print_output(cache.output, &names, || names_table("NAME", &names))?;
+            Ok(exit_status(source))
+        }
+        HeadlessCommand::Ls(ListCommand::Pods { namespace, cache }) => {
+            let (pods, source) = headless
+                .cached_or_fetched(PERSISTED_PODS_KEY, cache, || async {
+                    let mut pods = PodData::default();
+                    pods.update_list_all(headless.client(), &PodSelector::default())
+                        .await?;
+                    Ok(pods.list)
+                })
+                .await?;
+            let pods: Vec<PodInfo> = pods
+                .into_iter()
+                .filter(|p| namespace.as_ref().is_none_or(|ns| &p.namespace == ns))
+                .collect();
+            print_output(cache.output, &pods, || {
+                pods_table(&pods, namespace.is_none())
+            })?;
+            Ok(exit_status(source))
+        }
+        HeadlessCommand::Ls(ListCommand::Containers {
+            namespace,
+            pod,
+            cache,
+        }) => {
+            let key = format!("{}{}/{}", PERSISTED_CONT_KEY, namespace, pod);
+            let (names, source) = headless
+                .cached_or_fetched(&key, cache, || async {
+                    let mut containers = ContainerData::new();
+                    containers.update(headless.client(), namespace, pod).await?;
+                    Ok(containers.names)
+                })
+                .await?;
+            print_output(cache.output, &names, || names_table("NAME", &names))?;
+            Ok(exit_status(source))
+        }
+        HeadlessCommand::Logs {
+            namespace,
+            pod,
+            container,
+            tail,
+            output,
+        } => {
+            let pods: Api<Pod> = Api::namespaced(headless.client(), namespace);
+            let log_params = LogParams {
+                container: container.clone(),
+                timestamps: true,
+                tail_lines: Some(tail.unwrap_or(config.logs.tail_lines)),
+                ..Default::default()
+            };
+            let logs = pods
+                .logs(pod, &log_params)
+                .await
+                .wrap_err_with(|| format!("Failed to fetch logs of {}/{}", namespace, pod))?;
+            let lines: Vec<&str> = logs.lines().collect();
+            print_output(*output, &lines, || logs.trim_end().to_owned())?;
+            Ok(0)
+        }
+        HeadlessCommand::Audit { .. } | HeadlessCommand::Daemon { .. } => {
+            unreachable!("handled above")
+        }
+    }
+}
+
+impl Headless {
+    fn client(&self) -> kube::Client {
+        self.kube_ctx
+            .client
+            .clone()
+            .expect("Kube client not initialized")
+    }
+
+    /// The value persisted under `key` for the current context if it is fresh enough, and
+    /// otherwise the one `fetch` gets from the API server, which is persisted in turn. Stale
+    /// data is only returned when there is nothing better.
+    async fn cached_or_fetched<T, F, Fut>(
+        &self,
+        key: &str,
+        cache: &CacheArgs,
+        fetch: F,
+    ) -> Result<(T, Source)>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let key = persisted_key(self.kube_ctx.name.as_deref(), key);
+        let cached: Option<T> = self
+            .db
+            .get_json(KUCO_CACHE_TABLE.to_owned(), key.clone())
+            .await?;
+        let updated_at = self
+            .db
+            .get_updated_at(KUCO_CACHE_TABLE.to_owned(), key.clone())
+            .await?;
+        let age = updated_at.map(|at| Utc::now().timestamp() - at);
+
+        if age.is_some_and(|age| age <= cache.max_age) {
+            if let Some(value) = cached {
+                return Ok((value, Source::Cache));
+            }
+        }
+        if cache.cache_only {
+            return match cached {
+                Some(value) => Ok((value, Source::Stale)),
+                None => Err(eyre!("Nothing cached for '{}'", key)),
+            };
+        }
+
+        match fetch().await {
+            Ok(value) => {
+                self.db
+                    .set_json(KUCO_CACHE_TABLE.to_owned(), key, &value)
+                    .await?;
+                Ok((value, Source::Api))
+            }
+            Err(e) => match cached {
+                Some(value) => {
+                    eprintln!(
+                        "warning: {}; printing data cached {} ago",
+                        e,
+                        format_duration_secs(age.unwrap_or_default())
+                    );
+                    Ok((value, Source::Stale))
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+fn exit_status(source: Source) -> i32 {
+    match source {
+        Source::Cache | Source::Api => 0,
+        Source::Stale => EXIT_STALE,
+    }
+}
+
+fn print_output<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    value: &T,
+    table: impl FnOnce() -> String,
+) -> Result<()> {
+    let text = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value)?,
+        OutputFormat::Yaml => serde_yaml::to_string(value)?.trim_end().to_owned(),
+        OutputFormat::Table => table(),
+    };
+    if !text.is_empty() {
+        println!("{}", text);
+    }
+
+    Ok(())
+}
+
+fn names_table(header: &str, names: &[String]) -> String {
+    let rows = names.iter().map(|name| vec![name.clone()]).collect();
+    format_table(&[header], rows)
+}
+
+fn pods_table(pods: &[PodInfo], with_namespace: bool) -> String {
+    let now = Utc::now().timestamp();
+    let mut header = vec!["NAME", "READY", "STATUS", "RESTARTS", "AGE", "NODE", "IP"];
+    if with_namespace {
+        header.insert(0, "NAMESPACE");
+    }
+
+    let rows = pods
+        .iter()
+        .map(|pod| {
+            let mut row = vec![
+                pod.name.clone(),
+                format!("{}/{}", pod.ready_containers, pod.total_containers),
+                pod.status.clone(),
+                pod.restarts.to_string(),
+                pod.created_at
+                    .map(|created| format_duration_secs(now - created))
+                    .unwrap_or_default(),
+                pod.node.clone(),
+                pod.ip.clone(),
+            ];
+            if with_namespace {
+                row.insert(0, pod.namespace.clone());
+            }
+            row
+        })
+        .collect();
+
+    format_table(&header, rows)
+}
+
+fn audit_table(entries: &[AuditEntry]) -> String {
+    let header = [
+        "ID",
+        "TIME",
+        "CONTEXT",
+        "NAMESPACE",
+        "KIND",
+        "NAME",
+        "ACTION",
+        "PARAMETERS",
+        "OUTCOME",
+    ];
+
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.id.to_string(),
+                Local
+                    .timestamp_opt(entry.at, 0)
+                    .single()
+                    .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default(),
+                entry.context.clone(),
+                entry.namespace.clone(),
+                entry.kind.clone(),
+                entry.name.clone(),
+                entry.action.clone(),
+                entry.parameters.clone(),
+                entry.outcome.clone(),
+            ]
+        })
+        .collect();
+
+    format_table(&header, rows)
+}
+
+// kubectl style: left aligned columns, three spaces apart.
+fn format_table(header: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header_row: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    std::iter::once(header_row)
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("   ")
+                .trim_end()
+                .to_owned()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,817 @@
+/*
+ * Key bindings: maps key sequences to actions per interaction mode, and per view in NORMAL
+ * mode, with the defaults below overridable from the `[keys]` tables of the config file.
+ *
+ * A binding is written as whitespace separated keys. A key is a named key (`esc`, `enter`,
+ * `tab`, `up`, `f1` ...) or a char, optionally prefixed with `ctrl-`, `alt-` or `shift-`.
+ * A run of plain chars is one key per char, so `gg` is `g` twice while `esc` is Escape.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+
+use color_eyre::eyre::{Result, eyre};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::{InteractionMode, ViewMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Refresh,
+    OpenFinder,
+    Search,
+    Command,
+    // List selection. The lists are drawn bottom to top, so `Up` moves to the next entry.
+    Up,
+    Down,
+    Top,
+    Bottom,
+    NavRight,
+    NavLeft,
+    // Views
+    Nodes,
+    Kinds,
+    Storage,
+    Hpas,
+    Events,
+    ObjectEvents,
+    Policy,
+    ViewYaml,
+    // View specific
+    Selector,
+    EditHpa,
+    Scale,
+    SortNext,
+    SortPrev,
+    Columns,
+    EventTypeFilter,
+    EventReasonFilter,
+    LiveEvents,
+    Cordon,
+    Uncordon,
+    Drain,
+    Delete,
+    // Marks & bulk actions
+    ToggleMark,
+    MarkRange,
+    MarkAll,
+    BulkActions,
+    // Prompts
+    Cancel,
+    Submit,
+    Complete,
+    MatchMode,
+}
+
+impl Action {
+    /// Whether the action changes something in the cluster.
+    pub fn mutates(&self) -> bool {
+        matches!(
+            self,
+            Action::EditHpa
+                | Action::Scale
+                | Action::Cordon
+                | Action::Uncordon
+                | Action::Drain
+                | Action::Delete
+        )
+    }
+}
+
+const ACTIONS: [(Action, &str); 40] = [
+    (Action::Quit, "quit"),
+    (Action::Refresh, "refresh"),
+    (Action::OpenFinder, "open_finder"),
+    (Action::Search, "search"),
+    (Action::Command, "command"),
+    (Action::Up, "up"),
+    (Action::Down, "down"),
+    (Action::Top, "top"),
+    (Action::Bottom, "bottom"),
+    (Action::NavRight, "nav_right"),
+    (Action::NavLeft, "nav_left"),
+    (Action::Nodes, "nodes"),
+    (Action::Kinds, "kinds"),
+    (Action::Storage, "storage"),
+    (Action::Hpas, "hpas"),
+    (Action::Events, "events"),
+    (Action::ObjectEvents, "object_events"),
+    (Action::Policy, "policy"),
+    (Action::ViewYaml, "view_yaml"),
+    (Action::Selector, "selector"),
+    (Action::EditHpa, "edit_hpa"),
+    (Action::Scale, "scale"),
+    (Action::SortNext, "sort_next"),
+    (Action::SortPrev, "sort_prev"),
+    (Action::Columns, "columns"),
+    (Action::EventTypeFilter, "event_type_filter"),
+    (Action::EventReasonFilter, "event_reason_filter"),
+    (Action::LiveEvents, "live_events"),
+    (Action::Cordon, "cordon"),
+    (Action::Uncordon, "uncordon"),
+    (Action::Drain, "drain"),
+    (Action::Delete, "delete"),
+    (Action::ToggleMark, "toggle_mark"),
+    (Action::MarkRange, "mark_range"),
+    (Action::MarkAll, "mark_all"),
+    (Action::BulkActions, "bulk_actions"),
+    (Action::Cancel, "cancel"),
+    (Action::Submit, "submit"),
+    (Action::Complete, "complete"),
+    (Action::MatchMode, "match_mode"),
+];
+
+/// Unbinds whatever the keys were bound to, e.g. to free them for another binding.
+const UNBIND: &str = "none";
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(action, _)| action == self)
+            .map(|(_, name)| *name)
+            .unwrap_or_default()
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(action, _)| *action)
+    }
+}
+
+/// One key press, normalised so that a binding and the event it matches compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already in the case of a char (and in BackTab), and terminals disagree on
+        // reporting it.
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, prefix) in [
+            (KeyModifiers::CONTROL, "ctrl-"),
+            (KeyModifiers::ALT, "alt-"),
+            (KeyModifiers::SHIFT, "shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", prefix)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|(_, c)| *c == code)
+                    .map(|(name, _)| *name)
+                    .unwrap_or("?");
+                write!(f, "{}", name)
+            }
+        }
+    }
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("esc", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+fn parse_key(token: &str) -> Option<Vec<KeyChord>> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = token;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let (modifier, len) = if lower.starts_with("ctrl-") {
+            (KeyModifiers::CONTROL, 5)
+        } else if lower.starts_with("alt-") {
+            (KeyModifiers::ALT, 4)
+        } else if lower.starts_with("shift-") {
+            (KeyModifiers::SHIFT, 6)
+        } else {
+            break;
+        };
+        // A lone `-` after a modifier is the key itself, as in `ctrl--`.
+        if rest.len() == len {
+            break;
+        }
+        modifiers |= modifier;
+        rest = &rest[len..];
+    }
+
+    let lower = rest.to_ascii_lowercase();
+    if let Some((_, code)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+        return Some(vec![KeyChord::new(*code, modifiers)]);
+    }
+    if lower == "space" {
+        return Some(vec![KeyChord::new(KeyCode::Char(' '), modifiers)]);
+    }
+    if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return Some(vec![KeyChord::new(KeyCode::F(n), modifiers)]);
+    }
+
+    let chars: Vec<char> = rest.chars().collect();
+    match chars.as_slice() {
+        [] => None,
+        [c] => Some(vec![KeyChord::new(KeyCode::Char(*c), modifiers)]),
+        // Modifiers only apply to a single key.
+        _ if modifiers != KeyModifiers::NONE => None,
+        _ => Some(
+            chars
+                .into_iter()
+                .map(|c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .collect(),
+        ),
+    }
+}
+
+pub fn parse_sequence(keys: &str) -> Option<Vec<KeyChord>> {
+    let mut sequence = Vec::new();
+    for token in keys.split_whitespace() {
+        sequence.extend(parse_key(token)?);
+    }
+    if sequence.is_empty() {
+        return None;
+    }
+
+    Some(sequence)
+}
+
+fn format_sequence(sequence: &[KeyChord]) -> String {
+    let keys: Vec<String> = sequence.iter().map(|chord| chord.to_string()).collect();
+    keys.join(" ")
+}
+
+/// Where a binding applies: everywhere in an interaction mode, or in one view in NORMAL mode,
+/// where it takes precedence over the NORMAL bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Scope {
+    Mode(InteractionMode),
+    View(ViewMode),
+}
+
+const MODES: [InteractionMode; 4] = [
+    InteractionMode::NORMAL,
+    InteractionMode::SEARCH,
+    InteractionMode::SELECTOR,
+    InteractionMode::COMMAND,
+];
+
+const VIEW_MODES: [ViewMode; 15] = [
+    ViewMode::NS,
+    ViewMode::PODS,
+    ViewMode::CONT,
+    ViewMode::LOGS,
+    ViewMode::NODES,
+    ViewMode::NODEPODS,
+    ViewMode::ALLPODS,
+    ViewMode::KINDS,
+    ViewMode::RESOURCES,
+    ViewMode::PVCS,
+    ViewMode::PVS,
+    ViewMode::EVENTS,
+    ViewMode::HPAS,
+    ViewMode::POLICY,
+    ViewMode::AUDIT,
+];
+
+impl Scope {
+    fn name(&self) -> String {
+        match self {
+            Scope::Mode(mode) => format!("{:?}", mode).to_lowercase(),
+            Scope::View(view) => format!("{:?}", view).to_lowercase(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let modes = MODES.iter().cloned().map(Scope::Mode);
+        let views = VIEW_MODES.iter().cloned().map(Scope::View);
+        modes.chain(views).find(|scope| scope.name() == name)
+    }
+}
+
+const DEFAULT_BINDINGS: [(&str, &str, Action); 68] = [
+    // NORMAL
+    ("normal", "esc", Action::Quit),
+    ("normal", "q", Action::Quit),
+    ("normal", "ctrl-c", Action::Quit),
+    ("normal", "ctrl-p", Action::OpenFinder),
+    ("normal", "r", Action::Refresh),
+    ("normal", "/", Action::Search),
+    ("normal", ":", Action::Command),
+    ("normal", "N", Action::Nodes),
+    ("normal", "A", Action::Kinds),
+    ("normal", "V", Action::Storage),
+    ("normal", "H", Action::Hpas),
+    ("normal", "E", Action::Events),
+    ("normal", "e", Action::ObjectEvents),
+    ("normal", "o", Action::SortNext),
+    ("normal", "O", Action::SortPrev),
+    ("normal", "C", Action::Columns),
+    ("normal", "y", Action::ViewYaml),
+    ("normal", "right", Action::NavRight),
+    ("normal", "l", Action::NavRight),
+    ("normal", "enter", Action::NavRight),
+    ("normal", "left", Action::NavLeft),
+    ("normal", "h", Action::NavLeft),
+    ("normal", "up", Action::Up),
+    ("normal", "k", Action::Up),
+    ("normal", "down", Action::Down),
+    ("normal", "j", Action::Down),
+    ("normal", "gg", Action::Top),
+    ("normal", "G", Action::Bottom),
+    // Views
+    ("ns", "p", Action::Policy),
+    ("pods", "S", Action::Selector),
+    ("allpods", "S", Action::Selector),
+    ("hpas", "i", Action::EditHpa),
+    ("resources", "s", Action::Scale),
+    ("events", "t", Action::EventTypeFilter),
+    ("events", "f", Action::EventReasonFilter),
+    ("events", "L", Action::LiveEvents),
+    ("nodes", "c", Action::Cordon),
+    ("nodes", "u", Action::Uncordon),
+    ("nodes", "d", Action::Drain),
+    ("pods", "ctrl-d", Action::Delete),
+    ("allpods", "ctrl-d", Action::Delete),
+    ("nodepods", "ctrl-d", Action::Delete),
+    ("pods", "space", Action::ToggleMark),
+    ("allpods", "space", Action::ToggleMark),
+    ("nodepods", "space", Action::ToggleMark),
+    ("pods", "M", Action::MarkRange),
+    ("allpods", "M", Action::MarkRange),
+    ("nodepods", "M", Action::MarkRange),
+    ("pods", "ctrl-a", Action::MarkAll),
+    ("allpods", "ctrl-a", Action::MarkAll),
+    ("nodepods", "ctrl-a", Action::MarkAll),
+    ("pods", "b", Action::BulkActions),
+    ("allpods", "b", Action::BulkActions),
+    ("nodepods", "b", Action::BulkActions),
+    // SEARCH
+    ("search", "esc", Action::Cancel),
+    ("search", "ctrl-c", Action::Quit),
+    ("search", "enter", Action::Submit),
+    ("search", "ctrl-p", Action::Up),
+    ("search", "ctrl-n", Action::Down),
+    ("search", "tab", Action::MatchMode),
+    // SELECTOR
+    ("selector", "esc", Action::Cancel),
+    ("selector", "ctrl-c", Action::Quit),
+    ("selector", "enter", Action::Submit),
+    ("selector", "tab", Action::Complete),
+    // COMMAND
+    ("command", "esc", Action::Cancel),
+    ("command", "ctrl-c", Action::Quit),
+    ("command", "enter", Action::Submit),
+    ("command", "tab", Action::Complete),
+];
+
+/// What a key press amounted to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyResolution {
+    Action(Action),
+    /// The keys so far start a longer binding; wait for the next one.
+    Pending,
+    /// Not bound, so prompts hand the key to their line editor.
+    Unbound,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// `None` marks keys unbound in a view although the NORMAL scope binds them.
+    bindings: HashMap<Scope, HashMap<Vec<KeyChord>, Option<Action>>>,
+    pending: Vec<KeyChord>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings: HashMap<Scope, HashMap<Vec<KeyChord>, Option<Action>>> = HashMap::new();
+        for (scope, keys, action) in DEFAULT_BINDINGS {
+            let scope = Scope::from_name(scope).expect("default binding scope");
+            let sequence = parse_sequence(keys).expect("default binding keys");
+            bindings
+                .entry(scope)
+                .or_default()
+                .insert(sequence, Some(action));
+        }
+
+        Self {
+            bindings,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Keymap {
+    /// The default bindings with `overrides` (scope -> keys -> action name) applied. Unknown
+    /// scopes, keys or actions and conflicting bindings are all reported together.
+    pub fn new(overrides: &HashMap<String, HashMap<String, String>>) -> Result<Self> {
+        let mut keymap = Self::default();
+        let mut errors: Vec<String> = Vec::new();
+
+        let mut scopes: Vec<&String> = overrides.keys().collect();
+        scopes.sort();
+        for scope_name in scopes {
+            let Some(scope) = Scope::from_name(scope_name) else {
+                errors.push(format!("unknown key binding scope [keys.{}]", scope_name));
+                continue;
+            };
+
+            let mut assigned: HashMap<Vec<KeyChord>, &str> = HashMap::new();
+            let mut entries: Vec<(&String, &String)> = overrides[scope_name].iter().collect();
+            entries.sort();
+            for (keys, action_name) in entries {
+                let Some(sequence) = parse_sequence(keys) else {
+                    errors.push(format!("[keys.{}]: invalid keys '{}'", scope_name, keys));
+                    continue;
+                };
+                let action = match action_name.as_str() {
+                    UNBIND => None,
+                    name => match Action::from_name(name) {
+                        Some(action) => Some(action),
+                        None => {
+                            errors.push(format!(
+                                "[keys.{}]: unknown action '{}' for '{}'",
+                                scope_name, name, keys
+                            ));
+                            continue;
+                        }
+                    },
+                };
+
+                // Different spellings of the same keys, like `ctrl-P` and `ctrl-p`.
+                if let Some(previous) = assigned.insert(sequence.clone(), keys) {
+                    errors.push(format!(
+                        "[keys.{}]: '{}' and '{}' are the same keys",
+                        scope_name, previous, keys
+                    ));
+                    continue;
+                }
+                keymap
+                    .bindings
+                    .entry(scope.clone())
+                    .or_default()
+                    .insert(sequence, action);
+            }
+        }
+
+        errors.extend(keymap.conflicts());
+        if !errors.is_empty() {
+            return Err(eyre!("invalid key bindings:\n  {}", errors.join("\n  ")));
+        }
+
+        Ok(keymap)
+    }
+
+    /// The bindings in effect for a mode and view, with view bindings over the NORMAL ones.
+    fn effective(&self, mode: &InteractionMode, view: &ViewMode) -> HashMap<&[KeyChord], Action> {
+        let mut scopes = vec![Scope::Mode(mode.clone())];
+        if *mode == InteractionMode::NORMAL {
+            scopes.push(Scope::View(view.clone()));
+        }
+        self.merged(&scopes)
+    }
+
+    /// The bindings of `scopes`, later scopes taking precedence.
+    fn merged(&self, scopes: &[Scope]) -> HashMap<&[KeyChord], Action> {
+        let mut merged: HashMap<&[KeyChord], Option<Action>> = HashMap::new();
+        for scope in scopes {
+            if let Some(bindings) = self.bindings.get(scope) {
+                for (sequence, action) in bindings {
+                    merged.insert(sequence.as_slice(), *action);
+                }
+            }
+        }
+
+        merged
+            .into_iter()
+            .filter_map(|(sequence, action)| Some((sequence, action?)))
+            .collect()
+    }
+
+    // A binding that is a prefix of another would always fire before the longer one could.
+    // Conflicts within the NORMAL scope itself are reported once rather than for every view.
+    fn conflicts(&self) -> Vec<String> {
+        let normal = Scope::Mode(InteractionMode::NORMAL);
+        let mut contexts: Vec<(String, Vec<Scope>)> = MODES
+            .iter()
+            .map(|mode| {
+                (
+                    Scope::Mode(mode.clone()).name(),
+                    vec![Scope::Mode(mode.clone())],
+                )
+            })
+            .collect();
+        for view in VIEW_MODES {
+            let scope = Scope::View(view);
+            contexts.push((scope.name(), vec![normal.clone(), scope]));
+        }
+
+        let mut reported: Vec<String> = Vec::new();
+        let mut conflicts: Vec<String> = Vec::new();
+        for (scope_name, scopes) in contexts {
+            let bindings = self.merged(&scopes);
+            let mut sequences: Vec<&[KeyChord]> = bindings.keys().copied().collect();
+            sequences.sort_by_key(|sequence| format_sequence(sequence));
+            for short in &sequences {
+                for long in &sequences {
+                    if long.len() <= short.len() || !long.starts_with(short) {
+                        continue;
+                    }
+                    let conflict = format!(
+                        "'{}' ({}) hides '{}' ({})",
+                        format_sequence(short),
+                        bindings[short].name(),
+                        format_sequence(long),
+                        bindings[long].name(),
+                    );
+                    if !reported.contains(&conflict) {
+                        conflicts.push(format!("in {}: {}", scope_name, conflict));
+                        reported.push(conflict);
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Feed a key press, keeping it if it starts a longer binding. Keys that lead nowhere
+    /// are dropped, and the last of them is tried on its own.
+    pub fn resolve(
+        &mut self,
+        mode: &InteractionMode,
+        view: &ViewMode,
+        key: &KeyEvent,
+    ) -> KeyResolution {
+        let chord = KeyChord::from_event(key);
+        self.pending.push(chord);
+        let continued = self.pending.len() > 1;
+
+        let resolution = self.resolve_pending(mode, view);
+        if resolution == KeyResolution::Unbound && continued {
+            self.pending = vec![chord];
+            return self.resolve_pending(mode, view);
+        }
+        resolution
+    }
+
+    fn resolve_pending(&mut self, mode: &InteractionMode, view: &ViewMode) -> KeyResolution {
+        let effective = self.effective(mode, view);
+        if let Some(action) = effective.get(self.pending.as_slice()).copied() {
+            self.pending.clear();
+            return KeyResolution::Action(action);
+        }
+        if effective
+            .keys()
+            .any(|sequence| sequence.starts_with(&self.pending))
+        {
+            return KeyResolution::Pending;
+        }
+
+        self.pending.clear();
+        KeyResolution::Unbound
+    }
+
+    /// Drop a half typed sequence, e.g. when the mode changes under it.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    /// The keys typed so far towards a longer binding.
+    pub fn pending(&self) -> String {
+        format_sequence(&self.pending)
+    }
+
+    /// The first keys bound to `action` in NORMAL mode for `view`, for help text.
+    pub fn keys_for(&self, view: &ViewMode, action: Action) -> Option<String> {
+        let effective = self.effective(&InteractionMode::NORMAL, view);
+        let mut keys: Vec<String> = effective
+            .into_iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(sequence, _)| format_sequence(sequence))
+            .collect();
+        keys.sort_by_key(|k| (k.len(), k.clone()));
+        keys.into_iter().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord::new(code, modifiers)
+    }
+
+    fn press(keymap: &mut Keymap, view: ViewMode, code: KeyCode) -> KeyResolution {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        keymap.resolve(&InteractionMode::NORMAL, &view, &key)
+    }
+
+    fn overrides(entries: &[(&str, &str, &str)]) -> HashMap<String, HashMap<String, String>> {
+        let mut overrides: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for (scope, keys, action) in entries {
+            overrides
+                .entry(scope.to_string())
+                .or_default()
+                .insert(keys.to_string(), action.to_string());
+        }
+        overrides
+    }
+
+    #[test]
+    fn parses_modified_keys() {
+        let ctrl_d = vec![chord(KeyCode::Char('d'), KeyModifiers::CONTROL)];
+        assert_eq!(parse_sequence("ctrl-d"), Some(ctrl_d.clone()));
+        assert_eq!(parse_sequence("CTRL-D"), Some(ctrl_d.clone()));
+        // Terminals report ctrl with shift held as either case.
+        let pressed = KeyEvent::new(
+            KeyCode::Char('D'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+        assert_eq!(vec![KeyChord::from_event(&pressed)], ctrl_d);
+        assert_eq!(
+            parse_sequence("ctrl--"),
+            Some(vec![chord(KeyCode::Char('-'), KeyModifiers::CONTROL)])
+        );
+    }
+
+    #[test]
+    fn parses_uppercase_as_shifted_char() {
+        let shifted_m =
+            KeyChord::from_event(&KeyEvent::new(KeyCode::Char('m'), KeyModifiers::SHIFT));
+        assert_eq!(parse_sequence("M"), Some(vec![shifted_m]));
+        assert_eq!(parse_sequence("shift-m"), Some(vec![shifted_m]));
+        assert_ne!(parse_sequence("m"), Some(vec![shifted_m]));
+    }
+
+    #[test]
+    fn parses_named_keys_and_runs_of_chars() {
+        assert_eq!(
+            parse_sequence("space"),
+            Some(vec![chord(KeyCode::Char(' '), KeyModifiers::NONE)])
+        );
+        assert_eq!(
+            parse_sequence("f5"),
+            Some(vec![chord(KeyCode::F(5), KeyModifiers::NONE)])
+        );
+        assert_eq!(parse_sequence("gg"), parse_sequence("g g"));
+        assert_eq!(
+            parse_sequence("ctrl-d space gg").map(|sequence| format_sequence(&sequence)),
+            Some("ctrl-d space g g".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert_eq!(parse_sequence(""), None);
+        assert_eq!(parse_sequence("   "), None);
+        assert_eq!(parse_sequence("ctrl-ab"), None);
+        assert_eq!(parse_sequence("g ctrl-xy"), None);
+    }
+
+    #[test]
+    fn overrides_merge_over_the_defaults() {
+        let mut keymap = Keymap::new(&overrides(&[
+            ("pods", "x", "delete"),
+            ("pods", "b", UNBIND),
+            ("pods", "q", "refresh"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('x')),
+            KeyResolution::Action(Action::Delete)
+        );
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('b')),
+            KeyResolution::Unbound
+        );
+        // View bindings only take over in their own view.
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('q')),
+            KeyResolution::Action(Action::Refresh)
+        );
+        assert_eq!(
+            press(&mut keymap, ViewMode::NS, KeyCode::Char('q')),
+            KeyResolution::Action(Action::Quit)
+        );
+        assert_eq!(
+            press(&mut keymap, ViewMode::ALLPODS, KeyCode::Char('b')),
+            KeyResolution::Action(Action::BulkActions)
+        );
+        assert_eq!(
+            keymap.keys_for(&ViewMode::PODS, Action::Delete),
+            Some("x".to_owned())
+        );
+    }
+
+    #[test]
+    fn resolves_sequences_through_pending() {
+        let mut keymap = Keymap::default();
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('g')),
+            KeyResolution::Pending
+        );
+        assert_eq!(keymap.pending(), "g");
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('g')),
+            KeyResolution::Action(Action::Top)
+        );
+
+        // A key that leads nowhere is tried on its own.
+        press(&mut keymap, ViewMode::PODS, KeyCode::Char('g'));
+        assert_eq!(
+            press(&mut keymap, ViewMode::PODS, KeyCode::Char('q')),
+            KeyResolution::Action(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn reports_every_invalid_override() {
+        let error = Keymap::new(&overrides(&[
+            ("nowhere", "x", "quit"),
+            ("pods", "x", "explode"),
+            ("pods", "ctrl-xy", "quit"),
+            ("normal", "ctrl-P", "refresh"),
+            ("normal", "ctrl-p", "refresh"),
+        ]))
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("unknown key binding scope [keys.nowhere]"));
+        assert!(error.contains("[keys.pods]: unknown action 'explode' for 'x'"));
+        assert!(error.contains("[keys.pods]: invalid keys 'ctrl-xy'"));
+        assert!(error.contains("[keys.normal]: 'ctrl-P' and 'ctrl-p' are the same keys"));
+    }
+
+    #[test]
+    fn reports_prefix_conflicts() {
+        let error = Keymap::new(&overrides(&[("normal", "g", "refresh")]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("in normal: 'g' (refresh) hides 'g g' (top)"));
+        // Reported once for NORMAL rather than again for every view.
+        assert_eq!(error.matches("hides 'g g'").count(), 1);
+
+        let error = Keymap::new(&overrides(&[("pods", "b b", "delete")]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("in pods: 'b' (bulk_actions) hides 'b b' (delete)"));
+
+        assert!(
+            Keymap::new(&overrides(&[
+                ("pods", "b", UNBIND),
+                ("pods", "b b", "delete")
+            ]))
+            .is_ok()
+        );
+    }
+}
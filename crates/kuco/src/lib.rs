@@ -1,8 +1,22 @@
 pub mod app;
+pub mod audit;
+pub mod cli;
+pub mod command;
+pub mod config;
 pub mod constants;
+pub mod daemon;
 pub mod data;
 pub mod draw;
 pub mod event;
+pub mod finder;
+pub mod headless;
+pub mod keymap;
+pub mod line_editor;
+pub mod popup;
+pub mod prompt;
+pub mod search;
 pub mod sync;
+pub mod tables;
 pub mod tracing;
 pub mod view;
+pub mod yaml;
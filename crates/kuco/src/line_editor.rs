@@ -0,0 +1,248 @@
+/*
+ * Single line text editor shared by every prompt (search, selectors, the finder ...).
+ *
+ * The cursor is kept as a char index, so multi-byte input never splits a character.
+ */
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Entries kept in an editor's in-memory history.
+const HISTORY_LIMIT: usize = 50;
+
+/// What a key did to the editor, so callers only re-run searches when the text changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditOutcome {
+    Changed,
+    Moved,
+    Ignored,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LineEditor {
+    text: String,
+    /// Position of the cursor, in chars.
+    cursor: usize,
+    /// Previous entries, most recent first.
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// What was typed before browsing the history, restored when stepping past the newest entry.
+    draft: String,
+}
+
+impl LineEditor {
+    pub fn new(text: String, history: Vec<String>) -> Self {
+        let mut editor = Self {
+            history,
+            ..Self::default()
+        };
+        editor.set_text(text);
+        editor
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Replace the text, leaving the cursor at the end.
+    pub fn set_text(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.text = text;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text(String::new());
+        self.history_index = None;
+    }
+
+    /// Remember the current text as the most recent history entry.
+    pub fn push_history(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        self.history.retain(|entry| entry != &self.text);
+        self.history.insert(0, self.text.clone());
+        self.history.truncate(HISTORY_LIMIT);
+        self.history_index = None;
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.byte_index(self.cursor);
+        self.text.insert(at, c);
+        self.cursor += 1;
+    }
+
+    /// Insert pasted text at the cursor. Line breaks become spaces and other control
+    /// characters are dropped, since prompts are a single line.
+    pub fn insert_str(&mut self, s: &str) {
+        for c in s.chars() {
+            match c {
+                '\n' | '\r' | '\t' => self.insert(' '),
+                c if c.is_control() => {}
+                c => self.insert(c),
+            }
+        }
+    }
+
+    /// Remove the chars in `start..end` (char indices) and put the cursor at `start`.
+    fn remove_range(&mut self, start: usize, end: usize) -> EditOutcome {
+        if start >= end {
+            return EditOutcome::Ignored;
+        }
+        let (from, to) = (self.byte_index(start), self.byte_index(end));
+        self.text.replace_range(from..to, "");
+        self.cursor = start;
+        EditOutcome::Changed
+    }
+
+    fn move_to(&mut self, cursor: usize) -> EditOutcome {
+        let cursor = cursor.min(self.len());
+        if cursor == self.cursor {
+            return EditOutcome::Ignored;
+        }
+        self.cursor = cursor;
+        EditOutcome::Moved
+    }
+
+    /// Start of the word before the cursor, skipping any separators right before it.
+    fn word_start(&self) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut i = self.cursor;
+        while i > 0 && !is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+        while i > 0 && is_word_char(chars[i - 1]) {
+            i -= 1;
+        }
+        i
+    }
+
+    /// End of the word after the cursor, skipping any separators right after it.
+    fn word_end(&self) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut i = self.cursor;
+        while i < chars.len() && !is_word_char(chars[i]) {
+            i += 1;
+        }
+        while i < chars.len() && is_word_char(chars[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    /// Step back (`older`) or forward through the history, replacing the text.
+    pub fn recall(&mut self, older: bool) -> EditOutcome {
+        if self.history.is_empty() {
+            return EditOutcome::Ignored;
+        }
+
+        let index = match (self.history_index, older) {
+            (None, true) => {
+                self.draft = self.text.clone();
+                0
+            }
+            (None, false) => return EditOutcome::Ignored,
+            (Some(i), true) => (i + 1).min(self.history.len() - 1),
+            (Some(0), false) => {
+                self.history_index = None;
+                let draft = std::mem::take(&mut self.draft);
+                self.set_text(draft);
+                return EditOutcome::Changed;
+            }
+            (Some(i), false) => i - 1,
+        };
+        self.history_index = Some(index);
+        self.set_text(self.history[index].clone());
+        EditOutcome::Changed
+    }
+
+    /// Apply an editing key. Keys the editor has no use for are left to the caller.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> EditOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        let outcome = match key.code {
+            KeyCode::Char('a') if ctrl => self.move_to(0),
+            KeyCode::Char('e') if ctrl => self.move_to(self.len()),
+            KeyCode::Char('b') if ctrl => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Char('f') if ctrl => self.move_to(self.cursor + 1),
+            KeyCode::Char('b') if alt => self.move_to(self.word_start()),
+            KeyCode::Char('f') if alt => self.move_to(self.word_end()),
+            KeyCode::Char('h') if ctrl => {
+                self.remove_range(self.cursor.saturating_sub(1), self.cursor)
+            }
+            KeyCode::Char('d') if ctrl => {
+                self.remove_range(self.cursor, (self.cursor + 1).min(self.len()))
+            }
+            KeyCode::Char('w') if ctrl => self.remove_range(self.word_start(), self.cursor),
+            KeyCode::Char('u') if ctrl => self.remove_range(0, self.cursor),
+            KeyCode::Char('k') if ctrl => self.remove_range(self.cursor, self.len()),
+            KeyCode::Char(_) if ctrl || alt => EditOutcome::Ignored,
+            KeyCode::Char(c) => {
+                self.insert(c);
+                EditOutcome::Changed
+            }
+            KeyCode::Backspace if alt || ctrl => self.remove_range(self.word_start(), self.cursor),
+            KeyCode::Backspace => self.remove_range(self.cursor.saturating_sub(1), self.cursor),
+            KeyCode::Delete => self.remove_range(self.cursor, (self.cursor + 1).min(self.len())),
+            KeyCode::Left if ctrl => self.move_to(self.word_start()),
+            KeyCode::Right if ctrl => self.move_to(self.word_end()),
+            KeyCode::Left => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Right => self.move_to(self.cursor + 1),
+            KeyCode::Home => self.move_to(0),
+            KeyCode::End => self.move_to(self.len()),
+            KeyCode::Up => self.recall(true),
+            KeyCode::Down => self.recall(false),
+            _ => EditOutcome::Ignored,
+        };
+
+        // Editing a recalled entry makes it a new draft rather than part of the history.
+        if outcome == EditOutcome::Changed && !matches!(key.code, KeyCode::Up | KeyCode::Down) {
+            self.history_index = None;
+        }
+        outcome
+    }
+
+    /// Render the text with the cursor drawn as a reversed cell.
+    pub fn line(&self, style: Style) -> Line<'static> {
+        let cursor_style = style.add_modifier(Modifier::REVERSED);
+        let before: String = self.text.chars().take(self.cursor).collect();
+        let at: String = self
+            .text
+            .chars()
+            .nth(self.cursor)
+            .map(String::from)
+            .unwrap_or_else(|| " ".to_owned());
+        let after: String = self.text.chars().skip(self.cursor + 1).collect();
+
+        Line::from(vec![
+            Span::styled(before, style),
+            Span::styled(at, cursor_style),
+            Span::styled(after, style),
+        ])
+    }
+}
+
+/// Separators in selectors and paths end a word, as well as whitespace.
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, ',' | '=' | '/' | '!' | '(' | ')' | '.')
+}
@@ -1,45 +1,57 @@
-use kuco::{app::Kuco, sync::periodic_multistage_cache_sync};
+use kuco::{
+    app::Kuco,
+    cli::{Cli, HeadlessCommand},
+    daemon::sync_from_daemon_or_cluster,
+    headless,
+    keymap::Keymap,
+    sync::sync_once,
+};
 use kuco::tracing::init_tracing;
 
-use kuco_k8s_backend::context::KubeContext;
 use kuco_sqlite_backend::{SqliteCache, SqliteDb};
 
-use color_eyre::eyre::{Result, WrapErr, eyre};
-use std::{path::PathBuf, sync::Arc};
-
-fn get_user_home() -> Option<PathBuf> {
-    dirs_next::home_dir()
-}
+use clap::Parser;
+use color_eyre::eyre::{Result, WrapErr};
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use std::{io::stdout, sync::Arc};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Init Tracing
+    let cli = Cli::parse();
     color_eyre::install()?;
-    let _guard = init_tracing()?;
+
+    // Headless Subcommands
+    if let Some(command) = &cli.command {
+        // Only the daemon runs long enough to need a log file.
+        let guard = match command {
+            HeadlessCommand::Daemon { .. } => Some(init_tracing(&cli.log_file)?),
+            _ => None,
+        };
+        let status = headless::run(&cli, command).await?;
+        drop(guard);
+        std::process::exit(status);
+    }
+
+    // Init Tracing
+    let _guard = init_tracing(&cli.log_file)?;
+
+    // Load User Config
+    let config = cli.load_config()?;
+    let keymap = Keymap::new(&config.keys)?;
 
     // Setup Data Persistence Arguments
-    let db_connection_timeout: f64 = 30.0;
-    let db_path: PathBuf = match get_user_home() {
-        Some(home_path) => {
-            let path = home_path.join(".kuco").join("user_kube_data.db");
-            tracing::info!("Database path will be: {}", home_path.display());
-
-            path
-        }
-        None => {
-            let error_message = "Critical Error: Could not determine the home directory. Application cannot continue.";
-            tracing::error!("{}", error_message);
-
-            return Err(eyre!(error_message));
-        }
-    };
+    let db_path = config.db_path()?;
+    tracing::info!("Database path will be: {}", db_path.display());
 
     // Create KubeContext
-    let mut kube_context = KubeContext::default();
-    kube_context.init_context().await.map_err(|e| {
-        tracing::error!("Failed to initialize Kubernetes context: {}", e);
-        color_eyre::eyre::eyre!("K8s context init failed: {}", e)
-    })?;
+    let kube_context = cli.kube_context().await?;
+
+    // Check --namespace, --pod and --container before taking over the terminal.
+    let client = kube_context
+        .client
+        .clone()
+        .expect("Kube client not initialized");
+    let launch_target = cli.resolve_target(client).await?;
 
     // TODO: replace passing a context directly by using arc (and avoid cloning (: )
     let arc_kube_context = Arc::new(kube_context);
@@ -53,7 +65,7 @@ async fn main() -> Result<()> {
     tracing::info!("In-memory Sqlite cache initialized.");
 
     // Init Sqlite persistent storage
-    let sqlite_db = SqliteDb::new(&db_path, db_connection_timeout)
+    let sqlite_db = SqliteDb::new(&db_path, config.db.timeout_secs)
         .await
         .wrap_err("Sqlite cache init failed")?;
     let arc_sqlite_db = Arc::new(sqlite_db);
@@ -62,24 +74,57 @@ async fn main() -> Result<()> {
     // Clone contexts to send to secondary thread
     let arc_kube_context_for_task = arc_kube_context.clone();
     let arc_sqlite_cache_for_task = arc_sqlite_cache.clone();
-    let _arc_sqlite_db_for_task = arc_sqlite_db.clone();
-
-    // Secondary thread for syncing kube data to cache
-    tokio::spawn(periodic_multistage_cache_sync(
-        arc_kube_context_for_task,
-        arc_sqlite_cache_for_task,
-        // arc_sqlite_db_for_task,
-    ));
-    tracing::info!("Periodic K8s data sync task (using SQLx) spawned.");
-
+    let arc_sqlite_db_for_task = arc_sqlite_db.clone();
+
+    // Without the periodic sync, or when opening straight into a pod list, the cache has to be
+    // filled before the first view is drawn.
+    if cli.no_sync || launch_target.is_some() {
+        sync_once(
+            &arc_kube_context_for_task,
+            arc_sqlite_cache_for_task.as_ref(),
+            arc_sqlite_db_for_task.as_ref(),
+        )
+        .await
+        .wrap_err("Initial cache sync failed")?;
+        tracing::info!("Cache filled before start.");
+    }
+    if !cli.no_sync {
+        // Secondary thread for syncing kube data to cache, through the daemon when one runs
+        tokio::spawn(sync_from_daemon_or_cluster(
+            config.daemon_socket()?,
+            arc_kube_context_for_task,
+            arc_sqlite_cache_for_task,
+            arc_sqlite_db_for_task,
+            config.sync.clone(),
+        ));
+        tracing::info!("Periodic K8s data sync task (using SQLx) spawned.");
+    }
+
+    let mut kuco = Kuco::new(
+        arc_sqlite_cache,
+        arc_sqlite_db,
+        config,
+        keymap,
+        arc_kube_context.as_ref().clone(),
+    )
+    .await;
+    kuco.readonly = cli.readonly;
+    if let Some(target) = launch_target {
+        kuco.open_target(target).await;
+    }
 
     // Run TUI
     let terminal = ratatui::init();
-    let result = Kuco::new(arc_sqlite_cache, arc_sqlite_db)
-        .await
-        .run(terminal)
-        .await;
-
+    // Pasting falls back to typed keys without bracketed paste, so failing to toggle it is not
+    // worth leaving the terminal in raw mode over.
+    if let Err(e) = crossterm::execute!(stdout(), EnableBracketedPaste) {
+        tracing::error!("Failed to enable bracketed paste: {}", e);
+    }
+    let result = kuco.run(terminal).await;
+
+    if let Err(e) = crossterm::execute!(stdout(), DisableBracketedPaste) {
+        tracing::error!("Failed to disable bracketed paste: {}", e);
+    }
     ratatui::restore();
 
     result
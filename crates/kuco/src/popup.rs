@@ -0,0 +1,624 @@
+/*
+ * Modal popups drawn over the current view: confirmations for cluster-mutating actions and
+ * per-item progress for long running operations.
+ */
+
+use std::time::Duration;
+
+use kuco_k8s_backend::{
+    autoscaling::HpaInfo,
+    delete::{DeleteOptions, DeleteStatus, PodOwner},
+    discovery::ApiKind,
+    drain::{DrainOptions, DrainStatus},
+};
+use ratatui::{
+    style::{Color, Style, Stylize},
+    text::Line,
+};
+
+use crate::finder::Finder;
+use crate::yaml::YamlViewer;
+
+pub const DRAIN_TIMEOUT_STEP: Duration = Duration::from_secs(30);
+pub const GRACE_PERIOD_STEP: i64 = 5;
+/// Marked pods listed by name in the bulk popup before the rest are only counted.
+const BULK_TARGETS_SHOWN: usize = 10;
+
+#[derive(Debug, Clone)]
+pub enum Popup {
+    DrainConfirm {
+        node: String,
+        options: DrainOptions,
+        confirm: Option<TypedConfirm>,
+    },
+    Progress(OperationProgress),
+    HpaEdit(HpaEdit),
+    Scale(ScaleEdit),
+    Delete(PodDelete),
+    Bulk(BulkMenu),
+    Columns(ColumnChooser),
+    Finder(Finder),
+    Yaml(YamlViewer),
+}
+
+/// A name that has to be typed out before a destructive popup is confirmed, in contexts with
+/// `confirm_by_name` set. Confirming the popup first starts the typing.
+#[derive(Debug, Clone)]
+pub struct TypedConfirm {
+    pub expected: String,
+    pub input: String,
+    pub typing: bool,
+}
+
+impl TypedConfirm {
+    /// `Some` only when the protection rules ask for it.
+    pub fn required(required: bool, expected: &str) -> Option<Self> {
+        required.then(|| Self {
+            expected: expected.to_owned(),
+            input: String::new(),
+            typing: false,
+        })
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.input == self.expected
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        if !self.typing {
+            return vec![Line::styled(
+                format!(
+                    "Protected context: confirming asks for '{}'.",
+                    self.expected
+                ),
+                Style::new().fg(Color::Yellow),
+            )];
+        }
+
+        let color = if self.is_confirmed() {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        vec![
+            Line::styled(
+                format!("Type '{}' to confirm: {}_", self.expected, self.input),
+                Style::new().fg(color).bold(),
+            ),
+            Line::from("[Enter] confirm    [Esc] back").italic(),
+        ]
+    }
+}
+
+/// Toggles which columns of the current table view are shown.
+#[derive(Debug, Clone)]
+pub struct ColumnChooser {
+    pub columns: Vec<String>,
+    pub hidden: Vec<String>,
+    pub cursor: usize,
+}
+
+impl ColumnChooser {
+    /// Show or hide the column under the cursor. The first column identifies each row, so it
+    /// always stays visible.
+    pub fn toggle(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        let Some(column) = self.columns.get(self.cursor) else {
+            return;
+        };
+
+        match self.hidden.iter().position(|h| h == column) {
+            Some(i) => {
+                self.hidden.remove(i);
+            }
+            None => self.hidden.push(column.clone()),
+        }
+    }
+
+    pub fn move_cursor(&mut self, down: bool) {
+        if down {
+            self.cursor = (self.cursor + 1).min(self.columns.len().saturating_sub(1));
+        } else {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+    }
+}
+
+/// Pending change to an HPA's replica bounds, applied only once confirmed.
+#[derive(Debug, Clone)]
+pub struct HpaEdit {
+    pub namespace: String,
+    pub hpa: HpaInfo,
+    pub min_replicas: i32,
+    pub max_replicas: i32,
+    pub editing_max: bool,
+    pub confirm: Option<TypedConfirm>,
+}
+
+impl HpaEdit {
+    pub fn new(namespace: String, hpa: HpaInfo) -> Self {
+        Self {
+            namespace,
+            min_replicas: hpa.min_replicas,
+            max_replicas: hpa.max_replicas,
+            hpa,
+            editing_max: false,
+            confirm: None,
+        }
+    }
+
+    /// Step the field being edited by `delta`, keeping 1 <= min <= max.
+    pub fn adjust(&mut self, delta: i32) {
+        if self.editing_max {
+            self.max_replicas = (self.max_replicas + delta).max(self.min_replicas);
+        } else {
+            self.min_replicas = (self.min_replicas + delta).clamp(1, self.max_replicas);
+        }
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.min_replicas != self.hpa.min_replicas || self.max_replicas != self.hpa.max_replicas
+    }
+}
+
+/// Pending replica count for a scalable workload.
+#[derive(Debug, Clone)]
+pub struct ScaleEdit {
+    pub kind: ApiKind,
+    pub namespace: String,
+    pub name: String,
+    pub current_replicas: i32,
+    pub replicas: i32,
+    /// The autoscaler targeting this workload, if one does.
+    pub hpa: Option<HpaInfo>,
+    pub confirm: Option<TypedConfirm>,
+}
+
+/// Pending delete of a pod, with its owner shown so it is clear whether it comes back.
+#[derive(Debug, Clone)]
+pub struct PodDelete {
+    pub namespace: String,
+    pub name: String,
+    pub owner: Option<PodOwner>,
+    pub options: DeleteOptions,
+    pub confirm: Option<TypedConfirm>,
+}
+
+impl PodDelete {
+    /// Step the grace period by `delta` seconds, starting at 0 from the pod's own and going
+    /// back to it below 0.
+    pub fn adjust_grace_period(&mut self, delta: i64) {
+        step_grace_period(&mut self.options, delta);
+    }
+}
+
+fn step_grace_period(options: &mut DeleteOptions, delta: i64) {
+    options.grace_period = match options.grace_period {
+        None if delta > 0 => Some(0),
+        None => None,
+        Some(secs) => u32::try_from(i64::from(secs) + delta).ok(),
+    };
+}
+
+// The choices a delete is confirmed with, shared by the single and bulk delete popups.
+fn delete_option_lines(options: &DeleteOptions) -> Vec<Line<'static>> {
+    let grace_period = match (options.force, options.grace_period) {
+        (true, _) => "0s (forced)".to_owned(),
+        (false, Some(secs)) => format!("{}s", secs),
+        (false, None) => "the pod's own".to_owned(),
+    };
+    vec![
+        Line::from(format!("  [+/-] grace period: {}", grace_period)),
+        Line::from(format!(
+            "  [f] force (gracePeriodSeconds=0): {}",
+            if options.force { "on" } else { "off" }
+        )),
+        Line::from(format!(
+            "  [p] propagation policy: {}",
+            options.propagation.label()
+        )),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkAction {
+    Delete,
+    /// Roll out the Deployments, StatefulSets and DaemonSets owning the pods.
+    Restart,
+    /// Interleave the pods' logs in the logs view.
+    Logs,
+    /// Write the pods' manifests to one multi-document YAML file.
+    Export,
+}
+
+impl BulkAction {
+    pub fn mutates(&self) -> bool {
+        matches!(self, BulkAction::Delete | BulkAction::Restart)
+    }
+}
+
+/// The pods marked in a list, and the action picked for them. Actions that change the cluster
+/// wait in `pending` until confirmed.
+#[derive(Debug, Clone)]
+pub struct BulkMenu {
+    /// `(namespace, pod)` of every marked entry.
+    pub targets: Vec<(String, String)>,
+    pub pending: Option<BulkAction>,
+    /// What a [`BulkAction::Delete`] deletes every target with.
+    pub delete_options: DeleteOptions,
+    pub confirm: Option<TypedConfirm>,
+}
+
+impl BulkMenu {
+    pub fn new(targets: Vec<(String, String)>) -> Self {
+        Self {
+            targets,
+            pending: None,
+            delete_options: DeleteOptions::default(),
+            confirm: None,
+        }
+    }
+
+    /// Like [`PodDelete::adjust_grace_period`], for every target.
+    pub fn adjust_grace_period(&mut self, delta: i64) {
+        step_grace_period(&mut self.delete_options, delta);
+    }
+
+    /// The namespaces of the targets, sorted and without repeats.
+    pub fn namespaces(&self) -> Vec<String> {
+        let mut namespaces: Vec<String> = self.targets.iter().map(|(ns, _)| ns.clone()).collect();
+        namespaces.sort();
+        namespaces.dedup();
+        namespaces
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line> = self
+            .targets
+            .iter()
+            .take(BULK_TARGETS_SHOWN)
+            .map(|(ns, pod)| Line::from(format!("  {}/{}", ns, pod)))
+            .collect();
+        if self.targets.len() > BULK_TARGETS_SHOWN {
+            lines.push(Line::from(format!(
+                "  ... and {} more",
+                self.targets.len() - BULK_TARGETS_SHOWN
+            )));
+        }
+        lines.push(Line::from(""));
+
+        let count = self.targets.len();
+        match self.pending {
+            Some(action) => {
+                let question = match action {
+                    BulkAction::Delete => format!("Delete these {} pods?", count),
+                    _ => format!("Restart the workloads owning these {} pods?", count),
+                };
+                lines.push(Line::styled(
+                    question,
+                    Style::new().fg(Color::Yellow).bold(),
+                ));
+                if action == BulkAction::Delete {
+                    lines.push(Line::from(""));
+                    lines.extend(delete_option_lines(&self.delete_options));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::from("[y/Enter] confirm    [n/Esc] back").italic());
+            }
+            None => {
+                lines.push(Line::from("  [d] delete"));
+                lines.push(Line::from("  [r] restart owners"));
+                lines.push(Line::from("  [l] tail logs together"));
+                lines.push(Line::from("  [y] export YAML"));
+                lines.push(Line::from(""));
+                lines.push(Line::from("[Esc] cancel").italic());
+            }
+        }
+        lines
+    }
+}
+
+impl Popup {
+    pub fn drain_confirm(node: String, confirm: Option<TypedConfirm>) -> Self {
+        Popup::DrainConfirm {
+            node,
+            options: DrainOptions::default(),
+            confirm,
+        }
+    }
+
+    pub fn typed_confirm_mut(&mut self) -> Option<&mut TypedConfirm> {
+        match self {
+            Popup::DrainConfirm { confirm, .. } => confirm.as_mut(),
+            Popup::HpaEdit(edit) => edit.confirm.as_mut(),
+            Popup::Scale(scale) => scale.confirm.as_mut(),
+            Popup::Delete(delete) => delete.confirm.as_mut(),
+            Popup::Bulk(bulk) => bulk.confirm.as_mut(),
+            _ => None,
+        }
+    }
+
+    fn typed_confirm(&self) -> Option<&TypedConfirm> {
+        match self {
+            Popup::DrainConfirm { confirm, .. } => confirm.as_ref(),
+            Popup::HpaEdit(edit) => edit.confirm.as_ref(),
+            Popup::Scale(scale) => scale.confirm.as_ref(),
+            Popup::Delete(delete) => delete.confirm.as_ref(),
+            Popup::Bulk(bulk) => bulk.confirm.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Popup::DrainConfirm { node, .. } => format!(" Drain {} ", node),
+            Popup::Progress(progress) => format!(" {} ", progress.title),
+            Popup::HpaEdit(edit) => format!(" Edit {} ", edit.hpa.name),
+            Popup::Scale(scale) => format!(" Scale {}/{} ", scale.kind.kind, scale.name),
+            Popup::Delete(delete) => format!(" Delete pod {}/{} ", delete.namespace, delete.name),
+            Popup::Bulk(bulk) => format!(" {} marked pods ", bulk.targets.len()),
+            Popup::Columns(_) => " Columns ".to_owned(),
+            Popup::Finder(_) => " Find ".to_owned(),
+            Popup::Yaml(viewer) => format!(" {} ", viewer.title()),
+        }
+    }
+
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = self.body_lines();
+        if let Some(confirm) = self.typed_confirm() {
+            lines.push(Line::from(""));
+            lines.extend(confirm.lines());
+        }
+        lines
+    }
+
+    fn body_lines(&self) -> Vec<Line<'static>> {
+        match self {
+            Popup::DrainConfirm { node, options, .. } => vec![
+                Line::from(format!(
+                    "Cordon '{}' and evict all of its pods (DaemonSet and mirror pods are skipped).",
+                    node
+                )),
+                Line::from(""),
+                Line::from(format!(
+                    "  [f] force (evict unmanaged pods): {}",
+                    if options.force { "on" } else { "off" }
+                )),
+                Line::from(format!("  [+/-] timeout: {}s", options.timeout.as_secs())),
+                Line::from(""),
+                Line::from("[y/Enter] drain    [n/Esc] cancel").italic(),
+            ],
+            Popup::Progress(progress) => progress.lines(),
+            Popup::HpaEdit(edit) => {
+                let marker = |selected: bool| if selected { ">" } else { " " };
+                vec![
+                    Line::from(format!(
+                        "Replica bounds for {}/{}.",
+                        edit.hpa.target_kind, edit.hpa.target_name
+                    )),
+                    Line::from(""),
+                    Line::from(format!(
+                        "{} min replicas: {} -> {}",
+                        marker(!edit.editing_max),
+                        edit.hpa.min_replicas,
+                        edit.min_replicas
+                    )),
+                    Line::from(format!(
+                        "{} max replicas: {} -> {}",
+                        marker(edit.editing_max),
+                        edit.hpa.max_replicas,
+                        edit.max_replicas
+                    )),
+                    Line::from(""),
+                    Line::from("[Tab] switch field    [+/-] adjust").italic(),
+                    Line::from("[y/Enter] apply    [n/Esc] cancel").italic(),
+                ]
+            }
+            Popup::Scale(scale) => {
+                let mut lines = vec![
+                    Line::from(format!(
+                        "replicas: {} -> {}",
+                        scale.current_replicas, scale.replicas
+                    )),
+                    Line::from(""),
+                ];
+                // Whatever is set here gets reverted on the autoscaler's next sync.
+                if let Some(hpa) = &scale.hpa {
+                    lines.push(Line::styled(
+                        format!(
+                            "HPA '{}' controls this {} ({}-{} replicas); manual scaling will be overridden.",
+                            hpa.name, scale.kind.kind, hpa.min_replicas, hpa.max_replicas
+                        ),
+                        Style::new().fg(Color::Yellow).bold(),
+                    ));
+                    lines.push(Line::from(""));
+                }
+                lines.push(Line::from("[+/-] adjust").italic());
+                lines.push(Line::from("[y/Enter] scale    [n/Esc] cancel").italic());
+                lines
+            }
+            Popup::Delete(delete) => {
+                let owner = match &delete.owner {
+                    Some(owner) => {
+                        let mut owner_text = format!("{} '{}'", owner.kind, owner.name);
+                        if let Some(deployment) = &owner.deployment {
+                            owner_text += &format!(" of Deployment '{}'", deployment);
+                        }
+                        Line::from(format!(
+                            "Owned by {}, which will create a replacement.",
+                            owner_text
+                        ))
+                    }
+                    None => Line::styled(
+                        "No controller owns this pod; it will not be recreated.",
+                        Style::new().fg(Color::Yellow).bold(),
+                    ),
+                };
+                let mut lines = vec![owner, Line::from("")];
+                lines.extend(delete_option_lines(&delete.options));
+                lines.push(Line::from(""));
+                lines.push(Line::from("[y/Enter] delete    [n/Esc] cancel").italic());
+                lines
+            }
+            Popup::Bulk(bulk) => bulk.lines(),
+            Popup::Columns(chooser) => {
+                let mut lines: Vec<Line> = chooser
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        let shown = !chooser.hidden.contains(column);
+                        let line = Line::from(format!(
+                            "{} [{}] {}",
+                            if i == chooser.cursor { ">" } else { " " },
+                            if shown { "x" } else { " " },
+                            column
+                        ));
+                        if i == chooser.cursor {
+                            line.bold()
+                        } else {
+                            line
+                        }
+                    })
+                    .collect();
+                lines.push(Line::from(""));
+                lines.push(Line::from("[j/k] move    [Space] toggle    [Enter/Esc] done").italic());
+                lines
+            }
+            Popup::Finder(finder) => finder.lines(),
+            // Sized to the pane it is drawn in, see `YamlViewer::lines`.
+            Popup::Yaml(_) => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressItem {
+    pub key: String,
+    pub status: String,
+    pub failed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperationProgress {
+    pub title: String,
+    pub items: Vec<ProgressItem>,
+    pub error: Option<String>,
+    pub finished: bool,
+}
+
+impl OperationProgress {
+    pub fn new(title: String) -> Self {
+        Self {
+            title,
+            items: Vec::new(),
+            error: None,
+            finished: false,
+        }
+    }
+
+    /// Insert or update the status line for `key`, keeping the order items first appeared in.
+    pub fn update(&mut self, key: String, status: String, failed: bool) {
+        match self.items.iter_mut().find(|item| item.key == key) {
+            Some(item) => {
+                item.status = status;
+                item.failed = failed;
+            }
+            None => self.items.push(ProgressItem {
+                key,
+                status,
+                failed,
+            }),
+        }
+    }
+
+    pub fn update_drain(&mut self, key: String, status: DrainStatus) {
+        let (text, failed) = match status {
+            DrainStatus::Skipped(reason) => (format!("skipped ({})", reason), false),
+            DrainStatus::Evicting => ("evicting ...".to_owned(), false),
+            DrainStatus::Blocked(reason) => (format!("blocked, retrying: {}", reason), false),
+            DrainStatus::Terminating => ("terminating ...".to_owned(), false),
+            DrainStatus::Evicted => ("evicted".to_owned(), false),
+            DrainStatus::Failed(reason) => (format!("failed: {}", reason), true),
+        };
+        self.update(key, text, failed);
+    }
+
+    /// Each step keeps the ones before it in view, ending in the replacement's name.
+    pub fn update_delete(&mut self, key: String, status: DeleteStatus) {
+        let (text, failed) = match status {
+            DeleteStatus::Deleting => ("deleting ...".to_owned(), false),
+            DeleteStatus::Terminating => ("terminating ...".to_owned(), false),
+            DeleteStatus::Gone => ("terminating -> gone".to_owned(), false),
+            DeleteStatus::Replaced(name) => (
+                format!("terminating -> gone -> replaced by {}", name),
+                false,
+            ),
+            DeleteStatus::NotReplaced => (
+                "terminating -> gone -> no replacement yet".to_owned(),
+                false,
+            ),
+            DeleteStatus::ReplacementUnknown => (
+                "terminating -> gone -> replacement unknown (could not list the owner's pods)"
+                    .to_owned(),
+                false,
+            ),
+            DeleteStatus::Failed(reason) => (format!("failed: {}", reason), true),
+        };
+        self.update(key, text, failed);
+    }
+
+    pub fn finish(&mut self, error: Option<String>) {
+        self.finished = true;
+        self.error = error;
+    }
+
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line> = self
+            .items
+            .iter()
+            .map(|item| {
+                let style = if item.failed {
+                    Style::new().fg(Color::Red)
+                } else {
+                    Style::new().fg(Color::Magenta)
+                };
+                Line::styled(format!("{}  {}", item.key, item.status), style)
+            })
+            .collect();
+
+        if !self.finished {
+            lines.push(Line::from(""));
+            lines.push(Line::from("working ... [Esc] hide").italic());
+            return lines;
+        }
+
+        let failures: Vec<&ProgressItem> = self.items.iter().filter(|i| i.failed).collect();
+        lines.push(Line::from(""));
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(
+                format!("Error: {}", error),
+                Style::new().fg(Color::Red),
+            ));
+        }
+        if failures.is_empty() && self.error.is_none() {
+            lines.push(Line::styled("Completed.", Style::new().fg(Color::Green)));
+        } else if !failures.is_empty() {
+            lines.push(Line::styled(
+                format!("Failures ({}):", failures.len()),
+                Style::new().fg(Color::Red).bold(),
+            ));
+            for item in failures {
+                lines.push(Line::styled(
+                    format!("  {}  {}", item.key, item.status),
+                    Style::new().fg(Color::Red),
+                ));
+            }
+        }
+        lines.push(Line::from("[Esc/Enter] close").italic());
+
+        lines
+    }
+}
@@ -0,0 +1,287 @@
+/*
+ * Single line prompts typed into the input bar (selectors and commands), with history recall
+ * and tab completion.
+ */
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use kuco_k8s_backend::pods::{POD_FIELD_PATHS, PodInfo};
+
+use crate::command::{ArgKind, expand_alias, find_spec};
+use crate::line_editor::LineEditor;
+
+const POD_PHASES: [&str; 5] = ["Pending", "Running", "Succeeded", "Failed", "Unknown"];
+
+/// The pod selector being typed, e.g. `app=api,tier!=cache,status.phase=Running`.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorPrompt {
+    /// Holds the recently applied selectors as its history, most recent first.
+    pub editor: LineEditor,
+}
+
+impl SelectorPrompt {
+    pub fn new(input: String, history: Vec<String>) -> Self {
+        Self {
+            editor: LineEditor::new(input, history),
+        }
+    }
+
+    /// Complete the last term from the label keys and values of `pods`, and the
+    /// pod field paths. Returns the candidates when the completion is ambiguous.
+    pub fn complete(&mut self, pods: &[PodInfo]) -> Vec<String> {
+        let mut labels: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for pod in pods {
+            for (key, value) in &pod.labels {
+                labels.entry(key).or_default().insert(value);
+            }
+        }
+
+        // Everything up to the term being typed stays as it is.
+        let (head, term) = split_last_term(self.editor.text());
+        let head = head.to_owned();
+        let term = term.trim_start().to_owned();
+        let term = term.as_str();
+
+        let (prefix, partial, candidates): (String, &str, Vec<String>) =
+            match term.find("!=").or(term.find('=')) {
+                Some(op_index) => {
+                    let key = term[..op_index].trim();
+                    let op_len = if ["!=", "=="]
+                        .iter()
+                        .any(|op| term[op_index..].starts_with(op))
+                    {
+                        2
+                    } else {
+                        1
+                    };
+                    let values: Vec<String> = if key == "status.phase" {
+                        POD_PHASES.iter().map(|p| p.to_string()).collect()
+                    } else {
+                        labels
+                            .get(key)
+                            .map(|v| v.iter().map(|s| s.to_string()).collect())
+                            .unwrap_or_default()
+                    };
+                    (
+                        term[..op_index + op_len].to_owned(),
+                        term[op_index + op_len..].trim_start(),
+                        values,
+                    )
+                }
+                None => {
+                    let negated = term.starts_with('!');
+                    let keys: Vec<String> = labels
+                        .keys()
+                        .map(|k| k.to_string())
+                        .chain(POD_FIELD_PATHS.iter().map(|f| f.to_string()))
+                        .collect();
+                    (
+                        if negated {
+                            "!".to_owned()
+                        } else {
+                            String::new()
+                        },
+                        term.trim_start_matches('!'),
+                        keys,
+                    )
+                }
+            };
+
+        let (common, matches) = complete_prefix(partial, candidates);
+        if let Some(common) = common {
+            self.editor
+                .set_text(format!("{}{}{}", head, prefix, common));
+        }
+        matches
+    }
+}
+
+/// A `:` command being typed, and why the last one could not be run.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPrompt {
+    /// Holds the recently run commands as its history, most recent first.
+    pub editor: LineEditor,
+    pub error: Option<String>,
+    /// Kubeconfig contexts `:ctx` can switch to.
+    pub contexts: Vec<String>,
+}
+
+impl CommandPrompt {
+    pub fn new(history: Vec<String>, contexts: Vec<String>) -> Self {
+        Self {
+            editor: LineEditor::new(String::new(), history),
+            error: None,
+            contexts,
+        }
+    }
+
+    /// Complete the command name from `names`, or once one has been typed, its argument from
+    /// `namespaces` or the contexts. Returns the candidates when the completion is ambiguous.
+    pub fn complete(
+        &mut self,
+        names: &[String],
+        namespaces: &[String],
+        aliases: &HashMap<String, String>,
+    ) -> Vec<String> {
+        let text = self.editor.text().trim_start().to_owned();
+        let (head, partial, candidates) = match text.split_once(' ') {
+            None => (String::new(), text.as_str(), names.to_vec()),
+            Some((name, arg)) => {
+                let expanded = expand_alias(name, aliases);
+                let spec = expanded.split_whitespace().next().and_then(find_spec);
+                let candidates = match spec.map(|spec| spec.arg) {
+                    Some(ArgKind::Namespace) => namespaces.to_vec(),
+                    Some(ArgKind::Context) => self.contexts.clone(),
+                    _ => Vec::new(),
+                };
+                (format!("{} ", name), arg.trim_start(), candidates)
+            }
+        };
+
+        let (common, matches) = complete_prefix(partial, candidates);
+        if let Some(common) = common {
+            self.editor.set_text(format!("{}{}", head, common));
+        }
+        matches
+    }
+}
+
+/// The longest prefix shared by the candidates starting with `partial` (`None` without any),
+/// and those candidates when there is more than one.
+fn complete_prefix(partial: &str, candidates: Vec<String>) -> (Option<String>, Vec<String>) {
+    let matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|c| c.starts_with(partial))
+        .collect();
+    let Some(first) = matches.first() else {
+        return (None, Vec::new());
+    };
+    let common = matches.iter().fold(first.clone(), |common, m| {
+        common
+            .chars()
+            .zip(m.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    });
+
+    if matches.len() > 1 {
+        (Some(common), matches)
+    } else {
+        (Some(common), Vec::new())
+    }
+}
+
+/// Split `input` after its last comma outside of parentheses, like
+/// [`kuco_k8s_backend::pods::split_terms`] does for every term.
+fn split_last_term(input: &str) -> (&str, &str) {
+    let mut depth: usize = 0;
+    let mut split_at = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => split_at = i + 1,
+            _ => {}
+        }
+    }
+
+    input.split_at(split_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(labels: &[(&str, &str)]) -> PodInfo {
+        PodInfo {
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn pods() -> Vec<PodInfo> {
+        vec![
+            pod(&[("app", "api"), ("tier", "backend")]),
+            pod(&[("app", "web"), ("tier", "frontend")]),
+            pod(&[("app", "worker")]),
+        ]
+    }
+
+    // The text after completing `input`, and the candidates offered.
+    fn complete(input: &str) -> (String, Vec<String>) {
+        let mut prompt = SelectorPrompt::new(input.to_owned(), Vec::new());
+        let candidates = prompt.complete(&pods());
+        (prompt.editor.text().to_owned(), candidates)
+    }
+
+    #[test]
+    fn completes_label_keys_and_fields() {
+        assert_eq!(complete("ti"), ("tier".to_owned(), Vec::new()));
+        assert_eq!(
+            complete("status.ph"),
+            ("status.phase".to_owned(), Vec::new())
+        );
+
+        let (text, candidates) = complete("s");
+        assert_eq!(text, "s");
+        assert!(candidates.contains(&"spec.nodeName".to_owned()));
+        assert!(candidates.contains(&"status.phase".to_owned()));
+    }
+
+    #[test]
+    fn completes_negated_keys() {
+        assert_eq!(complete("!ti"), ("!tier".to_owned(), Vec::new()));
+        assert_eq!(
+            complete("app=api,!ti"),
+            ("app=api,!tier".to_owned(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn completes_values_after_any_operator() {
+        assert_eq!(complete("app=we"), ("app=web".to_owned(), Vec::new()));
+        assert_eq!(complete("app==we"), ("app==web".to_owned(), Vec::new()));
+        assert_eq!(
+            complete("tier!=f"),
+            ("tier!=frontend".to_owned(), Vec::new())
+        );
+        assert_eq!(
+            complete("status.phase=R"),
+            ("status.phase=Running".to_owned(), Vec::new())
+        );
+        assert_eq!(
+            complete("app=w"),
+            (
+                "app=w".to_owned(),
+                vec!["web".to_owned(), "worker".to_owned()]
+            )
+        );
+    }
+
+    #[test]
+    fn completes_only_the_last_term() {
+        assert_eq!(
+            complete("env in (a,b),ti"),
+            ("env in (a,b),tier".to_owned(), Vec::new())
+        );
+        assert_eq!(
+            complete("app=api, tier=b"),
+            ("app=api,tier=backend".to_owned(), Vec::new())
+        );
+        // Inside an unclosed set, there is no term to complete yet.
+        assert_eq!(
+            complete("app in (api,w"),
+            ("app in (api,w".to_owned(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_input_alone() {
+        assert_eq!(complete("zone=e"), ("zone=e".to_owned(), Vec::new()));
+        assert_eq!(complete("nope"), ("nope".to_owned(), Vec::new()));
+    }
+}
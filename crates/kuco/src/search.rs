@@ -0,0 +1,168 @@
+/*
+ * Matching for SEARCH mode: ranks display list entries and reports which characters matched,
+ * so they can be highlighted in the rendered rows.
+ */
+
+use nucleo_matcher::{
+    Config, Matcher, Utf32Str,
+    pattern::{Atom, AtomKind, CaseMatching, Normalization, Pattern},
+};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use regex::RegexBuilder;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MatchMode {
+    #[default]
+    Fuzzy,
+    Prefix,
+    Substring,
+    Regex,
+}
+
+impl MatchMode {
+    pub fn next(&self) -> Self {
+        match self {
+            MatchMode::Fuzzy => MatchMode::Prefix,
+            MatchMode::Prefix => MatchMode::Substring,
+            MatchMode::Substring => MatchMode::Regex,
+            MatchMode::Regex => MatchMode::Fuzzy,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchMode::Fuzzy => "fuzzy",
+            MatchMode::Prefix => "prefix",
+            MatchMode::Substring => "substring",
+            MatchMode::Regex => "regex",
+        }
+    }
+}
+
+/// An entry that matched, with its score and the char indices of the matched characters.
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    pub item: String,
+    pub score: u32,
+    pub indices: Vec<usize>,
+}
+
+/// Match `items` against `pattern`, best first. Equal scores go to the shorter entry, then keep
+/// their original order. Fails only on an invalid regex.
+pub fn match_items(
+    mode: MatchMode,
+    pattern: &str,
+    items: &[String],
+) -> Result<Vec<SearchMatch>, regex::Error> {
+    let mut matches = match mode {
+        MatchMode::Regex => match_regex(pattern, items)?,
+        _ => match_nucleo(mode, pattern, items),
+    };
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.item.chars().count().cmp(&b.item.chars().count()))
+    });
+
+    Ok(matches)
+}
+
+fn match_nucleo(mode: MatchMode, pattern: &str, items: &[String]) -> Vec<SearchMatch> {
+    let mut matcher = Matcher::new(Config::DEFAULT.match_paths());
+    let mut buf = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+
+    // Fuzzy keeps the multi-word pattern syntax; the other modes match the input literally.
+    let kind = match mode {
+        MatchMode::Prefix => Some(AtomKind::Prefix),
+        MatchMode::Substring => Some(AtomKind::Substring),
+        _ => None,
+    };
+    let fuzzy = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart);
+    let literal = kind.map(|kind| {
+        Atom::new(
+            pattern,
+            CaseMatching::Ignore,
+            Normalization::Smart,
+            kind,
+            false,
+        )
+    });
+
+    items
+        .iter()
+        .filter_map(|item| {
+            indices.clear();
+            let haystack = Utf32Str::new(item, &mut buf);
+            let score = match &literal {
+                Some(atom) => atom
+                    .indices(haystack, &mut matcher, &mut indices)
+                    .map(u32::from)?,
+                None => fuzzy.indices(haystack, &mut matcher, &mut indices)?,
+            };
+
+            let mut matched: Vec<usize> = indices.iter().map(|&i| i as usize).collect();
+            matched.sort_unstable();
+            matched.dedup();
+            Some(SearchMatch {
+                item: item.clone(),
+                score,
+                indices: matched,
+            })
+        })
+        .collect()
+}
+
+// Regexes have no notion of match quality, so earlier matches score higher.
+fn match_regex(pattern: &str, items: &[String]) -> Result<Vec<SearchMatch>, regex::Error> {
+    let regex = RegexBuilder::new(pattern).case_insensitive(true).build()?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let found = regex.find(item)?;
+            let start = item[..found.start()].chars().count();
+            let len = found.as_str().chars().count();
+
+            Some(SearchMatch {
+                item: item.clone(),
+                score: u32::MAX - start as u32,
+                indices: (start..start + len).collect(),
+            })
+        })
+        .collect())
+}
+
+/// Render `text` with the chars at `indices` highlighted on top of `style`.
+pub fn highlight_line(text: &str, indices: &[usize], style: Style) -> Line<'static> {
+    if indices.is_empty() {
+        return Line::styled(text.to_owned(), style);
+    }
+    let highlight = style
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans: Vec<Span> = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let matched = indices.binary_search(&i).is_ok();
+        if matched != current_matched && !current.is_empty() {
+            let span_style = if current_matched { highlight } else { style };
+            spans.push(Span::styled(std::mem::take(&mut current), span_style));
+        }
+        current_matched = matched;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(
+            current,
+            if current_matched { highlight } else { style },
+        ));
+    }
+
+    Line::from(spans)
+}
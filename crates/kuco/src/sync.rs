@@ -1,22 +1,145 @@
 use chrono::Utc;
 use color_eyre::Result;
 use k8s_openapi::api::core::v1::Pod;
-use kube::{Api, Client};
-use kuco_k8s_backend::{context::KubeContext, namespaces::NamespaceData, pods::PodData};
+use kube::Api;
+use kuco_k8s_backend::{
+    context::{KubeContext, list_contexts},
+    namespaces::NamespaceData,
+    nodes::NodeData,
+    pods::{PodData, PodInfo, PodSelector},
+};
 use kuco_sqlite_backend::KucoSqliteStore;
 use std::{sync::Arc, time::Duration};
+use tokio::sync::watch;
 
+use crate::config::SyncConfig;
 use crate::constants::{
-    CONT_NAMES_CACHE_KEY, KUCO_CACHE_TABLE, NS_NAMES_CACHE_KEY, POD_NAMES_CACHE_KEY,
+    ALL_POD_ROWS_CACHE_KEY, CONT_NAMES_CACHE_KEY, CONTEXTS_CACHE_KEY, CURRENT_CONTEXT_CACHE_KEY,
+    DEFAULT_CONTEXT_NAME, KUCO_CACHE_TABLE, NODES_CACHE_KEY, NS_NAMES_CACHE_KEY,
+    PERSISTED_CONT_KEY, PERSISTED_NS_KEY, PERSISTED_PODS_KEY, POD_NAMES_CACHE_KEY,
+    POD_ROWS_CACHE_KEY, POD_SELECTOR_CACHE_KEY,
 };
 
-async fn run_stage1_sync<S: KucoSqliteStore + Clone + 'static>(
-    client: &Client,
+/// Cache a cluster-wide pod list both as a whole and split into each of `ns_names`, so that
+/// namespaces without any (matching) pods are emptied too.
+pub async fn cache_pod_lists<S: KucoSqliteStore>(
+    cache_store: &S,
+    ns_names: &[String],
+    pods: &PodData,
+) -> Result<()> {
+    cache_store
+        .set_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            ALL_POD_ROWS_CACHE_KEY.to_string(),
+            &pods.list,
+        )
+        .await?;
+
+    for ns_name in ns_names {
+        let rows: Vec<PodInfo> = pods
+            .list
+            .iter()
+            .filter(|p| &p.namespace == ns_name)
+            .cloned()
+            .collect();
+        let names: Vec<String> = rows.iter().map(|p| p.name.clone()).collect();
+
+        // Names are still cached on their own for Stage 2 and the search list.
+        let pod_names_key = format!("{}{}", POD_NAMES_CACHE_KEY, ns_name);
+        cache_store
+            .set_json(KUCO_CACHE_TABLE.to_owned(), pod_names_key, &names)
+            .await?;
+
+        let pod_rows_key = format!("{}{}", POD_ROWS_CACHE_KEY, ns_name);
+        cache_store
+            .set_json(KUCO_CACHE_TABLE.to_owned(), pod_rows_key, &rows)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Key of `key` in the persistent DB, where synced data is kept per context so that headless
+/// commands never read one context's data for another.
+pub fn persisted_key(context: Option<&str>, key: &str) -> String {
+    format!("{}/{}", context.unwrap_or(DEFAULT_CONTEXT_NAME), key)
+}
+
+/// Whether `context` is still the one the TUI publishes through the cache. A sync that was
+/// listing a cluster when the TUI switched away from it drops its results rather than write
+/// them over the cache the switch refilled.
+async fn is_current_context<S: KucoSqliteStore>(context: Option<&str>, cache_store: &S) -> bool {
+    let published: Option<String> = cache_store
+        .get_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            CURRENT_CONTEXT_CACHE_KEY.to_string(),
+        )
+        .await
+        .unwrap_or_default();
+    published.is_none_or(|name| context == Some(name.as_str()))
+}
+
+async fn run_stage1_sync<S: KucoSqliteStore + Clone + 'static, D: KucoSqliteStore>(
+    kube_ctx: &KubeContext,
     cache_store: &S,
+    db_store: &D,
 ) -> Result<Vec<String>> {
-    tracing::info!("Running Stage 1 Sync: Namespaces and Pod Names");
+    tracing::info!("Running Stage 1 Sync: Namespaces, Nodes and Pods");
+    let client = kube_ctx
+        .client
+        .as_ref()
+        .expect("Kube client not initialized");
+    let context = kube_ctx.name.as_deref();
+
     let mut ns_data_fetcher = NamespaceData::new();
-    ns_data_fetcher.update(client.clone()).await; // Fetches namespace names
+    ns_data_fetcher.update(client.clone()).await?; // Fetches namespace names
+
+    // The TUI publishes its active pod selector through the cache, so the periodic sync keeps
+    // honouring it instead of overwriting the narrowed lists with every pod.
+    let selector: PodSelector = cache_store
+        .get_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            POD_SELECTOR_CACHE_KEY.to_string(),
+        )
+        .await?
+        .unwrap_or_default();
+
+    let mut pod_data_fetcher = PodData::default();
+    let pods = pod_data_fetcher
+        .update_list_all(client.clone(), &selector)
+        .await;
+
+    // Node requests are summed over the pod list above, unless the TUI's selector narrowed it,
+    // in which case the nodes need an unfiltered list of their own.
+    let mut unfiltered_pods = PodData::default();
+    let node_pods = if selector.is_empty() {
+        pods.as_ref()
+            .map(|_| &pod_data_fetcher.list)
+            .map_err(|e| e.to_string())
+    } else {
+        unfiltered_pods
+            .update_list_all(client.clone(), &PodSelector::default())
+            .await
+            .map(|_| &unfiltered_pods.list)
+            .map_err(|e| e.to_string())
+    };
+
+    // Nodes are cluster-scoped, so a missing RBAC grant here should not stop the pod sync.
+    let mut node_data_fetcher = NodeData::default();
+    let nodes = match node_pods {
+        Ok(node_pods) => node_data_fetcher
+            .update(client.clone(), node_pods)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
+
+    // Everything is listed before anything is written, so a context switch in the meantime is
+    // caught before the old cluster lands in the cache.
+    if !is_current_context(context, cache_store).await {
+        tracing::info!("Stage 1: Context changed while syncing, dropping the results.");
+        return Ok(Vec::new());
+    }
 
     cache_store
         .set_json(
@@ -25,22 +148,56 @@ async fn run_stage1_sync<S: KucoSqliteStore + Clone + 'static>(
             &ns_data_fetcher.names,
         )
         .await?;
+    db_store
+        .set_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            persisted_key(context, PERSISTED_NS_KEY),
+            &ns_data_fetcher.names,
+        )
+        .await?;
+
+    match nodes {
+        Ok(_) => {
+            cache_store
+                .set_json(
+                    KUCO_CACHE_TABLE.to_owned(),
+                    NODES_CACHE_KEY.to_string(),
+                    &node_data_fetcher.list,
+                )
+                .await?;
+        }
+        Err(e) => tracing::error!("Stage 1: Failed to get nodes: {}", e),
+    }
 
-    for ns_name in &ns_data_fetcher.names {
-        let mut pod_data_fetcher = PodData::default();
-        match pod_data_fetcher.get_names(client.clone(), ns_name).await {
-            Ok(_) => {
-                let pod_names_key = format!("{}{}", POD_NAMES_CACHE_KEY, ns_name);
-                cache_store
+    match pods {
+        Ok(_) => {
+            cache_pod_lists(cache_store, &ns_data_fetcher.names, &pod_data_fetcher).await?;
+            // A list narrowed by the TUI's selector is not every pod, so it is not persisted.
+            if selector.is_empty() {
+                db_store
                     .set_json(
                         KUCO_CACHE_TABLE.to_owned(),
-                        pod_names_key,
-                        &pod_data_fetcher.names,
+                        persisted_key(context, PERSISTED_PODS_KEY),
+                        &pod_data_fetcher.list,
                     )
                     .await?;
             }
-            Err(e) => tracing::error!("Stage 1: Failed to get pod names for ns {}: {}", ns_name, e),
         }
+        Err(e) => tracing::error!("Stage 1: Failed to get pods: {}", e),
+    }
+
+    // Contexts come from the local kubeconfig, not the cluster, and only feed `:ctx` completion.
+    match list_contexts(kube_ctx.kubeconfig.as_deref()) {
+        Ok((contexts, _)) => {
+            cache_store
+                .set_json(
+                    KUCO_CACHE_TABLE.to_owned(),
+                    CONTEXTS_CACHE_KEY.to_string(),
+                    &contexts,
+                )
+                .await?;
+        }
+        Err(e) => tracing::error!("Stage 1: Failed to read kubeconfig contexts: {}", e),
     }
 
     let current_timestamp_seconds: i64 = Utc::now().timestamp();
@@ -55,11 +212,16 @@ async fn run_stage1_sync<S: KucoSqliteStore + Clone + 'static>(
     Ok(ns_data_fetcher.names)
 }
 
-async fn run_stage2_sync_for_namespace<S: KucoSqliteStore + Clone + 'static>(
-    client: &Client,
+async fn run_stage2_sync_for_namespace<S: KucoSqliteStore + Clone + 'static, D: KucoSqliteStore>(
+    kube_ctx: &KubeContext,
     cache_store: &S,
+    db_store: &D,
     ns_name: &str,
 ) {
+    let client = kube_ctx
+        .client
+        .as_ref()
+        .expect("Kube client not initialized");
     let pod_names_key = format!("{}{}", POD_NAMES_CACHE_KEY, ns_name);
     let pod_names_for_ns: Option<Vec<String>> = cache_store
         .get_json(KUCO_CACHE_TABLE.to_owned(), pod_names_key)
@@ -90,6 +252,29 @@ async fn run_stage2_sync_for_namespace<S: KucoSqliteStore + Clone + 'static>(
                             .spec
                             .map(|spec| spec.containers.into_iter().map(|c| c.name).collect())
                             .unwrap_or_default();
+                        if !is_current_context(kube_ctx.name.as_deref(), cache_store).await {
+                            tracing::info!("Stage 2: Context changed while syncing, stopping.");
+                            return;
+                        }
+                        let persisted_cont_key = persisted_key(
+                            kube_ctx.name.as_deref(),
+                            &format!("{}{}/{}", PERSISTED_CONT_KEY, ns_name, pod_name),
+                        );
+                        if let Err(e) = db_store
+                            .set_json(
+                                KUCO_CACHE_TABLE.to_owned(),
+                                persisted_cont_key,
+                                &container_names,
+                            )
+                            .await
+                        {
+                            tracing::error!(
+                                "Stage 2: Failed to persist containers for {}/{}: {}",
+                                ns_name,
+                                pod_name,
+                                e
+                            );
+                        }
                         if let Err(e) = cache_store
                             .set_json(
                                 KUCO_CACHE_TABLE.to_owned(),
@@ -119,22 +304,74 @@ async fn run_stage2_sync_for_namespace<S: KucoSqliteStore + Clone + 'static>(
     }
 }
 
-pub async fn periodic_multistage_cache_sync<S: KucoSqliteStore + Clone + 'static>(
+// The TUI publishes the context it switched to through the cache. Rebuild the client whenever
+// that differs from the context this task is syncing; returns whether it did.
+async fn follow_context<S: KucoSqliteStore>(kube_ctx: &mut KubeContext, cache_store: &S) -> bool {
+    let wanted: Option<String> = cache_store
+        .get_json(
+            KUCO_CACHE_TABLE.to_owned(),
+            CURRENT_CONTEXT_CACHE_KEY.to_string(),
+        )
+        .await
+        .unwrap_or_default();
+    let Some(name) = wanted else {
+        return false;
+    };
+    if kube_ctx.name.as_ref() == Some(&name) {
+        return false;
+    }
+
+    match kube_ctx.init_named_context(&name).await {
+        Ok(_) => {
+            tracing::info!("Sync task switched to context '{}'", name);
+            true
+        }
+        Err(e) => {
+            tracing::error!("Sync task failed to switch to context '{}': {}", name, e);
+            false
+        }
+    }
+}
+
+/// A single Stage 1 pass, to fill the cache once when the periodic sync is turned off.
+pub async fn sync_once<S: KucoSqliteStore + Clone + 'static, D: KucoSqliteStore>(
+    kube_ctx: &KubeContext,
+    cache_store: &S,
+    db_store: &D,
+) -> Result<()> {
+    run_stage1_sync(kube_ctx, cache_store, db_store).await?;
+
+    Ok(())
+}
+
+// Bump the revision whoever watches the sync (the daemon) is waiting on.
+fn notify_synced(synced: &Option<watch::Sender<u64>>) {
+    if let Some(revision) = synced {
+        revision.send_modify(|r| *r += 1);
+    }
+}
+
+pub async fn periodic_multistage_cache_sync<
+    S: KucoSqliteStore + Clone + 'static,
+    D: KucoSqliteStore,
+>(
     arc_kube_ctx: Arc<KubeContext>,
     arc_cache_store: Arc<S>,
+    arc_db_store: Arc<D>,
+    intervals: SyncConfig,
+    synced: Option<watch::Sender<u64>>,
 ) {
-    let kube_client: Client = arc_kube_ctx
-        .client
-        .as_ref()
-        .expect("Kube client not initialized")
-        .clone();
+    let mut kube_ctx: KubeContext = arc_kube_ctx.as_ref().clone();
 
     // Access Arc<Cache>
     let cache_store: &S = arc_cache_store.as_ref();
+    let db_store: &D = arc_db_store.as_ref();
 
     // Set Tick Rates & Initialize Stage 2 Data
-    let mut stage1_ticker = tokio::time::interval(Duration::from_secs(5)); // Namespace/Pod names
-    let mut stage2_ticker = tokio::time::interval(Duration::from_secs(10)); // Slower full detail scan
+    let mut stage1_ticker =
+        tokio::time::interval(Duration::from_secs(intervals.namespaces_interval_secs)); // Namespace/Pod names
+    let mut stage2_ticker =
+        tokio::time::interval(Duration::from_secs(intervals.containers_interval_secs)); // Slower full detail scan
     let mut current_namespaces_for_stage2: Vec<String> = Vec::new();
     let mut stage2_ns_index = 0;
  
@@ -143,19 +380,31 @@ pub async fn periodic_multistage_cache_sync<S: KucoSqliteStore + Clone + 'static
     loop {
         tokio::select! {
             _ = stage1_ticker.tick() => {
-                match run_stage1_sync(&kube_client, cache_store).await {
-                    Ok(ns_names) => current_namespaces_for_stage2 = ns_names, // Update list for Stage 2
+                if follow_context(&mut kube_ctx, cache_store).await {
+                    stage2_ns_index = 0;
+                }
+                match run_stage1_sync(&kube_ctx, cache_store, db_store).await {
+                    Ok(ns_names) => {
+                        current_namespaces_for_stage2 = ns_names; // Update list for Stage 2
+                        notify_synced(&synced);
+                    }
                     Err(e) => tracing::error!("Stage 1 Sync failed: {:?}", e),
                 }
             }
             _ = stage2_ticker.tick() => {
+                // Namespaces of the previous cluster are dropped until Stage 1 lists the new one.
+                if follow_context(&mut kube_ctx, cache_store).await {
+                    current_namespaces_for_stage2.clear();
+                    stage2_ns_index = 0;
+                }
                 if !current_namespaces_for_stage2.is_empty() {
                     // Process one namespace per Stage 2 tick to spread the load
                     let ns_to_process = current_namespaces_for_stage2[stage2_ns_index].clone();
                     tracing::info!("Running Stage 2 Sync: Container Details for namespace '{}'", ns_to_process);
-                    run_stage2_sync_for_namespace(&kube_client, cache_store, &ns_to_process).await;
+                    run_stage2_sync_for_namespace(&kube_ctx, cache_store, db_store, &ns_to_process).await;
                     stage2_ns_index = (stage2_ns_index + 1) % current_namespaces_for_stage2.len(); // Cycle through namespaces
                      tracing::info!("Finished Stage 2 Sync for namespace '{}'", ns_to_process);
+                    notify_synced(&synced);
                 } else {
                     tracing::info!("Stage 2: No namespaces found from Stage 1 to process yet.");
                 }
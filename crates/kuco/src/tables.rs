@@ -0,0 +1,970 @@
+/*
+ * Column layouts and row builders for the views that render as tables rather than plain lists.
+ */
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+};
+
+use chrono::{Local, TimeZone, Utc};
+use kuco_k8s_backend::{
+    age::format_duration_secs,
+    autoscaling::HpaInfo,
+    discovery::ApiKind,
+    dynamic::DynamicData,
+    events::EventInfo,
+    nodes::NodeInfo,
+    pods::PodInfo,
+    policy::{LimitInfo, PdbInfo, PolicyData, QuotaUsage},
+    quantity::{format_bytes, format_cpu_millis, parse_quantity},
+    storage::{PvInfo, PvcInfo},
+};
+use kuco_sqlite_backend::AuditEntry;
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    widgets::{Cell, Row},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::ViewMode;
+use crate::data::KubeData;
+use crate::search::highlight_line;
+
+/// Per view sort order and hidden columns, persisted across restarts.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct TablePrefs {
+    /// Header of the column rows are sorted by, if any.
+    pub sort_by: Option<String>,
+    pub descending: bool,
+    pub hidden: Vec<String>,
+}
+
+impl TablePrefs {
+    /// Step the sort through `columns` in order, then back to unsorted.
+    pub fn cycle_sort(&mut self, columns: &[String]) {
+        let next = match &self.sort_by {
+            None => columns.first(),
+            Some(current) => columns
+                .iter()
+                .position(|c| c == current)
+                .and_then(|i| columns.get(i + 1)),
+        };
+        self.sort_by = next.cloned();
+    }
+}
+
+/// The key views are stored under in the table preferences.
+pub fn prefs_key(view_mode: &ViewMode) -> String {
+    format!("{:?}", view_mode)
+}
+
+pub struct TableRow {
+    /// The display list entry this row was built for.
+    pub key: String,
+    pub cells: Vec<String>,
+    pub style: Style,
+    /// Char indices to highlight in each cell, e.g. search matches. Empty when none are.
+    pub highlights: Vec<Vec<usize>>,
+    /// The number each cell sorts by, like an age in seconds rather than its "5m" text. Cells
+    /// without one sort by their text.
+    pub sort_values: Vec<Option<f64>>,
+}
+
+impl TableRow {
+    fn new(key: String, cells: Vec<String>, style: Style) -> Self {
+        Self {
+            key,
+            cells,
+            style,
+            highlights: Vec::new(),
+            sort_values: Vec::new(),
+        }
+    }
+
+    fn sort_values(mut self, values: Vec<Option<f64>>) -> Self {
+        self.sort_values = values;
+        self
+    }
+
+    /// Map char indices into the key onto the leading cells that spell it out, like the
+    /// NAMESPACE and NAME cells of a "namespace/pod" key.
+    fn highlight_key(&mut self, indices: &[usize]) {
+        let mut offset = 0;
+        let mut highlights: Vec<Vec<usize>> = Vec::new();
+        for cell in &self.cells {
+            let rest: String = self.key.chars().skip(offset).collect();
+            if cell.is_empty() || !rest.starts_with(cell.as_str()) {
+                break;
+            }
+            let len = cell.chars().count();
+            highlights.push(
+                indices
+                    .iter()
+                    .filter(|&&i| i >= offset && i < offset + len)
+                    .map(|i| i - offset)
+                    .collect(),
+            );
+            // Skip the separator between the parts of the key.
+            offset += len + 1;
+        }
+        self.highlights = highlights;
+    }
+}
+
+pub struct TableSpec {
+    pub header: Vec<String>,
+    pub widths: Vec<Constraint>,
+    pub rows: Vec<TableRow>,
+}
+
+impl TableSpec {
+    fn new(header: &[&str], widths: Vec<Constraint>, rows: Vec<TableRow>) -> Self {
+        Self {
+            header: header.iter().map(|h| h.to_string()).collect(),
+            widths,
+            rows,
+        }
+    }
+
+    /// Highlight the matched characters of each row's key, by key.
+    pub fn highlight(mut self, matches: &HashMap<String, Vec<usize>>) -> Self {
+        for row in self.rows.iter_mut() {
+            if let Some(indices) = matches.get(&row.key) {
+                row.highlight_key(indices);
+            }
+        }
+        self
+    }
+
+    /// Show marked rows reversed, which still stands out under the selection highlight.
+    pub fn mark(mut self, marks: &BTreeSet<String>) -> Self {
+        for row in self.rows.iter_mut().filter(|row| marks.contains(&row.key)) {
+            row.style = row.style.add_modifier(Modifier::REVERSED);
+        }
+        self
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.rows.iter().map(|r| r.key.clone()).collect()
+    }
+
+    /// Stable sort of the rows by the column headed `column`; unknown columns are a no-op.
+    pub fn sort_by(&mut self, column: &str, descending: bool) {
+        let Some(index) = self.header.iter().position(|h| h == column) else {
+            return;
+        };
+
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_cells(a, b, index);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+
+    /// Drop hidden columns (the first column always stays) and mark the sorted one.
+    pub fn apply_prefs(mut self, prefs: &TablePrefs) -> Self {
+        if let Some(sort_by) = &prefs.sort_by {
+            if let Some(header) = self.header.iter_mut().find(|h| *h == sort_by) {
+                header.push_str(if prefs.descending { " ▼" } else { " ▲" });
+            }
+        }
+
+        let visible: Vec<bool> = self
+            .header
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                i == 0
+                    || !prefs
+                        .hidden
+                        .iter()
+                        .any(|hidden| h.trim_end_matches([' ', '▲', '▼']) == hidden)
+            })
+            .collect();
+        let keep = |i: &usize| visible.get(*i).copied().unwrap_or(true);
+
+        self.header = self
+            .header
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| keep(i))
+            .map(|(_, h)| h)
+            .collect();
+        self.widths = self
+            .widths
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| keep(i))
+            .map(|(_, w)| w)
+            .collect();
+        for row in self.rows.iter_mut() {
+            row.cells = std::mem::take(&mut row.cells)
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| keep(i))
+                .map(|(_, c)| c)
+                .collect();
+            row.highlights = std::mem::take(&mut row.highlights)
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| keep(i))
+                .map(|(_, h)| h)
+                .collect();
+            row.sort_values = std::mem::take(&mut row.sort_values)
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| keep(i))
+                .map(|(_, v)| v)
+                .collect();
+        }
+
+        self
+    }
+
+    pub fn into_rows(self) -> (Vec<String>, Vec<Constraint>, Vec<Row<'static>>) {
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|r| {
+                let cells: Vec<Cell> = r
+                    .cells
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cell)| match r.highlights.get(i) {
+                        Some(indices) => Cell::from(highlight_line(cell, indices, r.style)),
+                        None => Cell::from(cell.clone()),
+                    })
+                    .collect();
+                Row::new(cells).style(r.style)
+            })
+            .collect();
+
+        (self.header, self.widths, rows)
+    }
+}
+
+/// Order the cells at `index` of two rows by their sort values when both have one; numbers
+/// sort before text, text sorts lexically.
+fn compare_cells(a: &TableRow, b: &TableRow, index: usize) -> Ordering {
+    let value = |row: &TableRow| row.sort_values.get(index).copied().flatten();
+    let text = |row: &TableRow| row.cells.get(index).cloned().unwrap_or_default();
+
+    match (value(a), value(b)) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => text(a).cmp(&text(b)),
+    }
+}
+
+/// Build the table for `view_mode`, with one row per entry of `display_list` (which may have
+/// been narrowed by a search), or `None` if the view renders as a plain list.
+pub fn table_for_view(
+    view_mode: &ViewMode,
+    data: &KubeData,
+    display_list: &[String],
+) -> Option<TableSpec> {
+    match view_mode {
+        ViewMode::PODS => {
+            let now = Utc::now().timestamp();
+            Some(TableSpec::new(
+                &["NAME", "READY", "STATUS", "RESTARTS", "AGE", "NODE", "IP"],
+                vec![
+                    Constraint::Fill(3),
+                    Constraint::Length(6),
+                    Constraint::Length(26),
+                    Constraint::Length(9),
+                    Constraint::Length(6),
+                    Constraint::Fill(2),
+                    Constraint::Length(16),
+                ],
+                display_list
+                    .iter()
+                    .map(
+                        |name| match data.pod_rows_list.iter().find(|p| &p.name == name) {
+                            Some(pod) => pod_row(pod, now),
+                            // Names are synced alongside rows, but fall back to the bare name so a
+                            // pod never drops out of the list while its row is missing.
+                            None => TableRow::new(
+                                name.clone(),
+                                vec![name.clone()],
+                                Style::new().fg(Color::Magenta),
+                            ),
+                        },
+                    )
+                    .collect(),
+            ))
+        }
+        ViewMode::ALLPODS => {
+            let now = Utc::now().timestamp();
+            Some(TableSpec::new(
+                &[
+                    "NAMESPACE",
+                    "NAME",
+                    "READY",
+                    "STATUS",
+                    "RESTARTS",
+                    "AGE",
+                    "NODE",
+                    "IP",
+                ],
+                vec![
+                    Constraint::Fill(2),
+                    Constraint::Fill(3),
+                    Constraint::Length(6),
+                    Constraint::Length(26),
+                    Constraint::Length(9),
+                    Constraint::Length(6),
+                    Constraint::Fill(2),
+                    Constraint::Length(16),
+                ],
+                display_list
+                    .iter()
+                    .filter_map(|key| {
+                        let (ns, name) = key.split_once('/')?;
+                        let pod = data
+                            .all_pod_rows_list
+                            .iter()
+                            .find(|p| p.namespace == ns && p.name == name)?;
+
+                        let mut row = pod_row(pod, now);
+                        row.key = key.clone();
+                        row.cells.insert(0, pod.namespace.clone());
+                        row.sort_values.insert(0, None);
+                        Some(row)
+                    })
+                    .collect(),
+            ))
+        }
+        ViewMode::NODES => Some(TableSpec::new(
+            &[
+                "NAME", "STATUS", "ROLES", "VERSION", "CPU", "MEMORY", "PODS", "PRESSURE", "TAINTS",
+            ],
+            vec![
+                Constraint::Fill(3),
+                Constraint::Length(26),
+                Constraint::Fill(1),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(18),
+                Constraint::Length(5),
+                Constraint::Length(12),
+                Constraint::Fill(2),
+            ],
+            display_list
+                .iter()
+                .filter_map(|name| data.node_list.iter().find(|n| &n.name == name))
+                .map(node_row)
+                .collect(),
+        )),
+        ViewMode::KINDS => Some(TableSpec::new(
+            &["NAME", "KIND", "APIVERSION", "NAMESPACED"],
+            vec![
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(10),
+            ],
+            display_list
+                .iter()
+                .filter_map(|key| data.api_kinds.iter().find(|k| &k.key() == key))
+                .map(kind_row)
+                .collect(),
+        )),
+        ViewMode::RESOURCES => Some(resources_table(&data.dynamic, display_list)),
+        ViewMode::PVCS => Some(TableSpec::new(
+            &[
+                "NAME",
+                "STATUS",
+                "VOLUME",
+                "CAPACITY",
+                "ACCESS",
+                "STORAGECLASS",
+                "MOUNTED BY",
+            ],
+            vec![
+                Constraint::Fill(2),
+                Constraint::Length(8),
+                Constraint::Fill(2),
+                Constraint::Length(9),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+            ],
+            display_list
+                .iter()
+                .filter_map(|name| data.storage.claims.iter().find(|c| &c.name == name))
+                .map(pvc_row)
+                .collect(),
+        )),
+        ViewMode::PVS => Some(TableSpec::new(
+            &[
+                "NAME",
+                "CAPACITY",
+                "ACCESS",
+                "RECLAIM",
+                "STATUS",
+                "CLAIM",
+                "STORAGECLASS",
+            ],
+            vec![
+                Constraint::Fill(2),
+                Constraint::Length(9),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+            ],
+            display_list
+                .iter()
+                .filter_map(|name| data.storage.volumes.iter().find(|v| &v.name == name))
+                .map(pv_row)
+                .collect(),
+        )),
+        ViewMode::EVENTS => {
+            let now = Utc::now().timestamp();
+            Some(TableSpec::new(
+                &["LAST SEEN", "TYPE", "REASON", "OBJECT", "COUNT", "MESSAGE"],
+                vec![
+                    Constraint::Length(9),
+                    Constraint::Length(8),
+                    Constraint::Length(20),
+                    Constraint::Fill(2),
+                    Constraint::Length(6),
+                    Constraint::Fill(5),
+                ],
+                display_list
+                    .iter()
+                    .filter_map(|key| data.events.list.iter().find(|e| &e.key() == key))
+                    .map(|e| event_row(e, now))
+                    .collect(),
+            ))
+        }
+        ViewMode::HPAS => Some(TableSpec::new(
+            &[
+                "NAME",
+                "REFERENCE",
+                "MIN",
+                "MAX",
+                "CURRENT",
+                "DESIRED",
+                "METRICS",
+                "CONDITIONS",
+            ],
+            vec![
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(4),
+                Constraint::Length(4),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+            ],
+            display_list
+                .iter()
+                .filter_map(|name| data.hpas.list.iter().find(|h| &h.name == name))
+                .map(hpa_row)
+                .collect(),
+        )),
+        ViewMode::AUDIT => Some(TableSpec::new(
+            &[
+                "TIME",
+                "CONTEXT",
+                "ACTION",
+                "OBJECT",
+                "PARAMETERS",
+                "OUTCOME",
+            ],
+            vec![
+                Constraint::Length(19),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+            ],
+            display_list
+                .iter()
+                .filter_map(|key| data.audit.iter().find(|e| &e.key() == key))
+                .map(audit_row)
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+fn audit_row(entry: &AuditEntry) -> TableRow {
+    let style = if entry.succeeded() {
+        Style::new().fg(Color::Magenta)
+    } else {
+        Style::new().fg(Color::Red)
+    };
+    let time = Local
+        .timestamp_opt(entry.at, 0)
+        .single()
+        .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    TableRow::new(
+        entry.key(),
+        vec![
+            time,
+            entry.context.clone(),
+            entry.action.clone(),
+            format!("{}/{}", entry.kind.to_lowercase(), entry.object()),
+            entry.parameters.clone(),
+            entry.outcome.clone(),
+        ],
+        style,
+    )
+    .sort_values(vec![Some(entry.at as f64)])
+}
+
+fn pod_row(pod: &PodInfo, now: i64) -> TableRow {
+    let style = if pod.is_healthy() {
+        Style::new().fg(Color::Magenta)
+    } else if pod.is_progressing() {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new().fg(Color::Red)
+    };
+
+    let age = pod.created_at.map(|created| now - created);
+
+    TableRow::new(
+        pod.name.clone(),
+        vec![
+            pod.name.clone(),
+            format!("{}/{}", pod.ready_containers, pod.total_containers),
+            pod.status.clone(),
+            pod.restarts.to_string(),
+            age.map(format_duration_secs).unwrap_or_default(),
+            pod.node.clone(),
+            pod.ip.clone(),
+        ],
+        style,
+    )
+    .sort_values(vec![
+        None,
+        Some(pod.ready_containers as f64),
+        None,
+        Some(pod.restarts as f64),
+        age.map(|secs| secs as f64),
+    ])
+}
+
+fn node_row(node: &NodeInfo) -> TableRow {
+    let mut status = if node.ready == "True" {
+        "Ready".to_owned()
+    } else {
+        "NotReady".to_owned()
+    };
+    if node.unschedulable {
+        status += ",SchedulingDisabled";
+    }
+
+    let pressure: Vec<&str> = [
+        ("Memory", &node.memory_pressure),
+        ("Disk", &node.disk_pressure),
+    ]
+    .into_iter()
+    .filter(|(_, condition)| condition.as_str() == "True")
+    .map(|(kind, _)| kind)
+    .collect();
+
+    let roles = if node.roles.is_empty() {
+        "<none>".to_owned()
+    } else {
+        node.roles.join(",")
+    };
+
+    let style = if node.ready != "True" {
+        Style::new().fg(Color::Red)
+    } else if node.unschedulable || !pressure.is_empty() {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new().fg(Color::Magenta)
+    };
+
+    TableRow::new(
+        node.name.clone(),
+        vec![
+            node.name.clone(),
+            status,
+            roles,
+            node.kubelet_version.clone(),
+            format!(
+                "{}/{}",
+                format_cpu_millis(node.requested_cpu_millis),
+                format_cpu_millis(node.allocatable_cpu_millis)
+            ),
+            format!(
+                "{}/{}",
+                format_bytes(node.requested_memory_bytes),
+                format_bytes(node.allocatable_memory_bytes)
+            ),
+            node.pod_count.to_string(),
+            if pressure.is_empty() {
+                "-".to_owned()
+            } else {
+                pressure.join(",")
+            },
+            node.taints.join(" "),
+        ],
+        style,
+    )
+    .sort_values(vec![
+        None,
+        None,
+        None,
+        None,
+        Some(node.requested_cpu_millis as f64),
+        Some(node.requested_memory_bytes as f64),
+        Some(node.pod_count as f64),
+    ])
+}
+
+fn kind_row(kind: &ApiKind) -> TableRow {
+    TableRow::new(
+        kind.key(),
+        vec![
+            kind.key(),
+            kind.kind.clone(),
+            kind.api_version.clone(),
+            kind.namespaced.to_string(),
+        ],
+        Style::new().fg(Color::Magenta),
+    )
+}
+
+fn resources_table(dynamic: &DynamicData, display_list: &[String]) -> TableSpec {
+    let mut header = vec!["NAME".to_owned()];
+    header.extend(dynamic.columns.iter().cloned());
+    let mut widths = vec![Constraint::Fill(3)];
+    widths.extend(dynamic.columns.iter().map(|_| Constraint::Fill(1)));
+
+    let rows = display_list
+        .iter()
+        .filter_map(|name| dynamic.rows.iter().find(|r| &r.name == name))
+        .map(|r| {
+            let mut cells = vec![r.name.clone()];
+            cells.extend(r.cells.iter().cloned());
+            // The cells are the objects' own values, so numbers and quantities parse as is.
+            let values = cells.iter().map(|cell| parse_quantity(cell)).collect();
+            TableRow::new(r.name.clone(), cells, Style::new().fg(Color::Magenta))
+                .sort_values(values)
+        })
+        .collect();
+
+    TableSpec {
+        header,
+        widths,
+        rows,
+    }
+}
+
+fn pvc_row(claim: &PvcInfo) -> TableRow {
+    let style = match claim.phase.as_str() {
+        "Bound" => Style::new().fg(Color::Magenta),
+        "Pending" => Style::new().fg(Color::Yellow),
+        _ => Style::new().fg(Color::Red),
+    };
+
+    TableRow::new(
+        claim.name.clone(),
+        vec![
+            claim.name.clone(),
+            claim.phase.clone(),
+            claim.volume.clone(),
+            claim.capacity.clone(),
+            claim.access_modes.join(","),
+            claim.storage_class.clone(),
+            if claim.mounted_by.is_empty() {
+                "<none>".to_owned()
+            } else {
+                claim.mounted_by.join(",")
+            },
+        ],
+        style,
+    )
+    .sort_values(vec![None, None, None, parse_quantity(&claim.capacity)])
+}
+
+fn pv_row(volume: &PvInfo) -> TableRow {
+    let style = match volume.phase.as_str() {
+        "Bound" | "Available" => Style::new().fg(Color::Magenta),
+        "Released" | "Pending" => Style::new().fg(Color::Yellow),
+        _ => Style::new().fg(Color::Red),
+    };
+
+    TableRow::new(
+        volume.name.clone(),
+        vec![
+            volume.name.clone(),
+            volume.capacity.clone(),
+            volume.access_modes.join(","),
+            volume.reclaim_policy.clone(),
+            volume.phase.clone(),
+            volume.claim.clone().unwrap_or_default(),
+            volume.storage_class.clone(),
+        ],
+        style,
+    )
+    .sort_values(vec![None, parse_quantity(&volume.capacity)])
+}
+
+fn event_row(event: &EventInfo, now: i64) -> TableRow {
+    let style = if event.is_warning() {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new().fg(Color::Magenta)
+    };
+
+    TableRow::new(
+        event.key(),
+        vec![
+            format_duration_secs(now - event.last_seen),
+            event.type_.clone(),
+            event.reason.clone(),
+            format!("{}/{}", event.object_kind.to_lowercase(), event.object_name),
+            event.count.to_string(),
+            event.message.clone(),
+        ],
+        style,
+    )
+    .sort_values(vec![
+        Some((now - event.last_seen) as f64),
+        None,
+        None,
+        None,
+        Some(event.count as f64),
+    ])
+}
+
+/// Conditions worth calling out: AbleToScale/ScalingActive when they are not "True", and
+/// ScalingLimited when it is.
+fn hpa_problem_conditions(hpa: &HpaInfo) -> Vec<String> {
+    let mut problems: Vec<String> = ["AbleToScale", "ScalingActive"]
+        .into_iter()
+        .filter(|type_| {
+            hpa.conditions.iter().any(|c| &c.type_ == type_) && !hpa.condition_is(type_)
+        })
+        .map(|type_| format!("!{}", type_))
+        .collect();
+    if hpa.condition_is("ScalingLimited") {
+        problems.push("ScalingLimited".to_owned());
+    }
+
+    problems
+}
+
+fn hpa_row(hpa: &HpaInfo) -> TableRow {
+    let problems = hpa_problem_conditions(hpa);
+    let style = if problems.iter().any(|p| p.starts_with('!')) {
+        Style::new().fg(Color::Red)
+    } else if !problems.is_empty() {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new().fg(Color::Magenta)
+    };
+
+    TableRow::new(
+        hpa.name.clone(),
+        vec![
+            hpa.name.clone(),
+            format!("{}/{}", hpa.target_kind, hpa.target_name),
+            hpa.min_replicas.to_string(),
+            hpa.max_replicas.to_string(),
+            hpa.current_replicas.to_string(),
+            hpa.desired_replicas.to_string(),
+            hpa.metrics
+                .iter()
+                .map(|m| format!("{} {}/{}", m.name, m.current, m.target))
+                .collect::<Vec<String>>()
+                .join(", "),
+            if problems.is_empty() {
+                "-".to_owned()
+            } else {
+                problems.join(",")
+            },
+        ],
+        style,
+    )
+    .sort_values(vec![
+        None,
+        None,
+        Some(hpa.min_replicas as f64),
+        Some(hpa.max_replicas as f64),
+        Some(hpa.current_replicas as f64),
+        Some(hpa.desired_replicas as f64),
+    ])
+}
+
+/// The namespace policy panel: one titled table each for quota usage, LimitRange defaults and
+/// PodDisruptionBudgets, narrowed to the entries in `display_list`.
+pub fn policy_tables(
+    policy: &PolicyData,
+    display_list: &[String],
+) -> Vec<(&'static str, TableSpec)> {
+    let shown = |key: String| display_list.contains(&key);
+
+    vec![
+        (
+            "Resource Quotas",
+            TableSpec::new(
+                &["QUOTA", "RESOURCE", "USED", "HARD", "USAGE"],
+                vec![
+                    Constraint::Fill(2),
+                    Constraint::Fill(2),
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                    Constraint::Length(18),
+                ],
+                policy
+                    .quotas
+                    .iter()
+                    .filter(|q| shown(q.key()))
+                    .map(quota_row)
+                    .collect(),
+            ),
+        ),
+        (
+            "Limit Ranges",
+            TableSpec::new(
+                &[
+                    "LIMITRANGE",
+                    "TYPE",
+                    "RESOURCE",
+                    "DEFAULT REQUEST",
+                    "DEFAULT LIMIT",
+                    "MIN",
+                    "MAX",
+                ],
+                vec![
+                    Constraint::Fill(2),
+                    Constraint::Length(22),
+                    Constraint::Fill(1),
+                    Constraint::Length(16),
+                    Constraint::Length(14),
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                ],
+                policy
+                    .limits
+                    .iter()
+                    .filter(|l| shown(l.key()))
+                    .map(limit_row)
+                    .collect(),
+            ),
+        ),
+        (
+            "Pod Disruption Budgets",
+            TableSpec::new(
+                &[
+                    "PDB",
+                    "MIN AVAILABLE",
+                    "MAX UNAVAILABLE",
+                    "ALLOWED DISRUPTIONS",
+                    "HEALTHY",
+                    "EXPECTED",
+                ],
+                vec![
+                    Constraint::Fill(2),
+                    Constraint::Length(14),
+                    Constraint::Length(16),
+                    Constraint::Length(20),
+                    Constraint::Length(10),
+                    Constraint::Length(9),
+                ],
+                policy
+                    .pdbs
+                    .iter()
+                    .filter(|p| shown(p.key()))
+                    .map(pdb_row)
+                    .collect(),
+            ),
+        ),
+    ]
+}
+
+/// A ten cell wide text bar for a usage ratio, e.g. "██████░░░░  62%".
+fn usage_bar(ratio: f64) -> String {
+    const WIDTH: usize = 10;
+    let filled = ((ratio.clamp(0.0, 1.0) * WIDTH as f64).round() as usize).min(WIDTH);
+
+    format!(
+        "{}{} {:>3.0}%",
+        "█".repeat(filled),
+        "░".repeat(WIDTH - filled),
+        (ratio * 100.0).min(999.0)
+    )
+}
+
+fn quota_row(usage: &QuotaUsage) -> TableRow {
+    let style = match usage.ratio {
+        Some(r) if r >= 1.0 => Style::new().fg(Color::Red),
+        Some(r) if r >= 0.8 => Style::new().fg(Color::Yellow),
+        _ => Style::new().fg(Color::Magenta),
+    };
+
+    TableRow::new(
+        usage.key(),
+        vec![
+            usage.quota.clone(),
+            usage.resource.clone(),
+            usage.used.clone(),
+            usage.hard.clone(),
+            usage.ratio.map(usage_bar).unwrap_or("-".to_owned()),
+        ],
+        style,
+    )
+}
+
+fn limit_row(limit: &LimitInfo) -> TableRow {
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or("-".to_owned());
+
+    TableRow::new(
+        limit.key(),
+        vec![
+            limit.limit_range.clone(),
+            limit.type_.clone(),
+            limit.resource.clone(),
+            or_dash(&limit.default_request),
+            or_dash(&limit.default),
+            or_dash(&limit.min),
+            or_dash(&limit.max),
+        ],
+        Style::new().fg(Color::Magenta),
+    )
+}
+
+fn pdb_row(pdb: &PdbInfo) -> TableRow {
+    // No allowed disruptions means every eviction (and so every drain) will be refused.
+    let style = if pdb.current_healthy < pdb.desired_healthy {
+        Style::new().fg(Color::Red)
+    } else if pdb.disruptions_allowed == 0 {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new().fg(Color::Magenta)
+    };
+
+    TableRow::new(
+        pdb.key(),
+        vec![
+            pdb.name.clone(),
+            pdb.min_available.clone().unwrap_or("N/A".to_owned()),
+            pdb.max_unavailable.clone().unwrap_or("N/A".to_owned()),
+            pdb.disruptions_allowed.to_string(),
+            format!("{}/{}", pdb.current_healthy, pdb.desired_healthy),
+            pdb.expected_pods.to_string(),
+        ],
+        style,
+    )
+}
@@ -1,6 +1,6 @@
 use color_eyre::Result;
 use color_eyre::eyre::Context;
-use std::fs::File;
+use std::{fs::File, path::Path};
 use tracing::Level;
 use tracing_appender::non_blocking;
 use tracing_appender::non_blocking::WorkerGuard;
@@ -8,12 +8,14 @@ use tracing_subscriber::EnvFilter;
 
 /// Initialize the tracing subscriber to log to a file
 ///
-/// This function initializes the tracing subscriber to log to a file named `tracing.log` in the
-/// current directory. The function returns a [`WorkerGuard`] that must be kept alive for the
-/// duration of the program to ensure that logs are flushed to the file on shutdown. The logs are
-/// written in a non-blocking fashion to ensure that the logs do not block the main thread.
-pub fn init_tracing() -> Result<WorkerGuard> {
-    let file = File::create("logs/tracing.log").wrap_err("failed to create tracing.log")?;
+/// This function initializes the tracing subscriber to log to the file at `path`, by default
+/// `logs/tracing.log` in the current directory. The function returns a [`WorkerGuard`] that must
+/// be kept alive for the duration of the program to ensure that logs are flushed to the file on
+/// shutdown. The logs are written in a non-blocking fashion to ensure that the logs do not block
+/// the main thread.
+pub fn init_tracing(path: &Path) -> Result<WorkerGuard> {
+    let file = File::create(path)
+        .wrap_err_with(|| format!("failed to create log file {}", path.display()))?;
     let (non_blocking, guard) = non_blocking(file);
 
     // By default, the subscriber is configured to log all events with a level of `DEBUG` or higher,
@@ -1,15 +1,24 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use kuco_sqlite_backend::SqliteCache;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
-    widgets::{Block, HighlightSpacing, List, ListDirection, StatefulWidget},
+    text::{Line, Text},
+    widgets::{
+        Block, HighlightSpacing, List, ListDirection, ListItem, Paragraph, Row, StatefulWidget,
+        Table, TableState, Widget, Wrap,
+    },
 };
 
 use crate::app::{InteractionMode, ViewMode};
-use crate::data::{KubeComponentState, KubeData};
+use crate::data::{KubeComponentState, KubeData, PodRef};
+use crate::search::highlight_line;
+use crate::tables::{TablePrefs, TableSpec, policy_tables, prefs_key, table_for_view};
 
 #[derive(Clone)]
 pub struct KubeWidget {
@@ -17,6 +26,14 @@ pub struct KubeWidget {
     pub view_mode: ViewMode,
     pub interact_mode: InteractionMode,
     pub data: KubeData,
+    /// View to go back to when leaving a view that can be opened from anywhere.
+    pub return_view_mode: Option<ViewMode>,
+    /// Entry to select in the display list once the next view is shown, e.g. after a jump.
+    pub pending_selection: Option<String>,
+    /// Sort order and hidden columns of each table view, by [`prefs_key`].
+    pub table_prefs: HashMap<String, TablePrefs>,
+    /// Char indices of the characters each display entry matched the search with.
+    pub search_matches: HashMap<String, Vec<usize>>,
 }
 
 impl KubeWidget {
@@ -26,9 +43,117 @@ impl KubeWidget {
             view_mode: ViewMode::NS,
             interact_mode: InteractionMode::NORMAL,
             data: KubeData::new(arc_ctx).await,
+            return_view_mode: None,
+            pending_selection: None,
+            table_prefs: HashMap::new(),
+            search_matches: HashMap::new(),
         }
     }
 
+    pub fn current_table_prefs(&self) -> TablePrefs {
+        self.table_prefs
+            .get(&prefs_key(&self.view_mode))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The pod a display entry of a pod view stands for. Only the namespaced pods view keys its
+    /// entries by bare pod name; the others list them as `namespace/pod`.
+    pub fn pod_ref(&self, key: &str) -> Option<PodRef> {
+        let (namespace, name) = match self.view_mode {
+            ViewMode::PODS => (
+                self.data
+                    .current_namespace_name
+                    .clone()
+                    .unwrap_or("default".to_owned()),
+                key.to_owned(),
+            ),
+            ViewMode::NODEPODS | ViewMode::ALLPODS => {
+                let (namespace, name) = key.split_once('/')?;
+                (namespace.to_owned(), name.to_owned())
+            }
+            _ => return None,
+        };
+        Some(PodRef {
+            context: self.data.context_name().unwrap_or_default().to_owned(),
+            namespace,
+            name,
+        })
+    }
+
+    /// Every pod the current pod view lists, whether or not a search hides it.
+    pub fn listed_pods(&self) -> Vec<PodRef> {
+        let keys = match self.view_mode {
+            ViewMode::PODS => &self.data.pod_names_list,
+            ViewMode::NODEPODS => &self.data.node_pods_list,
+            ViewMode::ALLPODS => &self.data.get_all_pods(),
+            _ => return Vec::new(),
+        };
+        keys.iter().filter_map(|key| self.pod_ref(key)).collect()
+    }
+
+    /// Reorder the display list by the current view's sort column, or back into the order the
+    /// entries were listed in when it has none.
+    pub fn sort_display(&mut self) {
+        let prefs = self.current_table_prefs();
+        let Some(sort_by) = prefs.sort_by else {
+            self.unsort_display();
+            return;
+        };
+        let Some(display) = self.display.as_ref() else {
+            return;
+        };
+        let Some(mut table) = table_for_view(&self.view_mode, &self.data, display) else {
+            return;
+        };
+
+        table.sort_by(&sort_by, prefs.descending);
+        let mut sorted = table.keys();
+        // Entries without a row yet keep their place at the end rather than disappearing.
+        sorted.extend(
+            display
+                .iter()
+                .filter(|entry| !table.rows.iter().any(|r| &r.key == *entry))
+                .cloned(),
+        );
+        self.display = Some(sorted);
+    }
+
+    // Only the table views can be sorted, so only they need their listed order back.
+    fn unsort_display(&mut self) {
+        let listed = match self.view_mode {
+            ViewMode::PODS => self.data.get_pods(),
+            ViewMode::ALLPODS => self.data.get_all_pods(),
+            ViewMode::NODES => self.data.get_nodes(),
+            ViewMode::KINDS => self.data.get_kinds(),
+            ViewMode::RESOURCES => self.data.get_resources(),
+            ViewMode::PVCS => self.data.get_claims(),
+            ViewMode::PVS => self.data.get_volumes(),
+            ViewMode::EVENTS => self.data.get_events(),
+            ViewMode::HPAS => self.data.get_hpas(),
+            ViewMode::AUDIT => self.data.get_audit(),
+            _ => return,
+        };
+        let Some(display) = self.display.as_ref() else {
+            return;
+        };
+
+        // The display may be narrowed by a search, which the listed order must not undo.
+        let shown: HashSet<&String> = display.iter().collect();
+        let mut unsorted: Vec<String> = listed
+            .into_iter()
+            .filter(|entry| shown.contains(entry))
+            .collect();
+        let listed: HashSet<&String> = unsorted.iter().collect();
+        let missing: Vec<String> = display
+            .iter()
+            .filter(|entry| !listed.contains(entry))
+            .cloned()
+            .collect();
+        unsorted.extend(missing);
+        self.display = Some(unsorted);
+    }
+
     pub async fn update_widget_kube_data(&mut self) {
         self.data.update_context().await;
 
@@ -51,10 +176,201 @@ impl KubeWidget {
                 self.data.update_logs_lines_list().await;
                 self.display = Some(self.data.get_logs());
             }
+            ViewMode::NODES => {
+                let _ = self.data.update_nodes_list().await;
+                self.display = Some(self.data.get_nodes());
+            }
+            ViewMode::NODEPODS => {
+                self.data.update_node_pods_list().await;
+                self.display = Some(self.data.get_node_pods());
+            }
+            ViewMode::ALLPODS => {
+                if let Err(e) = self.data.update_all_pods_list().await {
+                    tracing::error!("Failed to update the all pods list: {}", e);
+                }
+                self.display = Some(self.data.get_all_pods());
+            }
+            ViewMode::KINDS => {
+                self.data.update_api_kinds_list().await;
+                self.display = Some(self.data.get_kinds());
+            }
+            ViewMode::RESOURCES => {
+                self.data.update_resources_list().await;
+                self.display = Some(self.data.get_resources());
+            }
+            ViewMode::PVCS => {
+                self.data.update_claims_list().await;
+                self.display = Some(self.data.get_claims());
+            }
+            ViewMode::PVS => {
+                self.data.update_volumes_list().await;
+                self.display = Some(self.data.get_volumes());
+            }
+            ViewMode::EVENTS => {
+                self.data.update_events_list().await;
+                self.display = Some(self.data.get_events());
+            }
+            ViewMode::HPAS => {
+                self.data.update_hpas_list().await;
+                self.display = Some(self.data.get_hpas());
+            }
+            ViewMode::POLICY => {
+                self.data.update_policy().await;
+                self.display = Some(self.data.get_policy());
+            }
+            // Loaded from the persistent DB by the app, which holds it.
+            ViewMode::AUDIT => self.display = Some(self.data.get_audit()),
         }
+
+        self.sort_display();
     }
 }
 
+/// Extra detail about the selected row, shown in a panel under the table.
+fn detail_panel(
+    view_mode: &ViewMode,
+    data: &KubeData,
+    name: &str,
+) -> Option<(&'static str, Vec<Line<'static>>)> {
+    let warn = Style::new().fg(Color::Yellow);
+    let normal = Style::new().fg(Color::Magenta);
+
+    match view_mode {
+        // A claim stuck in Pending gets its Events shown, since they usually explain why (no
+        // matching class, provisioner errors, topology conflicts ...).
+        ViewMode::PVCS => {
+            let claim = data
+                .storage
+                .claims
+                .iter()
+                .find(|c| c.name == name)
+                .filter(|claim| claim.phase == "Pending")?;
+
+            let lines = if claim.events.is_empty() {
+                vec![Line::styled(
+                    "Pending, no events recorded for this claim.",
+                    warn,
+                )]
+            } else {
+                claim
+                    .events
+                    .iter()
+                    .map(|e| Line::styled(e.clone(), warn))
+                    .collect()
+            };
+            Some(("Events", lines))
+        }
+        ViewMode::HPAS => {
+            let hpa = data.hpas.list.iter().find(|h| h.name == name)?;
+
+            let mut lines: Vec<Line> = hpa
+                .metrics
+                .iter()
+                .map(|m| Line::styled(format!("{}: {} / {}", m.name, m.current, m.target), normal))
+                .collect();
+            lines.extend(hpa.conditions.iter().map(|c| {
+                let problem = match c.type_.as_str() {
+                    "ScalingLimited" => c.status == "True",
+                    _ => c.status != "True",
+                };
+                Line::styled(
+                    format!("{} {}  {}: {}", c.type_, c.status, c.reason, c.message),
+                    if problem { warn } else { normal },
+                )
+            }));
+            Some(("Metrics & Conditions", lines))
+        }
+        // Parameters and errors rarely fit their table cells.
+        ViewMode::AUDIT => {
+            let entry = data.audit.iter().find(|e| e.key() == name)?;
+
+            let outcome = if entry.succeeded() {
+                Line::styled(format!("outcome: {}", entry.outcome), normal)
+            } else {
+                Line::styled(format!("outcome: {}", entry.outcome), warn)
+            };
+            let lines = vec![
+                Line::styled(format!("parameters: {}", entry.parameters), normal),
+                outcome,
+            ];
+            Some(("Details", lines))
+        }
+        _ => None,
+    }
+}
+
+/// Stack the policy tables top to bottom, each sized to its rows. Sections with nothing in
+/// them still get a line, so an absent quota is distinguishable from one not loaded yet.
+fn render_policy_panel(sections: Vec<(&'static str, TableSpec)>, area: Rect, buf: &mut Buffer) {
+    let mut constraints: Vec<Constraint> = sections
+        .iter()
+        .map(|(_, table)| Constraint::Length(table.rows.len().max(1) as u16 + 3))
+        .collect();
+    constraints.push(Constraint::Fill(1));
+    let areas = Layout::vertical(constraints).split(area);
+
+    for ((title, table), section_area) in sections.into_iter().zip(areas.iter()) {
+        let block = Block::default()
+            .title(title)
+            .title_style(Style::new().bold());
+
+        if table.rows.is_empty() {
+            Paragraph::new(Line::styled("<none>", Style::new().fg(Color::Gray)))
+                .block(block)
+                .render(*section_area, buf);
+            continue;
+        }
+
+        let (header, widths, rows) = table.into_rows();
+        Widget::render(
+            Table::new(rows, widths)
+                .header(Row::new(header).style(Style::new().bold().fg(Color::Gray)))
+                .block(block),
+            *section_area,
+            buf,
+        );
+    }
+}
+
+/// Render a table anchored to the bottom of `area`, keeping the BottomToTop ordering and
+/// Up/Down semantics of the list views: index 0 is the bottom row.
+fn render_bottom_to_top_table(
+    table: TableSpec,
+    area: Rect,
+    buf: &mut Buffer,
+    state: &mut KubeComponentState,
+) {
+    if state.list_state.selected().is_none() {
+        state.list_state.select_first();
+    }
+
+    let (header, widths, mut rows) = table.into_rows();
+    let row_count = rows.len();
+    rows.reverse();
+
+    let height = (row_count as u16 + 1).min(area.height);
+    let table_area = Rect {
+        y: area.y + area.height - height,
+        height,
+        ..area
+    };
+
+    let selected = state
+        .list_state
+        .selected()
+        .filter(|_| row_count > 0)
+        .map(|i| row_count - 1 - i.min(row_count - 1));
+    let mut table_state = TableState::default().with_selected(selected);
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::new().bold().fg(Color::Gray)))
+        .block(Block::default().title_alignment(Alignment::Left))
+        .row_highlight_style(Style::default().bold().white().on_black())
+        .highlight_spacing(HighlightSpacing::Always);
+
+    StatefulWidget::render(table, table_area, buf, &mut table_state);
+}
+
 impl StatefulWidget for KubeWidget {
     type State = KubeComponentState;
 
@@ -64,13 +380,46 @@ impl StatefulWidget for KubeWidget {
 
         let block = Block::default().title_alignment(Alignment::Left);
 
-        let mut display_list;
+        let display_list;
         if self.display.clone().unwrap().is_empty() {
             match self.view_mode {
-                ViewMode::NS => display_list = self.data.namespace_names_list,
-                ViewMode::PODS => display_list = self.data.pod_names_list,
-                ViewMode::CONT => display_list = self.data.containers.names,
-                ViewMode::LOGS => display_list = self.data.logs.lines,
+                ViewMode::NS => display_list = self.data.namespace_names_list.clone(),
+                ViewMode::PODS => display_list = self.data.pod_names_list.clone(),
+                ViewMode::CONT => display_list = self.data.containers.names.clone(),
+                ViewMode::LOGS => display_list = self.data.logs.lines.clone(),
+                ViewMode::NODES => {
+                    display_list = self.data.node_list.iter().map(|n| n.name.clone()).collect()
+                }
+                ViewMode::NODEPODS => display_list = self.data.node_pods_list.clone(),
+                ViewMode::ALLPODS => display_list = self.data.get_all_pods(),
+                ViewMode::KINDS => {
+                    display_list = self.data.api_kinds.iter().map(|k| k.key()).collect()
+                }
+                ViewMode::RESOURCES => display_list = self.data.dynamic.names.clone(),
+                ViewMode::PVCS => {
+                    display_list = self
+                        .data
+                        .storage
+                        .claims
+                        .iter()
+                        .map(|c| c.name.clone())
+                        .collect()
+                }
+                ViewMode::PVS => {
+                    display_list = self
+                        .data
+                        .storage
+                        .volumes
+                        .iter()
+                        .map(|v| v.name.clone())
+                        .collect()
+                }
+                ViewMode::EVENTS => display_list = self.data.get_events(),
+                ViewMode::HPAS => {
+                    display_list = self.data.hpas.list.iter().map(|h| h.name.clone()).collect()
+                }
+                ViewMode::POLICY => display_list = self.data.policy.keys(),
+                ViewMode::AUDIT => display_list = self.data.get_audit(),
             }
         } else {
             // TODO: Is there a way to not take a clone of self here? Cannot pass &mut self to
@@ -78,12 +427,78 @@ impl StatefulWidget for KubeWidget {
             display_list = self.display.clone().unwrap();
         }
 
+        // Say why the list is empty, since the filters can hide every event.
+        if self.view_mode == ViewMode::EVENTS && display_list.is_empty() {
+            let placeholder = if self.data.events.list.is_empty() {
+                "no events"
+            } else {
+                "no matching events"
+            };
+            Paragraph::new(placeholder)
+                .style(Style::new().fg(Color::DarkGray))
+                .render(area, buf);
+            return;
+        }
+
+        if self.view_mode == ViewMode::POLICY {
+            render_policy_panel(policy_tables(&self.data.policy, &display_list), area, buf);
+            return;
+        }
+
+        let mut area = area;
+        let details = state
+            .list_state
+            .selected()
+            .and_then(|i| display_list.get(i))
+            .and_then(|name| detail_panel(&self.view_mode, &self.data, name));
+        if let Some((title, lines)) = details {
+            let height = (lines.len() as u16 + 1).min(area.height / 2);
+            let [table_area, details_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(height)]).areas(area);
+
+            Paragraph::new(Text::from(lines))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .title_style(Style::new().bold()),
+                )
+                .wrap(Wrap { trim: true })
+                .render(details_area, buf);
+            area = table_area;
+        }
+
+        if let Some(table) = table_for_view(&self.view_mode, &self.data, &display_list) {
+            let table = table
+                .highlight(&self.search_matches)
+                .mark(
+                    &display_list
+                        .iter()
+                        .filter(|key| {
+                            self.pod_ref(key)
+                                .is_some_and(|pod| state.marks.contains(&pod))
+                        })
+                        .cloned()
+                        .collect(),
+                )
+                .apply_prefs(&self.current_table_prefs());
+            render_bottom_to_top_table(table, area, buf, state);
+            return;
+        }
+
         if self.view_mode == ViewMode::LOGS {
             reverse_list_flag = false;
         }
 
+        let items: Vec<ListItem> = display_list
+            .iter()
+            .map(|entry| match self.search_matches.get(entry) {
+                Some(indices) => ListItem::new(highlight_line(entry, indices, Style::new())),
+                None => ListItem::new(entry.clone()),
+            })
+            .collect();
+
         if reverse_list_flag {
-            list = List::new(display_list)
+            list = List::new(items)
                 .block(block)
                 .style(Style::new().fg(Color::Magenta))
                 .highlight_style(Style::default().bold().white().on_black())
@@ -97,7 +512,7 @@ impl StatefulWidget for KubeWidget {
                 state.list_state.select_first();
             }
         } else {
-            list = List::new(display_list.clone())
+            list = List::new(items)
                 .block(block)
                 .style(Style::new().fg(Color::Magenta))
                 .highlight_style(Style::default().bold().white().on_black())
@@ -110,6 +525,6 @@ impl StatefulWidget for KubeWidget {
             }
         }
 
-        list.render(area, buf, &mut state.list_state);
+        StatefulWidget::render(list, area, buf, &mut state.list_state);
     }
 }
@@ -0,0 +1,455 @@
+/*
+ * Read-only YAML view of a live object: managedFields hidden unless asked for, highlighted,
+ * scrollable and searchable, and copied to the clipboard in one go.
+ */
+
+use std::io::Write;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use kube::{ResourceExt, api::DynamicObject};
+use ratatui::{
+    crossterm::event::KeyEvent,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+};
+
+use crate::line_editor::{EditOutcome, LineEditor};
+
+/// Lines moved by a page up or down.
+pub const PAGE_LINES: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct YamlViewer {
+    pub object: DynamicObject,
+    pub show_managed_fields: bool,
+    /// The manifest as shown, which is also what gets copied.
+    pub manifest: String,
+    lines: Vec<String>,
+    /// Index of the first line in view.
+    pub scroll: usize,
+    pub query: LineEditor,
+    /// Whether keys go to the search query rather than move the view.
+    pub searching: bool,
+    /// Indexes of the lines containing the query.
+    matches: Vec<usize>,
+}
+
+impl YamlViewer {
+    pub fn new(object: DynamicObject) -> Self {
+        let mut viewer = Self {
+            object,
+            show_managed_fields: false,
+            manifest: String::new(),
+            lines: Vec::new(),
+            scroll: 0,
+            query: LineEditor::default(),
+            searching: false,
+            matches: Vec::new(),
+        };
+        viewer.render_manifest();
+        viewer
+    }
+
+    pub fn title(&self) -> String {
+        let kind = self
+            .object
+            .types
+            .as_ref()
+            .map_or("", |types| types.kind.as_str());
+        match self.object.namespace() {
+            Some(namespace) => format!("{} {}/{}", kind, namespace, self.object.name_any()),
+            None => format!("{} {}", kind, self.object.name_any()),
+        }
+    }
+
+    pub fn toggle_managed_fields(&mut self) {
+        self.show_managed_fields = !self.show_managed_fields;
+        self.render_manifest();
+    }
+
+    fn render_manifest(&mut self) {
+        let mut object = self.object.clone();
+        if !self.show_managed_fields {
+            object.metadata.managed_fields = None;
+        }
+
+        self.manifest = serde_yaml::to_string(&object)
+            .unwrap_or_else(|e| format!("# failed to render the object as YAML: {}", e));
+        self.lines = self.manifest.lines().map(String::from).collect();
+        self.update_matches();
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.lines.len().saturating_sub(1));
+    }
+
+    pub fn scroll_to_end(&mut self) {
+        self.scroll = self.lines.len().saturating_sub(1);
+    }
+
+    pub fn start_search(&mut self) {
+        self.searching = true;
+        self.query.clear();
+        self.matches.clear();
+    }
+
+    /// Leave the query, keeping its matches highlighted unless `clear` is set.
+    pub fn stop_search(&mut self, clear: bool) {
+        self.searching = false;
+        if clear {
+            self.query.clear();
+            self.matches.clear();
+        }
+    }
+
+    pub fn handle_search_key(&mut self, key: &KeyEvent) {
+        if self.query.handle_key(key) == EditOutcome::Changed {
+            self.update_matches();
+            self.jump_to_match(true);
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) {
+        self.query.insert_str(text);
+        self.update_matches();
+        self.jump_to_match(true);
+    }
+
+    // Case-insensitive for ASCII only, which keeps byte offsets into the lowercased line valid
+    // for the line itself.
+    fn update_matches(&mut self) {
+        let query = self.query.text().to_ascii_lowercase();
+        self.matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.to_ascii_lowercase().contains(&query))
+                .map(|(i, _)| i)
+                .collect()
+        };
+    }
+
+    /// Scroll to the next match below the top line in view, or the last one above it, wrapping
+    /// around at either end.
+    pub fn next_match(&mut self, forward: bool) {
+        if forward {
+            self.scroll += 1;
+        }
+        self.jump_to_match(forward);
+    }
+
+    fn jump_to_match(&mut self, forward: bool) {
+        let target = if forward {
+            self.matches
+                .iter()
+                .find(|&&i| i >= self.scroll)
+                .or(self.matches.first())
+        } else {
+            self.matches
+                .iter()
+                .rev()
+                .find(|&&i| i < self.scroll)
+                .or(self.matches.last())
+        };
+        if let Some(&line) = target {
+            self.scroll = line;
+        }
+    }
+
+    /// The lines filling a pane `height` rows high, followed by the search prompt and the key
+    /// help. The scroll is clamped so the end of the manifest does not leave the pane half
+    /// empty.
+    pub fn lines(&mut self, height: usize) -> Vec<Line<'static>> {
+        let visible = height.saturating_sub(2);
+        self.scroll = self.scroll.min(self.lines.len().saturating_sub(visible));
+
+        let query = self.query.text().to_ascii_lowercase();
+        let mut lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(visible)
+            .map(|line| {
+                let ranges: Vec<(usize, usize)> = if query.is_empty() {
+                    Vec::new()
+                } else {
+                    line.to_ascii_lowercase()
+                        .match_indices(&query)
+                        .map(|(start, found)| (start, start + found.len()))
+                        .collect()
+                };
+                Line::from(overlay_matches(yaml_segments(line), &ranges))
+            })
+            .collect();
+        lines.resize(visible, Line::from(""));
+
+        let prompt = if self.searching || !self.query.is_empty() {
+            let mut prompt = if self.searching {
+                self.query.line(Style::new())
+            } else {
+                Line::from(self.query.text().to_owned())
+            };
+            prompt.spans.insert(0, Span::from("/").bold());
+            prompt.spans.push(Span::styled(
+                format!("  {} matching lines", self.matches.len()),
+                Style::new().fg(Color::DarkGray),
+            ));
+            prompt
+        } else {
+            Line::from(format!(
+                "line {}/{}",
+                (self.scroll + 1).min(self.lines.len()),
+                self.lines.len()
+            ))
+            .fg(Color::DarkGray)
+        };
+        lines.push(prompt);
+
+        let help = if self.searching {
+            "[Enter] keep    [Esc] clear".to_owned()
+        } else {
+            format!(
+                "[j/k] scroll  [/] search  [n/N] next/prev  [m] managedFields: {}  [c] copy  [Esc] close",
+                if self.show_managed_fields {
+                    "shown"
+                } else {
+                    "hidden"
+                }
+            )
+        };
+        lines.push(Line::from(help).italic());
+
+        lines
+    }
+}
+
+/// Put `text` on the clipboard with an OSC 52 escape, which the terminal handles itself, so
+/// copying works over SSH and without a clipboard tool installed.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
+
+/// Split one line of YAML into styled segments: keys, scalars by type, list markers and
+/// comments. Lines inside block scalars are highlighted as if they were YAML too.
+fn yaml_segments(line: &str) -> Vec<(String, Style)> {
+    let punctuation = Style::new().fg(Color::DarkGray);
+
+    let text = line.trim_start();
+    let mut segments = vec![(line[..line.len() - text.len()].to_owned(), Style::new())];
+    if text.starts_with('#') || text == "---" {
+        segments.push((text.to_owned(), punctuation.italic()));
+        return segments;
+    }
+
+    let mut rest = text;
+    while let Some(item) = rest.strip_prefix("- ") {
+        segments.push(("- ".to_owned(), Style::new().fg(Color::Yellow)));
+        rest = item;
+    }
+    if rest == "-" {
+        segments.push((rest.to_owned(), Style::new().fg(Color::Yellow)));
+        return segments;
+    }
+
+    if let Some(colon) = key_end(rest) {
+        segments.push((rest[..colon].to_owned(), Style::new().fg(Color::Cyan)));
+        segments.push((":".to_owned(), punctuation));
+        rest = &rest[colon + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push((rest.to_owned(), scalar_style(rest.trim())));
+    }
+    segments
+}
+
+// The colon ending a mapping key: the first one followed by a space or the end of the line,
+// after the closing quote of a quoted key.
+fn key_end(text: &str) -> Option<usize> {
+    let start = match text.chars().next() {
+        Some(quote @ ('"' | '\'')) => text[1..].find(quote)? + 2,
+        _ => 0,
+    };
+    text[start..]
+        .find(": ")
+        .map(|i| i + start)
+        .or_else(|| text.ends_with(':').then(|| text.len() - 1))
+}
+
+fn scalar_style(value: &str) -> Style {
+    match value {
+        "|" | "|-" | "|+" | ">" | ">-" | ">+" | "{}" | "[]" => Style::new().fg(Color::DarkGray),
+        "true" | "false" | "null" | "~" => Style::new().fg(Color::Magenta),
+        _ if value.parse::<f64>().is_ok() => Style::new().fg(Color::Magenta),
+        _ => Style::new().fg(Color::Green),
+    }
+}
+
+/// Restyle the byte `ranges` of the line the segments make up, splitting segments where a range
+/// starts or ends inside one.
+fn overlay_matches(
+    segments: Vec<(String, Style)>,
+    ranges: &[(usize, usize)],
+) -> Vec<Span<'static>> {
+    let highlight = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for (text, style) in segments {
+        let end = offset + text.len();
+        let mut cut = offset;
+        for &(start, stop) in ranges.iter().filter(|(s, e)| *s < end && *e > offset) {
+            let (start, stop) = (start.max(offset), stop.min(end));
+            if start > cut {
+                spans.push(Span::styled(
+                    text[cut - offset..start - offset].to_owned(),
+                    style,
+                ));
+            }
+            spans.push(Span::styled(
+                text[start - offset..stop - offset].to_owned(),
+                style.patch(highlight),
+            ));
+            cut = stop;
+        }
+        if cut < end {
+            spans.push(Span::styled(text[cut - offset..].to_owned(), style));
+        }
+        offset = end;
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(segments: &[(String, Style)]) -> Vec<&str> {
+        segments.iter().map(|(text, _)| text.as_str()).collect()
+    }
+
+    // The spans of `line` with `ranges` highlighted, as (text, highlighted) pairs.
+    fn highlighted(line: &str, ranges: &[(usize, usize)]) -> Vec<(String, bool)> {
+        overlay_matches(yaml_segments(line), ranges)
+            .into_iter()
+            .map(|span| {
+                (
+                    span.content.to_string(),
+                    span.style.bg == Some(Color::Yellow),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_colon_ending_a_key() {
+        assert_eq!(key_end("name: web"), Some(4));
+        assert_eq!(key_end("metadata:"), Some(8));
+        assert_eq!(key_end("image: nginx:1.27"), Some(5));
+        assert_eq!(key_end("url: http://example.com"), Some(3));
+        assert_eq!(key_end("nginx:1.27"), None);
+        assert_eq!(key_end("plain value"), None);
+    }
+
+    #[test]
+    fn finds_the_colon_after_a_quoted_key() {
+        assert_eq!(key_end(r#""app.kubernetes.io/name": web"#), Some(24));
+        assert_eq!(key_end(r#""a: b": c"#), Some(6));
+        assert_eq!(key_end("'it: is':"), Some(8));
+        // A quoted scalar, or a quote that never closes, is no key at all.
+        assert_eq!(key_end(r#""just: a value""#), None);
+        assert_eq!(key_end(r#""unclosed: key"#), None);
+    }
+
+    #[test]
+    fn splits_keys_and_scalars() {
+        let segments = yaml_segments("  replicas: 3");
+        assert_eq!(texts(&segments), vec!["  ", "replicas", ":", " 3"]);
+        assert_eq!(segments[1].1, Style::new().fg(Color::Cyan));
+        assert_eq!(segments[3].1, Style::new().fg(Color::Magenta));
+
+        let segments = yaml_segments(r#"  "app.kubernetes.io/name": web"#);
+        assert_eq!(
+            texts(&segments),
+            vec!["  ", r#""app.kubernetes.io/name""#, ":", " web"]
+        );
+        assert_eq!(segments[3].1, Style::new().fg(Color::Green));
+
+        assert_eq!(texts(&yaml_segments("spec:")), vec!["", "spec", ":"]);
+        assert_eq!(
+            texts(&yaml_segments("  # a comment: really")),
+            vec!["  ", "# a comment: really"]
+        );
+    }
+
+    #[test]
+    fn splits_list_items() {
+        assert_eq!(
+            texts(&yaml_segments("  - name: app")),
+            vec!["  ", "- ", "name", ":", " app"]
+        );
+        assert_eq!(texts(&yaml_segments("- - x")), vec!["", "- ", "- ", "x"]);
+        assert_eq!(texts(&yaml_segments("  -")), vec!["  ", "-"]);
+
+        let segments = yaml_segments("- --verbose");
+        assert_eq!(texts(&segments), vec!["", "- ", "--verbose"]);
+        assert_eq!(segments[2].1, Style::new().fg(Color::Green));
+    }
+
+    #[test]
+    fn highlights_matches_inside_a_segment() {
+        assert_eq!(
+            highlighted("image: nginx", &[(8, 10)]),
+            vec![
+                ("image".to_owned(), false),
+                (":".to_owned(), false),
+                (" n".to_owned(), false),
+                ("gi".to_owned(), true),
+                ("nx".to_owned(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlights_matches_across_segments() {
+        // "me: w" starts in the key and ends in the value.
+        assert_eq!(
+            highlighted("  name: web", &[(4, 9)]),
+            vec![
+                ("  ".to_owned(), false),
+                ("na".to_owned(), false),
+                ("me".to_owned(), true),
+                (":".to_owned(), true),
+                (" w".to_owned(), true),
+                ("eb".to_owned(), false),
+            ]
+        );
+        assert_eq!(
+            highlighted("- a: a", &[(2, 3), (5, 6)]),
+            vec![
+                ("- ".to_owned(), false),
+                ("a".to_owned(), true),
+                (":".to_owned(), false),
+                (" ".to_owned(), false),
+                ("a".to_owned(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_line_intact() {
+        for line in ["  - name: app", r#""a: b": c"#, "plain", "", "  # note"] {
+            let text: String = highlighted(line, &[(0, line.len().min(3))])
+                .into_iter()
+                .map(|(text, _)| text)
+                .collect();
+            assert_eq!(text, line);
+        }
+    }
+}